@@ -0,0 +1,184 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+};
+use cw2::set_contract_version;
+use milky_way::ownership::OwnershipMsg;
+use semver::Version;
+use staking::msg::{IBCLifecycleComplete, SudoMsg};
+
+use crate::error::{ContractError, ContractResult};
+use crate::execute::{
+    execute_claim_funds, execute_liquid_stake, execute_liquid_unstake, execute_ownership,
+    execute_update_allowed_source, execute_update_config, execute_withdraw, handle_transfer_reply,
+    save_allowed_source,
+};
+use crate::ibc::receive_lifecycle;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::query::{query_batch_requests, query_claimable_funds, query_config, query_ownership};
+use crate::state::{Config, State, CONFIG, OWNERSHIP, STATE, TRANSFER_WAITING_FOR_REPLY};
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let admin = msg
+        .admin
+        .map(|admin_str| deps.api.addr_validate(&admin_str))
+        .transpose()?
+        .unwrap_or(info.sender.clone());
+    OWNERSHIP.set_owner(deps.branch(), Some(admin.clone()))?;
+
+    STATE.save(
+        deps.storage,
+        &State {
+            transfer_id_counter: 0,
+        },
+    )?;
+
+    let config = Config {
+        staking_contract: deps.api.addr_validate(&msg.staking_contract)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    for source in msg.allowed_sources {
+        save_allowed_source(deps.branch(), source.channel_id, source.prefixes)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", admin)
+        .add_attribute("staking_contract", config.staking_contract))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response> {
+    match msg {
+        ExecuteMsg::LiquidStake {
+            channel_id,
+            sender,
+            expected_mint_amount,
+        } => execute_liquid_stake(deps, info, channel_id, sender, expected_mint_amount),
+        ExecuteMsg::LiquidUnstake { channel_id, sender } => {
+            execute_liquid_unstake(deps, info, channel_id, sender)
+        }
+        ExecuteMsg::Withdraw { batch_id, limit } => execute_withdraw(deps, env, batch_id, limit),
+        ExecuteMsg::ClaimFunds {} => execute_claim_funds(deps, info),
+        ExecuteMsg::TransferOwnership { new_owner } => execute_ownership(
+            deps,
+            env,
+            info,
+            OwnershipMsg::TransferOwnership { new_owner },
+        ),
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_ownership(deps, env, info, OwnershipMsg::AcceptOwnership {})
+        }
+        ExecuteMsg::RevokeOwnershipTransfer {} => {
+            execute_ownership(deps, env, info, OwnershipMsg::RevokeOwnershipTransfer {})
+        }
+        ExecuteMsg::UpdateOwnershipConfig {
+            transfer_delay,
+            transfer_expiry,
+        } => execute_ownership(
+            deps,
+            env,
+            info,
+            OwnershipMsg::UpdateOwnershipConfig {
+                transfer_delay,
+                transfer_expiry,
+            },
+        ),
+        ExecuteMsg::RenounceOwnership { confirm } => {
+            execute_ownership(deps, env, info, OwnershipMsg::RenounceOwnership { confirm })
+        }
+        ExecuteMsg::UpdateConfig { staking_contract } => {
+            execute_update_config(deps, info, staking_contract)
+        }
+        ExecuteMsg::UpdateAllowedSource {
+            channel_id,
+            prefixes,
+        } => execute_update_allowed_source(deps, info, channel_id, prefixes),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::BatchRequests {
+            batch_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_batch_requests(deps, batch_id, start_after, limit)?),
+        QueryMsg::ClaimableFunds { address } => {
+            to_json_binary(&query_claimable_funds(deps, address)?)
+        }
+        QueryMsg::Ownership {} => to_json_binary(&query_ownership(deps)?),
+    }
+    .map_err(ContractError::from)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> ContractResult<Response> {
+    match msg {
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCAck {
+            channel,
+            sequence,
+            success,
+            ..
+        }) => receive_lifecycle(deps, channel, sequence, success),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout { channel, sequence }) => {
+            receive_lifecycle(deps, channel, sequence, false)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> ContractResult<Response> {
+    if TRANSFER_WAITING_FOR_REPLY.has(deps.storage, reply.id) {
+        return handle_transfer_reply(deps, reply);
+    }
+    Err(ContractError::InvalidReplyID { id: reply.id })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
+    let current_version = cw2::get_contract_version(deps.storage)?;
+    if CONTRACT_NAME != current_version.contract.as_str() {
+        return Err(StdError::generic_err("Cannot upgrade to a different contract").into());
+    }
+
+    let version: Version = current_version
+        .version
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid contract version"))?;
+    let new_version: Version = CONTRACT_VERSION
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid contract version"))?;
+
+    if version > new_version {
+        return Err(StdError::generic_err("Cannot upgrade to a previous contract version").into());
+    }
+    if version == new_version {
+        return Err(StdError::generic_err("Cannot migrate to the same version.").into());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", current_version.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
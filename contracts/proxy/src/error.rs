@@ -0,0 +1,49 @@
+use cosmwasm_std::StdError;
+use cw_controllers::AdminError;
+use cw_utils::PaymentError;
+use milky_way::ownership::OwnershipError;
+use thiserror::Error;
+
+pub type ContractResult<T> = core::result::Result<T, ContractError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized: {sender}")]
+    Unauthorized { sender: String },
+
+    #[error("Admin error: {0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Payment error: {0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Channel {channel_id} is not an allowed source")]
+    SourceNotAllowed { channel_id: String },
+
+    #[error("Sender {sender} is not allowed on channel {channel_id}")]
+    SenderNotAllowed { channel_id: String, sender: String },
+
+    #[error("Invalid channel id {channel_id}")]
+    InvalidChannelId { channel_id: String },
+
+    #[error("Batch {batch_id} has not been received yet")]
+    BatchNotReceived { batch_id: u64 },
+
+    #[error("No unstake requests left in batch {batch_id}")]
+    NoRequestsInBatch { batch_id: u64 },
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("IBC transfer failed: {msg}")]
+    FailedIBCTransfer { msg: String },
+
+    #[error("Invalid reply id {id}")]
+    InvalidReplyID { id: u64 },
+}
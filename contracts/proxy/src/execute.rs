@@ -0,0 +1,426 @@
+use cosmwasm_std::{
+    attr, to_json_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    ReplyOn, Response, StdError, SubMsg, SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
+};
+use cw_utils::must_pay;
+use milky_way::ownership::OwnershipMsg;
+use osmosis_std::types::{
+    cosmos::base::v1beta1::Coin,
+    ibc::applications::transfer::v1::{MsgTransfer, MsgTransferResponse},
+};
+use prost::Message;
+use staking::contract::{IBC_TIMEOUT, OSMOSIS_ACCOUNT_PREFIX};
+use staking::helpers::{convert_address_prefix, derive_intermediate_sender, is_valid_channel_id};
+use staking::msg::{
+    BatchResponse, ConfigResponse as StakingConfigResponse, ExecuteMsg as StakingExecuteMsg,
+    QueryMsg as StakingQueryMsg, WithdrawalResponse,
+};
+use staking::state::IbcForward;
+
+use crate::error::{ContractError, ContractResult};
+use crate::state::{
+    ProxyBatch, ReturnTransfer, UnstakeRequest, ALLOWED_SOURCES, CLAIMABLE_FUNDS, CONFIG,
+    INFLIGHT_TRANSFERS, OWNERSHIP, PROXY_BATCHES, STATE, TRANSFER_WAITING_FOR_REPLY,
+    UNSTAKE_REQUESTS,
+};
+
+const DEFAULT_WITHDRAW_LIMIT: u32 = 30;
+
+// Checks that the hook arrived on an allowed channel from an allowed account and that
+// the claimed sender is the one the hook was derived from.
+// Returns the osmosis address of the sender, used as fallback if a transfer fails
+fn validate_source(
+    deps: Deps,
+    info: &MessageInfo,
+    channel_id: &str,
+    sender: &str,
+) -> ContractResult<Addr> {
+    let prefixes = ALLOWED_SOURCES
+        .may_load(deps.storage, channel_id.to_string())?
+        .ok_or(ContractError::SourceNotAllowed {
+            channel_id: channel_id.to_string(),
+        })?;
+
+    let sender_not_allowed = || ContractError::SenderNotAllowed {
+        channel_id: channel_id.to_string(),
+        sender: sender.to_string(),
+    };
+    let (prefix, _, _) = bech32::decode(sender).map_err(|_| sender_not_allowed())?;
+    if !prefixes.contains(&prefix) {
+        return Err(sender_not_allowed());
+    }
+
+    // IBC hooks execute the contract from an address derived from the channel and
+    // the original sender, so it can't be spoofed by a different account
+    let intermediate_sender =
+        derive_intermediate_sender(channel_id, sender, OSMOSIS_ACCOUNT_PREFIX)
+            .map_err(|_| sender_not_allowed())?;
+    if intermediate_sender != info.sender.as_str() {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+
+    Ok(Addr::unchecked(convert_address_prefix(
+        sender,
+        OSMOSIS_ACCOUNT_PREFIX,
+    )?))
+}
+
+// The minted stTIA is sent back by the staking contract, failed transfers can be
+// claimed there by the osmosis address of the sender
+pub fn execute_liquid_stake(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel_id: String,
+    sender: String,
+    expected_mint_amount: Option<Uint128>,
+) -> ContractResult<Response> {
+    let fallback_address = validate_source(deps.as_ref(), &info, &channel_id, &sender)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let stake_msg = WasmMsg::Execute {
+        contract_addr: config.staking_contract.to_string(),
+        msg: to_json_binary(&StakingExecuteMsg::LiquidStake {
+            mint_to: Some(fallback_address.to_string()),
+            expected_mint_amount,
+            forward: Some(IbcForward {
+                channel_id: channel_id.clone(),
+                receiver: sender.clone(),
+            }),
+        })?,
+        funds: info.funds,
+    };
+
+    Ok(Response::new()
+        .add_message(stake_msg)
+        .add_attribute("action", "liquid_stake")
+        .add_attribute("channel", channel_id)
+        .add_attribute("sender", sender))
+}
+
+// The request is unstaked in the name of the proxy and tracked per sender, so the
+// withdrawal can be split between them once the batch was received
+pub fn execute_liquid_unstake(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel_id: String,
+    sender: String,
+) -> ContractResult<Response> {
+    validate_source(deps.as_ref(), &info, &channel_id, &sender)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let staking_config: StakingConfigResponse = deps.querier.query_wasm_smart(
+        config.staking_contract.to_string(),
+        &StakingQueryMsg::Config {},
+    )?;
+    let amount = must_pay(&info, &staking_config.liquid_stake_token_denom)?;
+    let pending_batch: BatchResponse = deps.querier.query_wasm_smart(
+        config.staking_contract.to_string(),
+        &StakingQueryMsg::PendingBatch {},
+    )?;
+
+    UNSTAKE_REQUESTS.update(
+        deps.storage,
+        (pending_batch.id, sender.clone()),
+        |request| -> Result<UnstakeRequest, ContractError> {
+            match request {
+                Some(mut request) => {
+                    request.amount += amount;
+                    Ok(request)
+                }
+                None => Ok(UnstakeRequest {
+                    channel_id: channel_id.clone(),
+                    amount,
+                }),
+            }
+        },
+    )?;
+    PROXY_BATCHES.update(
+        deps.storage,
+        pending_batch.id,
+        |batch| -> Result<ProxyBatch, ContractError> {
+            let mut batch = batch.unwrap_or(ProxyBatch {
+                total_unstaked: Uint128::zero(),
+                withdrawn: None,
+            });
+            batch.total_unstaked += amount;
+            Ok(batch)
+        },
+    )?;
+
+    let unstake_msg = WasmMsg::Execute {
+        contract_addr: config.staking_contract.to_string(),
+        msg: to_json_binary(&StakingExecuteMsg::LiquidUnstake { owner: None })?,
+        funds: info.funds,
+    };
+
+    Ok(Response::new()
+        .add_message(unstake_msg)
+        .add_attribute("action", "liquid_unstake")
+        .add_attribute("channel", channel_id)
+        .add_attribute("sender", sender)
+        .add_attribute("batch", pending_batch.id.to_string())
+        .add_attribute("amount", amount))
+}
+
+// Permissionless, the first call withdraws the proxy's share from the staking contract.
+// The withdrawal is split pro rata between the requests and sent back over IBC
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    batch_id: u64,
+    limit: Option<u32>,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut batch = PROXY_BATCHES
+        .may_load(deps.storage, batch_id)?
+        .ok_or(ContractError::NoRequestsInBatch { batch_id })?;
+
+    let staking_config: StakingConfigResponse = deps.querier.query_wasm_smart(
+        config.staking_contract.to_string(),
+        &StakingQueryMsg::Config {},
+    )?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let withdrawn = match batch.withdrawn {
+        Some(withdrawn) => withdrawn,
+        None => {
+            let staking_batch: BatchResponse = deps.querier.query_wasm_smart(
+                config.staking_contract.to_string(),
+                &StakingQueryMsg::Batch { id: batch_id },
+            )?;
+            if staking_batch.status != "received" {
+                return Err(ContractError::BatchNotReceived { batch_id });
+            }
+
+            // the staking contract reports what it pays for the proxy's request,
+            // after the redemption fee it withholds
+            let withdrawal: WithdrawalResponse = deps.querier.query_wasm_smart(
+                config.staking_contract.to_string(),
+                &StakingQueryMsg::Withdrawal {
+                    batch_id,
+                    user: env.contract.address.to_string(),
+                },
+            )?;
+            let withdrawn = withdrawal.amount;
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: config.staking_contract.to_string(),
+                    msg: to_json_binary(&StakingExecuteMsg::Withdraw { batch_id })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+            batch.withdrawn = Some(withdrawn);
+            PROXY_BATCHES.save(deps.storage, batch_id, &batch)?;
+            withdrawn
+        }
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_WITHDRAW_LIMIT) as usize;
+    let requests = UNSTAKE_REQUESTS
+        .prefix(batch_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut state = STATE.load(deps.storage)?;
+    let mut sub_msgs: Vec<SubMsg> = vec![];
+    for (sender, request) in requests.iter() {
+        UNSTAKE_REQUESTS.remove(deps.storage, (batch_id, sender.clone()));
+
+        let amount = withdrawn.multiply_ratio(request.amount, batch.total_unstaked);
+        if amount.is_zero() {
+            continue;
+        }
+
+        state.transfer_id_counter += 1;
+        let transfer = ReturnTransfer {
+            channel_id: request.channel_id.clone(),
+            receiver: sender.clone(),
+            fallback_address: Addr::unchecked(convert_address_prefix(
+                sender,
+                OSMOSIS_ACCOUNT_PREFIX,
+            )?),
+            denom: staking_config.native_token_denom.clone(),
+            amount,
+        };
+        sub_msgs.push(transfer_sub_msg(
+            deps.storage,
+            &env,
+            transfer,
+            state.transfer_id_counter,
+        )?);
+    }
+    STATE.save(deps.storage, &state)?;
+
+    let remaining = UNSTAKE_REQUESTS
+        .prefix(batch_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if !remaining {
+        PROXY_BATCHES.remove(deps.storage, batch_id);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_submessages(sub_msgs)
+        .add_attribute("action", "withdraw")
+        .add_attribute("batch", batch_id.to_string())
+        .add_attribute("withdrawn", withdrawn)
+        .add_attribute("processed_requests", requests.len().to_string())
+        .add_attribute("completed", (!remaining).to_string()))
+}
+
+fn transfer_sub_msg(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    transfer: ReturnTransfer,
+    sub_msg_id: u64,
+) -> ContractResult<SubMsg> {
+    let ibc_msg = MsgTransfer {
+        source_channel: transfer.channel_id.clone(),
+        source_port: "transfer".to_string(),
+        token: Some(Coin {
+            denom: transfer.denom.clone(),
+            amount: transfer.amount.to_string(),
+        }),
+        receiver: transfer.receiver.clone(),
+        sender: env.contract.address.to_string(),
+        timeout_height: None,
+        timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
+        memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
+    };
+
+    TRANSFER_WAITING_FOR_REPLY.save(storage, sub_msg_id, &transfer)?;
+
+    Ok(SubMsg {
+        id: sub_msg_id,
+        msg: ibc_msg.into(),
+        gas_limit: None,
+        reply_on: ReplyOn::Always,
+    })
+}
+
+pub fn handle_transfer_reply(deps: DepsMut, msg: Reply) -> ContractResult<Response> {
+    let SubMsgResult::Ok(SubMsgResponse { data: Some(b), .. }) = msg.result else {
+        return Err(ContractError::FailedIBCTransfer {
+            msg: format!("failed reply: {:?}", msg.result),
+        });
+    };
+
+    let transfer_response =
+        MsgTransferResponse::decode(&b[..]).map_err(|_e| ContractError::FailedIBCTransfer {
+            msg: format!("could not decode response: {b}"),
+        })?;
+
+    let transfer = TRANSFER_WAITING_FOR_REPLY.load(deps.storage, msg.id)?;
+    TRANSFER_WAITING_FOR_REPLY.remove(deps.storage, msg.id);
+
+    // Save as in-flight to be able to credit the fallback address if the transfer fails
+    INFLIGHT_TRANSFERS.save(
+        deps.storage,
+        (transfer.channel_id.clone(), transfer_response.sequence),
+        &transfer,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "handle_transfer_reply")
+        .add_attribute("channel", transfer.channel_id)
+        .add_attribute("packet_sequence", transfer_response.sequence.to_string()))
+}
+
+// Sends all funds of failed transfers held for the sender
+pub fn execute_claim_funds(deps: DepsMut, info: MessageInfo) -> ContractResult<Response> {
+    let claimable = CLAIMABLE_FUNDS
+        .prefix(info.sender.to_string())
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if claimable.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let mut coins = vec![];
+    for (denom, amount) in claimable {
+        CLAIMABLE_FUNDS.remove(deps.storage, (info.sender.to_string(), denom.clone()));
+        coins.push(cosmwasm_std::Coin::new(amount.u128(), denom));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_funds")
+        .add_attribute("receiver", info.sender.to_string())
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins,
+        }))
+}
+
+// Ownership messages are handled by the shared ownership component
+pub fn execute_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: OwnershipMsg,
+) -> ContractResult<Response> {
+    Ok(OWNERSHIP.execute(deps, env, info, msg)?)
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking_contract: Option<String>,
+) -> ContractResult<Response> {
+    OWNERSHIP.assert_owner(deps.as_ref(), &info.sender)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender);
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(staking_contract) = staking_contract {
+        config.staking_contract = deps.api.addr_validate(&staking_contract)?;
+        response = response.add_attribute("staking_contract", staking_contract);
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+pub fn execute_update_allowed_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel_id: String,
+    prefixes: Vec<String>,
+) -> ContractResult<Response> {
+    OWNERSHIP.assert_owner(deps.as_ref(), &info.sender)?;
+
+    save_allowed_source(deps, channel_id.clone(), prefixes.clone())?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_allowed_source"),
+        attr("channel", channel_id),
+        attr("prefixes", prefixes.join(",")),
+    ]))
+}
+
+pub fn save_allowed_source(
+    deps: DepsMut,
+    channel_id: String,
+    prefixes: Vec<String>,
+) -> ContractResult<()> {
+    if !is_valid_channel_id(&channel_id) {
+        return Err(ContractError::InvalidChannelId { channel_id });
+    }
+    if prefixes.is_empty() {
+        ALLOWED_SOURCES.remove(deps.storage, channel_id);
+        return Ok(());
+    }
+    if prefixes.iter().any(|prefix| prefix.is_empty()) {
+        return Err(StdError::generic_err("Invalid prefix").into());
+    }
+    ALLOWED_SOURCES.save(deps.storage, channel_id, &prefixes)?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::{DepsMut, Response, Uint128};
+
+use crate::error::ContractError;
+use crate::state::{CLAIMABLE_FUNDS, INFLIGHT_TRANSFERS};
+
+/// Called by the chain when the ack or timeout for a transfer sent back to the origin
+/// of a request has been received.
+///
+/// Transfers are no longer tracked once they completed. If the transfer failed, the
+/// tokens are back in the contract and can be claimed by the osmosis address of the
+/// receiver with `ClaimFunds`.
+pub fn receive_lifecycle(
+    deps: DepsMut,
+    source_channel: String,
+    sequence: u64,
+    success: bool,
+) -> Result<Response, ContractError> {
+    let response = Response::new()
+        .add_attribute("action", "receive_lifecycle")
+        .add_attribute("channel", source_channel.clone())
+        .add_attribute("sequence", sequence.to_string());
+
+    let key = (source_channel, sequence);
+    let Some(transfer) = INFLIGHT_TRANSFERS.may_load(deps.storage, key.clone())? else {
+        return Ok(response.add_attribute("msg", "received unexpected lifecycle"));
+    };
+    INFLIGHT_TRANSFERS.remove(deps.storage, key);
+
+    if success {
+        return Ok(response.add_attribute("msg", "success"));
+    }
+
+    CLAIMABLE_FUNDS.update(
+        deps.storage,
+        (
+            transfer.fallback_address.to_string(),
+            transfer.denom.clone(),
+        ),
+        |claimable| -> Result<Uint128, ContractError> {
+            Ok(claimable.unwrap_or_default() + transfer.amount)
+        },
+    )?;
+
+    Ok(response
+        .add_attribute("error", "ibc transfer failed")
+        .add_attribute("claimable_by", transfer.fallback_address)
+        .add_attribute("amount", transfer.amount)
+        .add_attribute("denom", transfer.denom))
+}
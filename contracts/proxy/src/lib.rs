@@ -0,0 +1,10 @@
+pub mod contract;
+pub mod error;
+pub mod execute;
+pub mod ibc;
+pub mod msg;
+pub mod query;
+pub mod state;
+
+#[cfg(test)]
+pub mod tests;
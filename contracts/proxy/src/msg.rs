@@ -0,0 +1,105 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, Uint128};
+use milky_way::ownership::OwnershipResponse;
+
+use crate::state::AllowedSource;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub admin: Option<String>,
+    /// Staking contract the deposits and unstake requests are sent to
+    pub staking_contract: String,
+    /// Channels IBC hooks are accepted from
+    pub allowed_sources: Vec<AllowedSource>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Liquid stakes the sent TIA for `sender`, called through an IBC hook on `channel_id`.
+    /// The minted stTIA is sent back to `sender` over the same channel.
+    LiquidStake {
+        /// IBC channel id on Osmosis the hook arrived on
+        channel_id: String,
+        /// Address on the source chain that sent the hook
+        sender: String,
+        expected_mint_amount: Option<Uint128>,
+    },
+    /// Liquid unstakes the sent stTIA for `sender`, called through an IBC hook on
+    /// `channel_id`. The TIA is sent back to `sender` once the batch was received.
+    LiquidUnstake {
+        /// IBC channel id on Osmosis the hook arrived on
+        channel_id: String,
+        /// Address on the source chain that sent the hook
+        sender: String,
+    },
+    /// Withdraws the proxy's share of a received batch and sends it back to the
+    /// origin of each request, at most `limit` requests per call
+    Withdraw {
+        batch_id: u64,
+        limit: Option<u32>,
+    },
+    /// Claims the tokens of failed transfers held for the sender
+    ClaimFunds {},
+    TransferOwnership {
+        new_owner: String,
+    },
+    AcceptOwnership {},
+    RevokeOwnershipTransfer {},
+    /// Sets how long a pending owner has to wait before accepting
+    /// the ownership, and how long they can accept it for.
+    UpdateOwnershipConfig {
+        transfer_delay: Option<u64>,
+        transfer_expiry: Option<u64>,
+    },
+    /// Removes the owner for good.
+    RenounceOwnership {
+        /// Has to be the contract address.
+        confirm: String,
+    },
+    /// Updates the contract configuration.
+    UpdateConfig {
+        /// Optional new staking contract address.
+        staking_contract: Option<String>,
+    },
+    /// Sets the prefixes allowed on a channel, an empty list removes the channel
+    UpdateAllowedSource {
+        channel_id: String,
+        prefixes: Vec<String>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(Vec<UnstakeRequestResponse>)]
+    BatchRequests {
+        batch_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<Coin>)]
+    ClaimableFunds { address: String },
+    /// Owner, pending owner and when the pending owner can accept the ownership
+    #[returns(OwnershipResponse)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub admin: Option<Addr>,
+    pub staking_contract: Addr,
+    pub allowed_sources: Vec<AllowedSource>,
+}
+
+#[cw_serde]
+pub struct UnstakeRequestResponse {
+    pub batch_id: u64,
+    pub sender: String,
+    pub channel_id: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
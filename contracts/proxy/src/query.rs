@@ -0,0 +1,71 @@
+use cosmwasm_std::{Coin, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+use milky_way::ownership::OwnershipResponse;
+
+use crate::{
+    msg::{ConfigResponse, UnstakeRequestResponse},
+    state::{AllowedSource, ALLOWED_SOURCES, CLAIMABLE_FUNDS, CONFIG, OWNERSHIP, UNSTAKE_REQUESTS},
+};
+
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let admin = OWNERSHIP.owner(deps)?;
+    let allowed_sources = ALLOWED_SOURCES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|source| {
+            source.map(|(channel_id, prefixes)| AllowedSource {
+                channel_id,
+                prefixes,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ConfigResponse {
+        admin,
+        staking_contract: config.staking_contract,
+        allowed_sources,
+    })
+}
+
+pub fn query_batch_requests(
+    deps: Deps,
+    batch_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<UnstakeRequestResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    UNSTAKE_REQUESTS
+        .prefix(batch_id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|request| {
+            request.map(|(sender, request)| UnstakeRequestResponse {
+                batch_id,
+                sender,
+                channel_id: request.channel_id,
+                amount: request.amount,
+            })
+        })
+        .collect()
+}
+
+pub fn query_claimable_funds(deps: Deps, address: String) -> StdResult<Vec<Coin>> {
+    CLAIMABLE_FUNDS
+        .prefix(address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|claimable| claimable.map(|(denom, amount)| Coin::new(amount.u128(), denom)))
+        .collect()
+}
+
+pub fn query_ownership(deps: Deps) -> StdResult<OwnershipResponse> {
+    OWNERSHIP.query(deps)
+}
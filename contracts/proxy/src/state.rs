@@ -0,0 +1,71 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use milky_way::ownership::Ownership;
+
+#[cw_serde]
+pub struct Config {
+    /// Staking contract the deposits and unstake requests are sent to
+    pub staking_contract: Addr,
+}
+
+#[cw_serde]
+pub struct State {
+    pub transfer_id_counter: u64,
+}
+
+/// A channel IBC hooks are accepted from together with the bech32 prefixes of the
+/// senders allowed on it
+#[cw_serde]
+pub struct AllowedSource {
+    /// IBC channel id on Osmosis to the source chain
+    pub channel_id: String,
+    /// Bech32 prefixes of the accounts on the source chain
+    pub prefixes: Vec<String>,
+}
+
+/// Unstake request of a remote account, aggregated into the proxy's request in the
+/// staking contract
+#[cw_serde]
+pub struct UnstakeRequest {
+    /// Channel the request arrived on, the withdrawal is sent back over it
+    pub channel_id: String,
+    /// Amount of stTIA unstaked
+    pub amount: Uint128,
+}
+
+/// The proxy's share of a staking batch
+#[cw_serde]
+pub struct ProxyBatch {
+    /// Total stTIA unstaked through the proxy in the batch
+    pub total_unstaked: Uint128,
+    /// Native tokens withdrawn from the staking contract, set once the batch was received
+    pub withdrawn: Option<Uint128>,
+}
+
+/// Tokens sent back to the origin chain of a request
+#[cw_serde]
+pub struct ReturnTransfer {
+    pub channel_id: String,
+    pub receiver: String,
+    /// Osmosis address of the receiver that can claim the tokens if the transfer fails
+    pub fallback_address: Addr,
+    pub denom: String,
+    pub amount: Uint128,
+}
+
+pub const OWNERSHIP: Ownership = Ownership::new("admin", "pending_ownership", "ownership_config");
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STATE: Item<State> = Item::new("state");
+
+// channel id -> allowed bech32 prefixes
+pub const ALLOWED_SOURCES: Map<String, Vec<String>> = Map::new("allowed_sources");
+// (batch id, sender on the source chain) -> request
+pub const UNSTAKE_REQUESTS: Map<(u64, String), UnstakeRequest> = Map::new("unstake_requests");
+pub const PROXY_BATCHES: Map<u64, ProxyBatch> = Map::new("proxy_batches");
+pub const TRANSFER_WAITING_FOR_REPLY: Map<u64, ReturnTransfer> =
+    Map::new("transfer_waiting_for_reply");
+// (channel id, sequence) -> transfer
+pub const INFLIGHT_TRANSFERS: Map<(String, u64), ReturnTransfer> = Map::new("inflight_transfers");
+// (address, denom) -> amount
+pub const CLAIMABLE_FUNDS: Map<(String, String), Uint128> = Map::new("claimable_funds");
@@ -0,0 +1,5 @@
+mod ownership_tests;
+mod stake_tests;
+mod test_helper;
+mod unstake_tests;
+mod withdraw_tests;
@@ -0,0 +1,78 @@
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, ExecuteMsg, QueryMsg};
+use crate::tests::test_helper::{init, ADMIN, OSMO1};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_json, Addr};
+use milky_way::ownership::{OwnershipError, OwnershipResponse};
+
+#[test]
+fn transfer_ownership() {
+    let mut deps = init();
+    let msg = ExecuteMsg::TransferOwnership {
+        new_owner: OSMO1.to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        msg.clone(),
+    );
+    assert!(matches!(res, Err(ContractError::Ownership(_))));
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Ownership {}).unwrap();
+    let ownership: OwnershipResponse = from_json(res).unwrap();
+    let claimable_at = mock_env().block.time.plus_seconds(7 * 24 * 60 * 60);
+    assert_eq!(ownership.pending_owner, Some(Addr::unchecked(OSMO1)));
+    assert_eq!(ownership.owner_transfer_min_time, Some(claimable_at));
+    assert_eq!(
+        ownership.owner_transfer_expiry,
+        Some(claimable_at.plus_seconds(7 * 24 * 60 * 60))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::AcceptOwnership {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::Ownership(OwnershipError::TransferNotReady {
+            time_to_claim: claimable_at
+        }))
+    );
+
+    let mut env = mock_env();
+    env.block.time = claimable_at;
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_json(res).unwrap();
+    assert_eq!(config.admin, Some(Addr::unchecked(OSMO1)));
+}
+
+#[test]
+fn renounce_ownership() {
+    let mut deps = init();
+    let msg = ExecuteMsg::RenounceOwnership {
+        confirm: mock_env().contract.address.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_json(res).unwrap();
+    assert_eq!(config.admin, None);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        staking_contract: Some(OSMO1.to_string()),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg);
+    assert!(matches!(res, Err(ContractError::Admin(_))));
+}
@@ -0,0 +1,194 @@
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, ExecuteMsg, QueryMsg};
+use crate::state::AllowedSource;
+use crate::tests::test_helper::{
+    hook_sender, init, ADMIN, CELESTIA1, CELESTIA_CHANNEL, COSMOS1, COSMOS2, COSMOS_CHANNEL,
+    NATIVE_TOKEN, OSMO1, STAKING, STARS1,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, from_json, to_json_binary, CosmosMsg, WasmMsg};
+use cw_controllers::AdminError;
+use staking::msg::ExecuteMsg as StakingExecuteMsg;
+use staking::state::IbcForward;
+
+#[test]
+fn liquid_stake_forwards_to_sender() {
+    let mut deps = init();
+    let info = mock_info(
+        &hook_sender(CELESTIA_CHANNEL, CELESTIA1),
+        &coins(1000, NATIVE_TOKEN),
+    );
+    let msg = ExecuteMsg::LiquidStake {
+        channel_id: CELESTIA_CHANNEL.to_string(),
+        sender: CELESTIA1.to_string(),
+        expected_mint_amount: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: STAKING.to_string(),
+            msg: to_json_binary(&StakingExecuteMsg::LiquidStake {
+                mint_to: Some(OSMO1.to_string()),
+                expected_mint_amount: None,
+                forward: Some(IbcForward {
+                    channel_id: CELESTIA_CHANNEL.to_string(),
+                    receiver: CELESTIA1.to_string(),
+                }),
+            })
+            .unwrap(),
+            funds: coins(1000, NATIVE_TOKEN),
+        })
+    );
+}
+
+#[test]
+fn liquid_stake_from_unallowed_source() {
+    let mut deps = init();
+
+    for (channel_id, sender, expected) in [
+        (
+            "channel-5",
+            COSMOS1,
+            ContractError::SourceNotAllowed {
+                channel_id: "channel-5".to_string(),
+            },
+        ),
+        (
+            COSMOS_CHANNEL,
+            STARS1,
+            ContractError::SenderNotAllowed {
+                channel_id: COSMOS_CHANNEL.to_string(),
+                sender: STARS1.to_string(),
+            },
+        ),
+        (
+            CELESTIA_CHANNEL,
+            COSMOS1,
+            ContractError::SenderNotAllowed {
+                channel_id: CELESTIA_CHANNEL.to_string(),
+                sender: COSMOS1.to_string(),
+            },
+        ),
+    ] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&hook_sender(channel_id, sender), &coins(1000, NATIVE_TOKEN)),
+            ExecuteMsg::LiquidStake {
+                channel_id: channel_id.to_string(),
+                sender: sender.to_string(),
+                expected_mint_amount: None,
+            },
+        );
+        assert_eq!(res.unwrap_err(), expected);
+    }
+}
+
+#[test]
+fn liquid_stake_with_spoofed_sender() {
+    let mut deps = init();
+
+    // the hook of another account on the same channel, and a native account
+    for caller in [hook_sender(COSMOS_CHANNEL, COSMOS2), OSMO1.to_string()] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&caller, &coins(1000, NATIVE_TOKEN)),
+            ExecuteMsg::LiquidStake {
+                channel_id: COSMOS_CHANNEL.to_string(),
+                sender: COSMOS1.to_string(),
+                expected_mint_amount: None,
+            },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { sender: caller }
+        );
+    }
+}
+
+#[test]
+fn update_allowed_source() {
+    let mut deps = init();
+
+    let msg = ExecuteMsg::UpdateAllowedSource {
+        channel_id: "channel-2".to_string(),
+        prefixes: vec!["stars".to_string()],
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::Admin(AdminError::NotAdmin {})
+    );
+
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateAllowedSource {
+            channel_id: "connection-2".to_string(),
+            prefixes: vec!["stars".to_string()],
+        },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::InvalidChannelId {
+            channel_id: "connection-2".to_string()
+        }
+    );
+
+    // an empty list removes the channel
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateAllowedSource {
+            channel_id: COSMOS_CHANNEL.to_string(),
+            prefixes: vec![],
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_json(res).unwrap();
+    assert_eq!(
+        config.allowed_sources,
+        vec![
+            AllowedSource {
+                channel_id: CELESTIA_CHANNEL.to_string(),
+                prefixes: vec!["celestia".to_string()],
+            },
+            AllowedSource {
+                channel_id: "channel-2".to_string(),
+                prefixes: vec!["stars".to_string()],
+            },
+        ]
+    );
+
+    // the new source is accepted
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            &hook_sender("channel-2", STARS1),
+            &coins(1000, NATIVE_TOKEN),
+        ),
+        ExecuteMsg::LiquidStake {
+            channel_id: "channel-2".to_string(),
+            sender: STARS1.to_string(),
+            expected_mint_amount: None,
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,129 @@
+use crate::contract::instantiate;
+use crate::msg::InstantiateMsg;
+use crate::state::AllowedSource;
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    from_json, to_json_binary, ContractResult, OwnedDeps, SystemResult, Timestamp, Uint128,
+    WasmQuery,
+};
+use staking::helpers::derive_intermediate_sender;
+use staking::msg::{
+    BatchResponse, ConfigResponse, QueryMsg as StakingQueryMsg, WithdrawalResponse,
+};
+
+pub static ADMIN: &str = "osmo12z558dm3ew6avgjdj07mfslx80rp9sh8nt7q3w";
+pub static STAKING: &str = "staking";
+pub static CELESTIA1: &str = "celestia1sfhy3emrgp26wnzuu64p06kpkxd9phel74e0yx";
+pub static COSMOS1: &str = "cosmos1sfhy3emrgp26wnzuu64p06kpkxd9phel0lgl7t";
+pub static COSMOS2: &str = "cosmos1ztrhpdznu2xlwakd4yp3hg9lwyr3d46a48qlx8";
+pub static STARS1: &str = "stars1sfhy3emrgp26wnzuu64p06kpkxd9phelmrlz46";
+// osmo addresses of the same keys
+pub static OSMO1: &str = "osmo1sfhy3emrgp26wnzuu64p06kpkxd9phel8ym0ge";
+pub static OSMO2: &str = "osmo1ztrhpdznu2xlwakd4yp3hg9lwyr3d46aaun0s4";
+pub static CELESTIA_CHANNEL: &str = "channel-0";
+pub static COSMOS_CHANNEL: &str = "channel-1";
+pub static NATIVE_TOKEN: &str =
+    "ibc/C3E53D20BC7A4CC993B17C7971F8ECD06A433C10B6A96F4C4C3714F0624C56DA";
+pub static LST_DENOM: &str = "factory/staking/stTIA";
+
+pub fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        admin: Some(ADMIN.to_string()),
+        staking_contract: STAKING.to_string(),
+        allowed_sources: vec![
+            AllowedSource {
+                channel_id: CELESTIA_CHANNEL.to_string(),
+                prefixes: vec!["celestia".to_string()],
+            },
+            AllowedSource {
+                channel_id: COSMOS_CHANNEL.to_string(),
+                prefixes: vec!["cosmos".to_string()],
+            },
+        ],
+    };
+    let info = mock_info(ADMIN, &[]);
+
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    if res.is_err() {
+        panic!("error: {:?}", res);
+    }
+
+    mock_staking(&mut deps, 1, "pending", Uint128::zero(), Uint128::zero());
+
+    deps
+}
+
+// Mocks the staking contract queries, `status` and the totals are used for the
+// batch with `batch_id`, which is also returned as the pending batch
+pub fn mock_staking(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    batch_id: u64,
+    status: &'static str,
+    batch_total_liquid_stake: Uint128,
+    received_native_unstaked: Uint128,
+) {
+    mock_staking_with_withdrawal(
+        deps,
+        batch_id,
+        status,
+        batch_total_liquid_stake,
+        received_native_unstaked,
+        None,
+    );
+}
+
+// Same as `mock_staking`, with `withdrawal` returned for the proxy's request in the batch
+pub fn mock_staking_with_withdrawal(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    batch_id: u64,
+    status: &'static str,
+    batch_total_liquid_stake: Uint128,
+    received_native_unstaked: Uint128,
+    withdrawal: Option<WithdrawalResponse>,
+) {
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query {:?}", query);
+        };
+        let res = match from_json(msg).unwrap() {
+            StakingQueryMsg::Config {} => to_json_binary(&ConfigResponse {
+                native_token_denom: NATIVE_TOKEN.to_string(),
+                liquid_stake_token_denom: LST_DENOM.to_string(),
+                ..Default::default()
+            }),
+            StakingQueryMsg::PendingBatch {} | StakingQueryMsg::Batch { .. } => {
+                to_json_binary(&BatchResponse {
+                    id: batch_id,
+                    batch_total_liquid_stake,
+                    expected_native_unstaked: Uint128::zero(),
+                    received_native_unstaked,
+                    unstake_request_count: 0,
+                    next_batch_action_time: Timestamp::from_seconds(0),
+                    status: status.to_string(),
+                    readiness: None,
+                })
+            }
+            StakingQueryMsg::Withdrawal { user, .. } => {
+                assert_eq!(user, MOCK_CONTRACT_ADDR);
+                match &withdrawal {
+                    Some(withdrawal) => to_json_binary(withdrawal),
+                    None => {
+                        return SystemResult::Ok(ContractResult::Err(
+                            "no request in batch".to_string(),
+                        ))
+                    }
+                }
+            }
+            _ => panic!("unexpected query {:?}", msg),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+}
+
+pub fn hook_sender(channel_id: &str, sender: &str) -> String {
+    derive_intermediate_sender(channel_id, sender, "osmo").unwrap()
+}
@@ -0,0 +1,132 @@
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, UnstakeRequestResponse};
+use crate::state::PROXY_BATCHES;
+use crate::tests::test_helper::{
+    hook_sender, init, mock_staking, CELESTIA1, CELESTIA_CHANNEL, COSMOS1, COSMOS_CHANNEL,
+    LST_DENOM, NATIVE_TOKEN, STAKING,
+};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, CosmosMsg, OwnedDeps, Response, Uint128, WasmMsg,
+};
+use cw_utils::PaymentError;
+use staking::msg::ExecuteMsg as StakingExecuteMsg;
+
+fn unstake(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    channel_id: &str,
+    sender: &str,
+    amount: u128,
+) -> Response {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&hook_sender(channel_id, sender), &coins(amount, LST_DENOM)),
+        ExecuteMsg::LiquidUnstake {
+            channel_id: channel_id.to_string(),
+            sender: sender.to_string(),
+        },
+    )
+    .unwrap()
+}
+
+#[test]
+fn liquid_unstake_is_tracked_per_sender() {
+    let mut deps = init();
+
+    let res = unstake(&mut deps, COSMOS_CHANNEL, COSMOS1, 300);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: STAKING.to_string(),
+            msg: to_json_binary(&StakingExecuteMsg::LiquidUnstake { owner: None }).unwrap(),
+            funds: coins(300, LST_DENOM),
+        })
+    );
+    unstake(&mut deps, CELESTIA_CHANNEL, CELESTIA1, 100);
+    unstake(&mut deps, COSMOS_CHANNEL, COSMOS1, 200);
+
+    // requests of the next pending batch are tracked separately
+    mock_staking(&mut deps, 2, "pending", Uint128::zero(), Uint128::zero());
+    unstake(&mut deps, COSMOS_CHANNEL, COSMOS1, 50);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::BatchRequests {
+            batch_id: 1,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let requests: Vec<UnstakeRequestResponse> = from_json(res).unwrap();
+    assert_eq!(
+        requests,
+        vec![
+            UnstakeRequestResponse {
+                batch_id: 1,
+                sender: CELESTIA1.to_string(),
+                channel_id: CELESTIA_CHANNEL.to_string(),
+                amount: Uint128::from(100u128),
+            },
+            UnstakeRequestResponse {
+                batch_id: 1,
+                sender: COSMOS1.to_string(),
+                channel_id: COSMOS_CHANNEL.to_string(),
+                amount: Uint128::from(500u128),
+            },
+        ]
+    );
+
+    let batch = PROXY_BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(batch.total_unstaked, Uint128::from(600u128));
+    assert_eq!(batch.withdrawn, None);
+    let batch = PROXY_BATCHES.load(&deps.storage, 2).unwrap();
+    assert_eq!(batch.total_unstaked, Uint128::from(50u128));
+}
+
+#[test]
+fn liquid_unstake_with_spoofed_sender() {
+    let mut deps = init();
+
+    let caller = hook_sender(CELESTIA_CHANNEL, CELESTIA1);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&caller, &coins(100, LST_DENOM)),
+        ExecuteMsg::LiquidUnstake {
+            channel_id: COSMOS_CHANNEL.to_string(),
+            sender: COSMOS1.to_string(),
+        },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::Unauthorized { sender: caller }
+    );
+    assert!(!PROXY_BATCHES.has(&deps.storage, 1));
+}
+
+#[test]
+fn liquid_unstake_requires_lst_denom() {
+    let mut deps = init();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            &hook_sender(CELESTIA_CHANNEL, CELESTIA1),
+            &coins(100, NATIVE_TOKEN),
+        ),
+        ExecuteMsg::LiquidUnstake {
+            channel_id: CELESTIA_CHANNEL.to_string(),
+            sender: CELESTIA1.to_string(),
+        },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::Payment(PaymentError::MissingDenom(LST_DENOM.to_string()))
+    );
+    assert!(!PROXY_BATCHES.has(&deps.storage, 1));
+}
@@ -0,0 +1,299 @@
+use crate::contract::{execute, query, reply, sudo};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::{INFLIGHT_TRANSFERS, PROXY_BATCHES};
+use crate::tests::test_helper::{
+    hook_sender, init, mock_staking, mock_staking_with_withdrawal, CELESTIA1, CELESTIA_CHANNEL,
+    COSMOS1, COSMOS_CHANNEL, LST_DENOM, NATIVE_TOKEN, OSMO1, OSMO2, STAKING,
+};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, OwnedDeps, Reply, ReplyOn,
+    SubMsg, SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
+};
+use osmosis_std::types::ibc::applications::transfer::v1::{MsgTransfer, MsgTransferResponse};
+use staking::contract::IBC_TIMEOUT;
+use staking::msg::{
+    ExecuteMsg as StakingExecuteMsg, IBCLifecycleComplete, SudoMsg, WithdrawalResponse,
+};
+
+// COSMOS1 unstakes 300 and CELESTIA1 100 of the 1000 stTIA in batch 1
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = init();
+    for (channel_id, sender, amount) in [
+        (COSMOS_CHANNEL, COSMOS1, 300),
+        (CELESTIA_CHANNEL, CELESTIA1, 100),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&hook_sender(channel_id, sender), &coins(amount, LST_DENOM)),
+            ExecuteMsg::LiquidUnstake {
+                channel_id: channel_id.to_string(),
+                sender: sender.to_string(),
+            },
+        )
+        .unwrap();
+    }
+    deps
+}
+
+fn transfer_msg(id: u64, channel_id: &str, receiver: &str, amount: u128) -> SubMsg {
+    let env = mock_env();
+    SubMsg {
+        id,
+        msg: CosmosMsg::from(MsgTransfer {
+            source_channel: channel_id.to_string(),
+            source_port: "transfer".to_string(),
+            sender: env.contract.address.to_string(),
+            receiver: receiver.to_string(),
+            token: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
+                denom: NATIVE_TOKEN.to_string(),
+                amount: amount.to_string(),
+            }),
+            timeout_height: None,
+            timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
+            memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
+        }),
+        gas_limit: None,
+        reply_on: ReplyOn::Always,
+    }
+}
+
+#[test]
+fn withdraw_before_batch_received() {
+    let mut deps = setup();
+    mock_staking(
+        &mut deps,
+        1,
+        "submitted",
+        Uint128::from(1000u128),
+        Uint128::zero(),
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::Withdraw {
+            batch_id: 1,
+            limit: None,
+        },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::BatchNotReceived { batch_id: 1 }
+    );
+}
+
+#[test]
+fn withdraw_is_split_between_requests() {
+    let mut deps = setup();
+    mock_staking_with_withdrawal(
+        &mut deps,
+        1,
+        "received",
+        Uint128::from(1000u128),
+        Uint128::from(1100u128),
+        Some(WithdrawalResponse {
+            amount: Uint128::from(440u128),
+            redemption_fee: Uint128::zero(),
+        }),
+    );
+
+    // the proxy withdraws 1100 * 400 / 1000 = 440
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::Withdraw {
+            batch_id: 1,
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: STAKING.to_string(),
+            msg: to_json_binary(&StakingExecuteMsg::Withdraw { batch_id: 1 }).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[1],
+        transfer_msg(1, CELESTIA_CHANNEL, CELESTIA1, 110)
+    );
+    let batch = PROXY_BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(batch.withdrawn, Some(Uint128::from(440u128)));
+
+    // the staking contract is only withdrawn from once
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::Withdraw {
+            batch_id: 1,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![transfer_msg(2, COSMOS_CHANNEL, COSMOS1, 330)]
+    );
+    assert!(!PROXY_BATCHES.has(&deps.storage, 1));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::Withdraw {
+            batch_id: 1,
+            limit: None,
+        },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::NoRequestsInBatch { batch_id: 1 }
+    );
+}
+
+#[test]
+fn withdraw_after_redemption_fee() {
+    let mut deps = setup();
+    mock_staking_with_withdrawal(
+        &mut deps,
+        1,
+        "received",
+        Uint128::from(1000u128),
+        Uint128::from(1100u128),
+        Some(WithdrawalResponse {
+            amount: Uint128::from(396u128),
+            redemption_fee: Uint128::from(44u128),
+        }),
+    );
+
+    // the staking contract withholds 10% of the 440, the rest is split
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::Withdraw {
+            batch_id: 1,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1..],
+        vec![
+            transfer_msg(1, CELESTIA_CHANNEL, CELESTIA1, 99),
+            transfer_msg(2, COSMOS_CHANNEL, COSMOS1, 297),
+        ]
+    );
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "withdrawn" && a.value == "396"));
+}
+
+#[test]
+fn failed_transfer_is_claimable() {
+    let mut deps = setup();
+    mock_staking_with_withdrawal(
+        &mut deps,
+        1,
+        "received",
+        Uint128::from(1000u128),
+        Uint128::from(1100u128),
+        Some(WithdrawalResponse {
+            amount: Uint128::from(440u128),
+            redemption_fee: Uint128::zero(),
+        }),
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::Withdraw {
+            batch_id: 1,
+            limit: None,
+        },
+    )
+    .unwrap();
+
+    for (id, sequence) in [(1, 4), (2, 5)] {
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    data: Some(Binary::from(MsgTransferResponse { sequence })),
+                    events: vec![],
+                }),
+            },
+        )
+        .unwrap();
+    }
+    assert!(INFLIGHT_TRANSFERS.has(&deps.storage, (CELESTIA_CHANNEL.to_string(), 4)));
+
+    // the transfer to CELESTIA1 succeeds, the one to COSMOS1 times out
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCAck {
+            channel: CELESTIA_CHANNEL.to_string(),
+            sequence: 4,
+            ack: "".to_string(),
+            success: true,
+        }),
+    )
+    .unwrap();
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+            channel: COSMOS_CHANNEL.to_string(),
+            sequence: 5,
+        }),
+    )
+    .unwrap();
+    assert!(INFLIGHT_TRANSFERS.is_empty(&deps.storage));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ClaimableFunds {
+            address: OSMO1.to_string(),
+        },
+    )
+    .unwrap();
+    let claimable: Vec<Coin> = from_json(res).unwrap();
+    assert_eq!(claimable, coins(330, NATIVE_TOKEN));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::ClaimFunds {},
+    );
+    assert_eq!(res.unwrap_err(), ContractError::NothingToClaim {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::ClaimFunds {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: OSMO1.to_string(),
+            amount: coins(330, NATIVE_TOKEN),
+        })
+    );
+}
@@ -1,50 +1,79 @@
 use crate::execute::{
-    circuit_breaker, execute_submit_batch, fee_withdraw, handle_ibc_reply, receive_rewards,
-    receive_unstaked_tokens, recover, resume_contract, update_config,
+    approve_monitor_action, cancel_config_change, circuit_breaker, execute_claim_funds,
+    execute_config_change, execute_forward_withdrawal, execute_migrate_batches,
+    execute_settle_swept_request, execute_submit_batch, execute_sweep_unclaimed, fee_withdraw,
+    fund_keeper_bounty, grant_role, handle_forward_reply, handle_ibc_reply, propose_monitor_action,
+    receive_rewards, receive_unstaked_tokens, recover, resume_contract, revoke_role,
+    set_oracle_target_enabled, update_config, validate_config_change, validate_protocol_fee_config,
+    veto_config_change,
 };
-use crate::helpers::validate_addresses;
+use crate::helpers::{validate_address, validate_addresses};
 use crate::ibc::{receive_ack, receive_timeout};
 use crate::migrations;
 use crate::query::{
-    query_all_unstake_requests, query_all_unstake_requests_v2, query_batch, query_batches,
-    query_batches_by_ids, query_config, query_ibc_queue, query_pending_batch, query_reply_queue,
-    query_state, query_unstake_requests,
+    query_all_unstake_requests, query_all_unstake_requests_v2, query_archived_batches,
+    query_audit_log, query_batch, query_batches, query_batches_by_ids, query_claimable_funds,
+    query_config, query_extra_rewards, query_fee_distribution, query_ibc_queue,
+    query_monitor_proposals, query_ownership, query_pending_batch, query_pending_config_changes,
+    query_rates, query_reply_queue, query_roles, query_simulate_liquid_stake,
+    query_simulate_liquid_unstake, query_state, query_swept_requests, query_unstake_requests,
+    query_withdrawal,
 };
 use crate::state::{
-    Config, MultisigAddressConfig, ProtocolFeeConfig, State, ADMIN, BATCHES, CONFIG,
-    IBC_WAITING_FOR_REPLY, PENDING_BATCH_ID, STATE,
+    Config, MultisigAddressConfig, State, BATCHES, CONFIG, FORWARD_WAITING_FOR_REPLY,
+    IBC_WAITING_FOR_REPLY, LAST_POSTED_RATES, OWNERSHIP, PENDING_BATCH_ID, STATE,
 };
 use crate::{
     error::ContractError,
     execute::{
-        execute_accept_ownership, execute_add_validator, execute_liquid_stake,
-        execute_liquid_unstake, execute_remove_validator, execute_revoke_ownership_transfer,
-        execute_transfer_ownership, execute_withdraw,
+        execute_add_validator, execute_liquid_stake, execute_liquid_unstake, execute_ownership,
+        execute_remove_validator, execute_withdraw,
     },
     msg::{ExecuteMsg, IBCLifecycleComplete, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg},
 };
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdError, StdResult, Uint128,
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdResult, Uint128,
 };
 use cosmwasm_std::{CosmosMsg, Timestamp};
 use cw2::set_contract_version;
 use cw_utils::must_pay;
+use milky_way::ownership::OwnershipMsg;
 use milky_way::staking::Batch;
+use milky_way::validation::invalid_field;
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgCreateDenom;
-use semver::Version;
 
 // Version information for migration
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const IBC_TIMEOUT: Timestamp = Timestamp::from_nanos(1000000000000); // TODO: Placeholder value for IBC timeout
 
+// Share of the rewards the DAO treasury fee can be set to at most
+pub const MAX_DAO_TREASURY_FEE: Decimal = Decimal::percent(20);
+// Share of a batch and amount of native tokens the keeper reward can be set to at most
+pub const MAX_KEEPER_REWARD_RATE: Decimal = Decimal::percent(1);
+pub const MAX_KEEPER_REWARD: Uint128 = Uint128::new(10_000_000);
+// Delay before a protocol fee change takes effect
+pub const FEE_CHANGE_DELAY: u64 = 3 * 24 * 60 * 60;
+// Delay before a proposed change of the multisig addresses, IBC channel, native denom,
+// treasury, fee recipients, extra reward denoms, oracles or monitors can be executed
+pub const CONFIG_CHANGE_DELAY: u64 = 3 * 24 * 60 * 60;
+
+// Proposals a monitor can have open at once
+pub const MAX_OPEN_MONITOR_PROPOSALS: usize = 5;
+
+// Window of the time-weighted average rates, by default and at most
+pub const DEFAULT_TWAP_WINDOW: u64 = 24 * 60 * 60;
+pub const MAX_TWAP_WINDOW: u64 = 7 * 24 * 60 * 60;
+
 pub const CELESTIA_ACCOUNT_PREFIX: &str = "celestia";
 pub const OSMOSIS_ACCOUNT_PREFIX: &str = "osmo";
 pub const CELESTIA_VALIDATOR_PREFIX: &str = "celestiavaloper";
 
 ///////////////////
-/// INSTANTIATE ///
+//  INSTANTIATE  //
 ///////////////////
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -57,7 +86,7 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     // TODO: determine if info.sender is the admin or if we want to pass in with msg
-    ADMIN.set(deps.branch(), Some(info.sender.clone()))?;
+    OWNERSHIP.set_owner(deps.branch(), Some(info.sender.clone()))?;
 
     // validations
     let validators = validate_addresses(&msg.validators, CELESTIA_VALIDATOR_PREFIX)?;
@@ -72,21 +101,38 @@ pub fn instantiate(
         "liquid_stake_token_denom must be alphabetic"
     );
 
+    // The initial fees and sensitive config take effect right away instead of being staged
+    validate_protocol_fee_config(&msg.protocol_fee_config)?;
+    let config_change = validate_config_change(
+        Some(msg.multisig_address_config),
+        Some(msg.native_token_denom),
+        Some(msg.ibc_channel_id),
+        Some(msg.treasury_address),
+    )?;
+    let monitors = validate_addresses(&msg.monitors, OSMOSIS_ACCOUNT_PREFIX)
+        .map_err(invalid_field("monitors"))?;
+    let oracle_address = msg
+        .oracle_address
+        .filter(|address| !address.is_empty())
+        .map(|address| {
+            validate_address(&address, OSMOSIS_ACCOUNT_PREFIX)
+                .map_err(invalid_field("oracle_address"))
+        })
+        .transpose()?;
+
     // Init Config
-    let config = Config {
+    let mut config = Config {
         native_token_denom: "".to_string(),
         liquid_stake_token_denom: format!(
             "factory/{0}/{1}",
             env.contract.address, msg.liquid_stake_token_denom
         ),
         treasury_address: Addr::unchecked(""),
-        monitors: Some(vec![]),
+        monitors: Some(monitors),
         validators,
         batch_period: 0,
         unbonding_period: 0,
-        protocol_fee_config: ProtocolFeeConfig {
-            dao_treasury_fee: Uint128::zero(),
-        },
+        protocol_fee_config: msg.protocol_fee_config,
         multisig_address_config: MultisigAddressConfig {
             staker_address: Addr::unchecked(""),
             reward_collector_address: Addr::unchecked(""),
@@ -94,9 +140,18 @@ pub fn instantiate(
         minimum_liquid_stake_amount: Uint128::zero(),
         ibc_channel_id: "".to_string(),
         stopped: true, // we start stopped
-        oracle_address: None,
+        oracle_address,
         send_fees_to_treasury: msg.send_fees_to_treasury,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
     };
+    config_change.apply(&mut config);
 
     CONFIG.save(deps.storage, &config)?;
 
@@ -107,14 +162,23 @@ pub fn instantiate(
         Some(msg.batch_period),
         Some(msg.unbonding_period),
         Some(msg.minimum_liquid_stake_amount),
-        Some(msg.multisig_address_config),
-        Some(msg.protocol_fee_config),
-        Some(msg.native_token_denom),
-        Some(msg.ibc_channel_id.clone()),
-        Some(msg.monitors),
-        Some(msg.treasury_address),
-        msg.oracle_address,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
         Some(msg.send_fees_to_treasury),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
     )?;
 
     // Init State
@@ -125,6 +189,8 @@ pub fn instantiate(
         total_reward_amount: Uint128::zero(),
         total_fees: Uint128::zero(),
         ibc_id_counter: 0,
+        keeper_bounty: Uint128::zero(),
+        last_batch_submission: None,
         rate: 1u128.into(),
         owner_transfer_min_time: None,
     };
@@ -157,7 +223,7 @@ pub fn instantiate(
 }
 
 ///////////////
-/// EXECUTE ///
+//  EXECUTE  //
 ///////////////
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -172,28 +238,60 @@ pub fn execute(
         ExecuteMsg::LiquidStake {
             mint_to,
             expected_mint_amount,
+            forward,
         } => {
             let payment = must_pay(&info, &config.native_token_denom)?;
-            execute_liquid_stake(deps, env, info, payment, mint_to, expected_mint_amount)
+            execute_liquid_stake(
+                deps,
+                env,
+                info,
+                payment,
+                mint_to,
+                expected_mint_amount,
+                forward,
+            )
         }
-        ExecuteMsg::LiquidUnstake {} => {
+        ExecuteMsg::LiquidUnstake { owner } => {
             let payment = must_pay(&info, &config.liquid_stake_token_denom)?;
-            execute_liquid_unstake(deps, env, info, payment)
+            execute_liquid_unstake(deps, env, info, payment, owner)
         }
         ExecuteMsg::SubmitBatch {} => execute_submit_batch(deps, env, info),
         ExecuteMsg::Withdraw { batch_id } => execute_withdraw(deps, env, info, batch_id),
+        ExecuteMsg::ForwardWithdrawal { batch_id, owner } => {
+            execute_forward_withdrawal(deps, env, batch_id, owner)
+        }
         ExecuteMsg::AddValidator { new_validator } => {
             execute_add_validator(deps, env, info, new_validator)
         }
         ExecuteMsg::RemoveValidator { validator } => {
             execute_remove_validator(deps, env, info, validator)
         }
-        ExecuteMsg::TransferOwnership { new_owner } => {
-            execute_transfer_ownership(deps, env, info, new_owner)
+        ExecuteMsg::TransferOwnership { new_owner } => execute_ownership(
+            deps,
+            env,
+            info,
+            OwnershipMsg::TransferOwnership { new_owner },
+        ),
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_ownership(deps, env, info, OwnershipMsg::AcceptOwnership {})
         }
-        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
         ExecuteMsg::RevokeOwnershipTransfer {} => {
-            execute_revoke_ownership_transfer(deps, env, info)
+            execute_ownership(deps, env, info, OwnershipMsg::RevokeOwnershipTransfer {})
+        }
+        ExecuteMsg::UpdateOwnershipConfig {
+            transfer_delay,
+            transfer_expiry,
+        } => execute_ownership(
+            deps,
+            env,
+            info,
+            OwnershipMsg::UpdateOwnershipConfig {
+                transfer_delay,
+                transfer_expiry,
+            },
+        ),
+        ExecuteMsg::RenounceOwnership { confirm } => {
+            execute_ownership(deps, env, info, OwnershipMsg::RenounceOwnership { confirm })
         }
         ExecuteMsg::UpdateConfig {
             batch_period,
@@ -207,6 +305,15 @@ pub fn execute(
             treasury_address,
             oracle_address,
             send_fees_to_treasury,
+            keeper_reward,
+            batch_trigger,
+            unclaimed_sweep,
+            fee_recipients,
+            extra_reward_denoms,
+            oracle_targets,
+            oracle_throttle,
+            monitor_quorum,
+            clear,
         } => update_config(
             deps,
             env,
@@ -222,7 +329,32 @@ pub fn execute(
             treasury_address,
             oracle_address,
             send_fees_to_treasury,
+            keeper_reward,
+            batch_trigger,
+            unclaimed_sweep,
+            fee_recipients,
+            extra_reward_denoms,
+            oracle_targets,
+            oracle_throttle,
+            monitor_quorum,
+            clear,
         ),
+        ExecuteMsg::ProposeMonitorAction { action } => {
+            propose_monitor_action(deps, env, info, action)
+        }
+        ExecuteMsg::ApproveMonitorAction { id } => approve_monitor_action(deps, env, info, id),
+        ExecuteMsg::GrantRole { role, address } => grant_role(deps, env, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => revoke_role(deps, env, info, role, address),
+        ExecuteMsg::ExecuteConfigChange { id } => execute_config_change(deps, env, info, id),
+        ExecuteMsg::CancelConfigChange { id } => cancel_config_change(deps, env, info, id),
+        ExecuteMsg::VetoConfigChange { id } => veto_config_change(deps, env, info, id),
+        ExecuteMsg::SetOracleTargetEnabled { address, enabled } => {
+            set_oracle_target_enabled(deps, env, info, address, enabled)
+        }
+        ExecuteMsg::FundKeeperBounty {} => {
+            let payment = must_pay(&info, &config.native_token_denom)?;
+            fund_keeper_bounty(deps, payment)
+        }
         ExecuteMsg::ReceiveRewards {} => receive_rewards(deps, env, info),
         ExecuteMsg::ReceiveUnstakedTokens { batch_id } => {
             receive_unstaked_tokens(deps, env, info, batch_id)
@@ -251,17 +383,25 @@ pub fn execute(
             paginated.unwrap_or(false),
         ),
         ExecuteMsg::FeeWithdraw { amount } => fee_withdraw(deps, env, info, amount),
+        ExecuteMsg::ClaimFunds {} => execute_claim_funds(deps, info),
+        ExecuteMsg::SweepUnclaimed { batch_id, limit } => {
+            execute_sweep_unclaimed(deps, env, info, batch_id, limit)
+        }
+        ExecuteMsg::SettleSweptRequest { batch_id, user } => {
+            execute_settle_swept_request(deps, info, batch_id, user)
+        }
+        ExecuteMsg::MigrateBatches { limit } => execute_migrate_batches(deps, limit),
     }
 }
 
 /////////////
-/// QUERY ///
+//  QUERY  //
 /////////////
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Config {} => to_json_binary(&query_config(deps, env)?),
         QueryMsg::State {} => to_json_binary(&query_state(deps)?),
         QueryMsg::Batch { id } => to_json_binary(&query_batch(deps, id)?),
         QueryMsg::Batches {
@@ -270,7 +410,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             status,
         } => to_json_binary(&query_batches(deps, start_after, limit, status)?),
         QueryMsg::BatchesByIds { ids } => to_json_binary(&query_batches_by_ids(deps, ids)?),
-        QueryMsg::PendingBatch {} => to_json_binary(&query_pending_batch(deps)?),
+        QueryMsg::ArchivedBatches { start_after, limit } => {
+            to_json_binary(&query_archived_batches(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingBatch {} => to_json_binary(&query_pending_batch(deps, env)?),
         QueryMsg::UnstakeRequests { user } => {
             to_json_binary(&query_unstake_requests(deps, user.into_string())?)
         }
@@ -289,54 +432,51 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::IbcReplyQueue { start_after, limit } => {
             to_json_binary(&query_reply_queue(deps, start_after, limit)?)
         }
+        QueryMsg::ClaimableFunds { address } => {
+            to_json_binary(&query_claimable_funds(deps, address)?)
+        }
+        QueryMsg::SweptRequests { user } => to_json_binary(&query_swept_requests(deps, user)?),
+        QueryMsg::SimulateLiquidStake { amount } => {
+            to_json_binary(&query_simulate_liquid_stake(deps, env, amount)?)
+        }
+        QueryMsg::SimulateLiquidUnstake { amount } => {
+            to_json_binary(&query_simulate_liquid_unstake(deps, env, amount)?)
+        }
+        QueryMsg::Withdrawal { batch_id, user } => {
+            to_json_binary(&query_withdrawal(deps, batch_id, user)?)
+        }
+        QueryMsg::FeeDistribution {} => to_json_binary(&query_fee_distribution(deps)?),
+        QueryMsg::ExtraRewards {} => to_json_binary(&query_extra_rewards(deps)?),
+        QueryMsg::Rates { twap_window } => to_json_binary(&query_rates(deps, env, twap_window)?),
+        QueryMsg::LastPostedRates {} => to_json_binary(&LAST_POSTED_RATES.may_load(deps.storage)?),
+        QueryMsg::MonitorProposals { start_after, limit } => {
+            to_json_binary(&query_monitor_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Ownership {} => to_json_binary(&query_ownership(deps)?),
+        QueryMsg::Roles {} => to_json_binary(&query_roles(deps)?),
+        QueryMsg::PendingConfigChanges { start_after, limit } => {
+            to_json_binary(&query_pending_config_changes(deps, start_after, limit)?)
+        }
+        QueryMsg::AuditLog { start_after, limit } => {
+            to_json_binary(&query_audit_log(deps, start_after, limit)?)
+        }
     }
 }
 
 ///////////////
-/// MIGRATE ///
+//  MIGRATE  //
 ///////////////
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    let current_version = cw2::get_contract_version(deps.storage)?;
-    if CONTRACT_NAME != current_version.contract.as_str() {
-        return Err(StdError::generic_err("Cannot upgrade to a different contract").into());
-    }
-
-    let version: Version = current_version
-        .version
-        .parse()
-        .map_err(|_| StdError::generic_err("Invalid contract version"))?;
-    let new_version: Version = CONTRACT_VERSION
-        .parse()
-        .map_err(|_| StdError::generic_err("Invalid contract version"))?;
-
-    // current version not launchpad v2
-    if version > new_version {
-        return Err(StdError::generic_err("Cannot upgrade to a previous contract version").into());
-    }
-    // if same version return
-    if version == new_version {
-        return Err(StdError::generic_err("Cannot migrate to the same version.").into());
-    }
-
-    let migration_response = match msg {
-        MigrateMsg::V0_4_18ToV0_4_20 {
-            send_fees_to_treasury,
-        } => migrations::v0_4_20::migrate(deps.branch(), env, send_fees_to_treasury)?,
-    };
-
-    // set new contract version
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-
-    Ok(migration_response)
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrations::migrate(deps, &env, &msg)
 }
 
 /////////////
-/// SUDO  ///
+//  SUDO   //
 /////////////
 
-#[cfg_attr(not(feature = "imported"), entry_point)]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
     match msg {
         SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCAck {
@@ -352,14 +492,16 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, Contract
 }
 
 /////////////
-/// REPLY ///
+//  REPLY  //
 /////////////
 
-#[cfg_attr(not(feature = "imported"), entry_point)]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
-    let ibc_waiting_result = IBC_WAITING_FOR_REPLY.load(deps.storage, reply.id);
-    match ibc_waiting_result {
-        Ok(_ibc_waiting_for_reply) => handle_ibc_reply(deps, reply),
-        Err(_) => Err(ContractError::InvalidReplyID { id: reply.id }),
+    if IBC_WAITING_FOR_REPLY.has(deps.storage, reply.id) {
+        return handle_ibc_reply(deps, reply);
+    }
+    if FORWARD_WAITING_FOR_REPLY.has(deps.storage, reply.id) {
+        return handle_forward_reply(deps, reply);
     }
+    Err(ContractError::InvalidReplyID { id: reply.id })
 }
@@ -1,8 +1,10 @@
-use cosmwasm_std::{StdError, Timestamp, Uint128};
+use cosmwasm_std::{Decimal, StdError, Uint128};
 use cw2::VersionError;
 use cw_controllers::AdminError;
 use cw_utils::PaymentError;
+use milky_way::ownership::OwnershipError;
 use milky_way::staking::BatchStatus;
+use milky_way::validation::InvalidField;
 use thiserror::Error;
 
 pub type ContractResult<T> = core::result::Result<T, ContractError>;
@@ -19,11 +21,11 @@ pub enum ContractError {
     #[error("Admin error: {0}")]
     Admin(#[from] AdminError),
 
-    #[error("No pending owner")]
-    NoPendingOwner {},
+    #[error("{sender} is missing the {role} role")]
+    MissingRole { sender: String, role: String },
 
-    #[error("Ownership transfer not ready")]
-    OwnershipTransferNotReady { time_to_claim: Timestamp },
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
 
     #[error("Payment error: {0}")]
     Payment(#[from] PaymentError),
@@ -58,6 +60,9 @@ pub enum ContractError {
     #[error("The prvoided IBC channel and reserve token config is wrong")]
     IbcChannelConfigWrong {},
 
+    #[error("Invalid {field}: {reason}")]
+    InvalidConfigField { field: String, reason: String },
+
     #[error("Batch is not ready to be submitted")]
     BatchNotReady { actual: u64, expected: u64 },
 
@@ -130,9 +135,105 @@ pub enum ContractError {
     #[error("Insufficient funds")]
     InsufficientFunds {},
 
+    #[error("Keeper reward can be at most {max_rate} of a batch and {max_reward} per batch")]
+    InvalidKeeperReward {
+        max_rate: Decimal,
+        max_reward: Uint128,
+    },
+
+    #[error("Deposit and redemption fees can't be more than 100%")]
+    InvalidFee {},
+
+    #[error("DAO treasury fee {fee} is above the maximum of {max}")]
+    DaoTreasuryFeeTooHigh { fee: Decimal, max: Decimal },
+
+    #[error("Extra reward denoms need to be unique and different from the native and liquid stake denoms")]
+    InvalidExtraRewardDenoms {},
+
+    #[error("Oracle targets need unique addresses and templates producing valid JSON")]
+    InvalidOracleTargets {},
+
+    #[error("No oracle target with address {address}")]
+    OracleTargetNotFound { address: String },
+
+    #[error("Monitor quorum needs a threshold between 1 and the number of monitors")]
+    InvalidMonitorQuorum {},
+
+    #[error("This action needs the approval of the monitors")]
+    MonitorQuorumRequired {},
+
+    #[error("Monitors and the monitor quorum can't be changed while the contract is stopped")]
+    MonitorsChangeWhileStopped {},
+
+    #[error("Monitor quorum is not enabled for this action")]
+    MonitorQuorumNotEnabled {},
+
+    #[error("No monitor proposal with id {id}")]
+    MonitorProposalNotFound { id: u64 },
+
+    #[error("Monitor proposal {id} expired at {expires_at}")]
+    MonitorProposalExpired { id: u64, expires_at: u64 },
+
+    #[error("{monitor} already has {max} open monitor proposals")]
+    TooManyMonitorProposals { monitor: String, max: usize },
+
+    #[error("{sender} already approved monitor proposal {id}")]
+    AlreadyApproved { id: u64, sender: String },
+
+    #[error("No pending config change with id {id}")]
+    ConfigChangeNotFound { id: u64 },
+
+    #[error("Config change {id} can't be executed before {effective_at}")]
+    ConfigChangeTimelocked { id: u64, effective_at: u64 },
+
+    #[error("Fee recipients need to be unique with weights summing up to 100%")]
+    InvalidFeeRecipients {},
+
     #[error("If liquid staking is done from a non native Osmosis address you need to provide an address via 'mint_to'")]
     MissingMintAddress {},
 
+    #[error("The provided IBC forward channel or receiver is invalid")]
+    InvalidForward {},
+
+    #[error("No funds to claim")]
+    NothingToClaim {},
+
+    #[error("Config update doesn't change any field")]
+    EmptyConfigUpdate {},
+
+    #[error("Sweeping unclaimed withdrawals is not enabled")]
+    SweepNotEnabled {},
+
+    #[error("Claim window of batch {batch_id} is open until {ends_at}")]
+    ClaimWindowOpen { batch_id: u64, ends_at: u64 },
+
+    #[error("Withdrawal from batch {batch_id} was swept, it can be claimed from the treasury")]
+    WithdrawalSwept { batch_id: u64 },
+
+    #[error("Swept request of batch {batch_id} was already settled")]
+    SweptRequestSettled { batch_id: u64 },
+
     #[error("{0}")]
     Version(#[from] VersionError),
+
+    #[error("No migration path from {from} to {to}")]
+    NoMigrationPath { from: String, to: String },
+
+    #[error("Migration option {option} is required")]
+    MissingMigrationOption { option: String },
+
+    #[error("Migrated state is invalid: {reason}")]
+    MigrationInvariant { reason: String },
+
+    #[error("No batches left to migrate")]
+    NoBatchMigration {},
+}
+
+impl From<InvalidField> for ContractError {
+    fn from(err: InvalidField) -> Self {
+        ContractError::InvalidConfigField {
+            field: err.field,
+            reason: err.reason,
+        }
+    }
 }
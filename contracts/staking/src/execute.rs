@@ -1,22 +1,46 @@
-use crate::contract::{CELESTIA_VALIDATOR_PREFIX, IBC_TIMEOUT};
+use crate::contract::{
+    CELESTIA_ACCOUNT_PREFIX, CELESTIA_VALIDATOR_PREFIX, CONFIG_CHANGE_DELAY, FEE_CHANGE_DELAY,
+    IBC_TIMEOUT, MAX_DAO_TREASURY_FEE, MAX_KEEPER_REWARD, MAX_KEEPER_REWARD_RATE,
+    MAX_OPEN_MONITOR_PROPOSALS, MAX_TWAP_WINDOW, OSMOSIS_ACCOUNT_PREFIX,
+};
 use crate::error::{ContractError, ContractResult};
 use crate::helpers::{
-    compute_mint_amount, compute_unbond_amount, derive_intermediate_sender, get_rates,
-    paginate_map, validate_address, validate_addresses,
+    assert_role, attribute_value, batch_readiness, compute_mint_amount, compute_unbond_amount,
+    compute_withdrawal, config_diff, convert_address_prefix, derive_intermediate_sender,
+    fee_recipients, get_rates, get_rates_256, is_valid_channel_id, paginate_map, validate_address,
+    validate_addresses, validate_forward,
 };
-use crate::oracle::Oracle;
+use crate::migrations::v0_6_0::{migrate_batches, migrate_legacy_batch, DEFAULT_BATCH_LIMIT};
+use crate::oracle::{OracleFormat, OracleTarget};
 use crate::state::{
-    ibc::{IBCTransfer, PacketLifecycleStatus},
-    Config, IbcWaitingForReply, MultisigAddressConfig, ProtocolFeeConfig, State, ADMIN, BATCHES,
-    CONFIG, IBC_WAITING_FOR_REPLY, INFLIGHT_PACKETS, PENDING_BATCH_ID, STATE,
+    ibc::{ForwardTransfer, IBCTransfer, PacketLifecycleStatus},
+    ArchivedBatch, AuditEntry, BatchTriggerConfig, Config, ConfigChange, ExtraRewardDenom,
+    ExtraRewardDestination, FeeRecipient, IbcForward, IbcWaitingForReply, KeeperRewardConfig,
+    KeeperRewardSource, MonitorAction, MonitorProposal, MonitorQuorumConfig, MultisigAddressConfig,
+    OptionalConfig, OracleThrottleConfig, PendingConfigChange, PendingProtocolFeeConfig,
+    PostedRates, ProtocolFeeConfig, RateSnapshot, RewardFeeMode, Role, State, SweepDestination,
+    SweptRequest, UnclaimedSweepConfig, ARCHIVED_BATCHES, AUDIT_LOG, AUDIT_LOG_ID_COUNTER, BATCHES,
+    BATCH_REDEMPTION_FEES, CLAIMABLE_FUNDS, CONFIG, CONFIG_CHANGE_ID_COUNTER, DISTRIBUTED_FEES,
+    FORWARDED_EXTRA_REWARDS, FORWARD_WAITING_FOR_REPLY, IBC_WAITING_FOR_REPLY, INFLIGHT_FORWARDS,
+    INFLIGHT_PACKETS, LAST_POSTED_RATES, LEGACY_BATCHES_BEFORE, MONITOR_PROPOSALS,
+    MONITOR_PROPOSAL_ID_COUNTER, OWNERSHIP, PENDING_BATCH_ID, PENDING_CONFIG_CHANGES,
+    PENDING_PROTOCOL_FEE_CONFIG, RATE_SNAPSHOTS, ROLES, STATE, SWEPT_REQUESTS,
 };
 use crate::state::{new_unstake_request, remove_unstake_request, unstake_requests, UnstakeRequest};
 use cosmwasm_std::{
-    ensure, Addr, CosmosMsg, Deps, DepsMut, Env, IbcTimeout, MessageInfo, Order, ReplyOn, Response,
-    SubMsg, SubMsgResponse, SubMsgResult, Timestamp, Uint128,
+    ensure, to_json_string, Addr, Attribute, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env,
+    IbcTimeout, MessageInfo, Order, ReplyOn, Response, StdResult, Storage, SubMsg, SubMsgResponse,
+    SubMsgResult, Timestamp, Uint128,
 };
+use cw_storage_plus::Bound;
 use cw_utils::PaymentError;
+use milky_way::events::{
+    MilkyWayEvent, ReceiveUnstakedEvent, RecoverEvent, RewardsEvent, StakeEvent, SubmitBatchEvent,
+    UnstakeEvent, WithdrawEvent,
+};
+use milky_way::ownership::{OwnershipError, OwnershipMsg};
 use milky_way::staking::{Batch, BatchStatus};
+use milky_way::validation::invalid_field;
 use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
 use osmosis_std::types::cosmos::base::v1beta1::Coin;
 use osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContract;
@@ -24,6 +48,12 @@ use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
 use osmosis_std::types::ibc::applications::transfer::v1::MsgTransferResponse;
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::{MsgBurn, MsgMint};
 use prost::Message;
+use std::collections::HashSet;
+
+// Maximum number of unstake requests swept per call by default
+const DEFAULT_SWEEP_LIMIT: u32 = 50;
+// Maximum number of expired rate snapshots removed per call
+const RATE_SNAPSHOT_PRUNE_LIMIT: usize = 10;
 
 pub fn transfer_stake_msg(
     deps: &Deps,
@@ -88,34 +118,382 @@ fn transfer_stake_sub_msg(
     })
 }
 
+// Sends tokens held by the contract to a user on a remote chain. The transfer is tracked
+// so that the tokens can be claimed by the fallback address if it fails
+fn transfer_forward_sub_msg(
+    deps: &mut DepsMut,
+    env: &Env,
+    forward: ForwardTransfer,
+    sub_msg_id: u64,
+) -> Result<SubMsg, ContractError> {
+    let timeout = IbcTimeout::with_timestamp(Timestamp::from_nanos(
+        env.block.time.nanos() + IBC_TIMEOUT.nanos(),
+    ));
+
+    let ibc_msg = MsgTransfer {
+        source_channel: forward.channel_id.clone(),
+        source_port: "transfer".to_string(),
+        token: Some(Coin {
+            denom: forward.denom.clone(),
+            amount: forward.amount.to_string(),
+        }),
+        receiver: forward.receiver.clone(),
+        sender: env.contract.address.to_string(),
+        timeout_height: None,
+        timeout_timestamp: timeout.timestamp().unwrap().nanos(),
+        memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
+    };
+
+    // Same reasoning as for save_ibc_waiting_for_reply
+    if FORWARD_WAITING_FOR_REPLY.has(deps.storage, sub_msg_id) {
+        return Err(ContractError::ContractLocked {
+            msg: "Already waiting for a reply".to_string(),
+        });
+    }
+    FORWARD_WAITING_FOR_REPLY.save(deps.storage, sub_msg_id, &forward)?;
+
+    Ok(SubMsg {
+        id: sub_msg_id,
+        msg: ibc_msg.into(),
+        gas_limit: None,
+        reply_on: ReplyOn::Always,
+    })
+}
+
 fn update_oracle_msgs(
-    deps: Deps,
+    deps: &mut DepsMut,
     env: Env,
     config: &Config,
 ) -> Result<Vec<CosmosMsg>, ContractError> {
-    let (redemption_rate, purchase_rate) = get_rates(&deps);
-    let mut messages: Vec<CosmosMsg> = Vec::new();
-    // Post rates to Milkyway Oracle contract
-    let post_rates_msg = Oracle::PostRates {
-        purchase_rate: purchase_rate.to_string(),
-        redemption_rate: redemption_rate.to_string(),
-        denom: config.liquid_stake_token_denom.clone(),
-    };
+    record_rates(deps.storage, &env)?;
+
+    let (redemption_rate, purchase_rate) = get_rates(&deps.as_ref());
+    // Post rates to Milkyway Oracle contract and the enabled oracle targets
+    let milkyway_oracle = config.oracle_address.clone().map(|address| OracleTarget {
+        address,
+        format: OracleFormat::PostRates,
+        enabled: true,
+    });
+    let targets = milkyway_oracle
+        .iter()
+        .chain(config.oracle_targets.iter().flatten())
+        .filter(|target| target.enabled)
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return Ok(vec![]);
+    }
 
-    let post_rate_msg_json = serde_json::to_string(&post_rates_msg).unwrap();
-    messages.push(
-        MsgExecuteContract {
-            sender: env.contract.address.to_string(),
-            contract: config.oracle_address.clone().unwrap().to_string(),
-            msg: post_rate_msg_json.as_bytes().to_vec(),
-            funds: vec![],
+    let last_posted_rates = LAST_POSTED_RATES.may_load(deps.storage)?;
+    if let (Some(throttle), Some(last)) = (&config.oracle_throttle, &last_posted_rates) {
+        let moved = last.redemption_rate.is_zero()
+            || redemption_rate.abs_diff(last.redemption_rate) / last.redemption_rate
+                > throttle.threshold;
+        let heartbeat_elapsed = env.block.time.seconds() >= last.posted_at + throttle.heartbeat;
+        if !moved && !heartbeat_elapsed {
+            return Ok(vec![]);
         }
-        .into(),
-    );
+    }
+    LAST_POSTED_RATES.save(
+        deps.storage,
+        &PostedRates {
+            redemption_rate,
+            purchase_rate,
+            posted_at: env.block.time.seconds(),
+        },
+    )?;
+
+    let mut messages: Vec<CosmosMsg> = Vec::new();
+    for target in targets {
+        let msg = target.format.msg(
+            &config.liquid_stake_token_denom,
+            redemption_rate,
+            purchase_rate,
+            env.block.time,
+        )?;
+        messages.push(
+            MsgExecuteContract {
+                sender: env.contract.address.to_string(),
+                contract: target.address.to_string(),
+                msg,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
 
     Ok(messages)
 }
 
+// Snapshots the rates when they changed and drops the snapshots no longer needed for the
+// time-weighted average rates
+fn record_rates(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    let state = STATE.load(storage)?;
+    let (redemption_rate, purchase_rate) = get_rates_256(&state);
+    let now = env.block.time.seconds();
+
+    let latest = RATE_SNAPSHOTS
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    if let Some((_, latest)) = latest {
+        if latest.redemption_rate == redemption_rate && latest.purchase_rate == purchase_rate {
+            return Ok(());
+        }
+    }
+    RATE_SNAPSHOTS.save(
+        storage,
+        now,
+        &RateSnapshot {
+            redemption_rate,
+            purchase_rate,
+        },
+    )?;
+
+    // the latest snapshot before the window is kept, it is in effect at the window's start
+    let cutoff = now.saturating_sub(MAX_TWAP_WINDOW);
+    let expired = RATE_SNAPSHOTS
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(cutoff)),
+            Order::Descending,
+        )
+        .skip(1)
+        .take(RATE_SNAPSHOT_PRUNE_LIMIT)
+        .collect::<StdResult<Vec<_>>>()?;
+    for time in expired {
+        RATE_SNAPSHOTS.remove(storage, time);
+    }
+
+    Ok(())
+}
+
+// Appends a privileged action to the audit log
+fn audit(
+    storage: &mut dyn Storage,
+    env: &Env,
+    actor: &Addr,
+    action: &str,
+    before: Option<String>,
+    after: Option<String>,
+) -> StdResult<()> {
+    let id = AUDIT_LOG_ID_COUNTER.may_load(storage)?.unwrap_or_default() + 1;
+    AUDIT_LOG_ID_COUNTER.save(storage, &id)?;
+    AUDIT_LOG.save(
+        storage,
+        id,
+        &AuditEntry {
+            id,
+            actor: actor.clone(),
+            action: action.to_string(),
+            before,
+            after,
+            height: env.block.height,
+            time: env.block.time,
+        },
+    )
+}
+
+// Targets need unique osmo addresses and a template producing valid JSON
+fn validate_oracle_targets(targets: Vec<OracleTarget>) -> ContractResult<Vec<OracleTarget>> {
+    let addresses = targets
+        .iter()
+        .map(|target| target.address.to_string())
+        .collect();
+    validate_addresses(&addresses, OSMOSIS_ACCOUNT_PREFIX)
+        .map_err(|_| ContractError::InvalidOracleTargets {})?;
+    for target in &targets {
+        target
+            .format
+            .msg(
+                "denom",
+                Decimal::one(),
+                Decimal::one(),
+                Timestamp::default(),
+            )
+            .map_err(|_| ContractError::InvalidOracleTargets {})?;
+    }
+
+    Ok(targets)
+}
+
+pub fn set_oracle_target_enabled(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    enabled: bool,
+) -> ContractResult<Response> {
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let target = config
+        .oracle_targets
+        .iter_mut()
+        .flatten()
+        .find(|target| target.address == address)
+        .ok_or_else(|| ContractError::OracleTargetNotFound {
+            address: address.clone(),
+        })?;
+    let before = to_json_string(target)?;
+    target.enabled = enabled;
+    let after = to_json_string(target)?;
+    CONFIG.save(deps.storage, &config)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "set_oracle_target_enabled",
+        Some(before),
+        Some(after),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_oracle_target_enabled")
+        .add_attribute("address", address)
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+// Splits a fee between the fee recipients by weight, rounding dust goes to the first recipient
+fn fee_shares(config: &Config, amount: Uint128) -> Vec<(Addr, Uint128)> {
+    let recipients = fee_recipients(config);
+    let mut shares: Vec<(Addr, Uint128)> = recipients
+        .into_iter()
+        .map(|recipient| (recipient.address, amount * recipient.weight))
+        .collect();
+    let dust = amount - shares.iter().map(|(_, share)| *share).sum::<Uint128>();
+    shares[0].1 += dust;
+
+    shares.retain(|(_, share)| !share.is_zero());
+    shares
+}
+
+// Splits fees sent out of the contract between the fee recipients and records the amounts sent
+fn split_fees(
+    storage: &mut dyn Storage,
+    config: &Config,
+    amount: Uint128,
+) -> ContractResult<Vec<(Addr, Uint128)>> {
+    let shares = fee_shares(config, amount);
+    for (address, share) in &shares {
+        DISTRIBUTED_FEES.update(storage, address, |distributed| -> StdResult<_> {
+            Ok(distributed.unwrap_or_default() + share)
+        })?;
+    }
+    Ok(shares)
+}
+
+fn fee_bank_msgs(
+    storage: &mut dyn Storage,
+    config: &Config,
+    amount: Uint128,
+) -> ContractResult<Vec<CosmosMsg>> {
+    Ok(split_fees(storage, config, amount)?
+        .into_iter()
+        .map(|(address, share)| {
+            cosmwasm_std::BankMsg::Send {
+                to_address: address.to_string(),
+                amount: vec![cosmwasm_std::Coin::new(
+                    share.u128(),
+                    config.native_token_denom.clone(),
+                )],
+            }
+            .into()
+        })
+        .collect())
+}
+
+// Keeps the fee in the collected fees, or sends it right away when fees are sent to the treasury
+fn collect_fee(
+    storage: &mut dyn Storage,
+    config: &Config,
+    state: &mut State,
+    fee: Uint128,
+) -> ContractResult<Vec<CosmosMsg>> {
+    if fee.is_zero() {
+        return Ok(vec![]);
+    }
+    if !config.send_fees_to_treasury {
+        state.total_fees += fee;
+        return Ok(vec![]);
+    }
+    fee_bank_msgs(storage, config, fee)
+}
+
+// Makes a staged fee config the current one once its delay has passed
+fn apply_pending_protocol_fee_config(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &mut Config,
+) -> StdResult<()> {
+    if let Some(pending) = PENDING_PROTOCOL_FEE_CONFIG.may_load(storage)? {
+        if pending.effective_at <= env.block.time.seconds() {
+            config.protocol_fee_config = pending.protocol_fee_config;
+            CONFIG.save(storage, config)?;
+            PENDING_PROTOCOL_FEE_CONFIG.remove(storage);
+        }
+    }
+    Ok(())
+}
+
+pub fn grant_role(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> ContractResult<Response> {
+    OWNERSHIP.assert_owner(deps.as_ref(), &info.sender)?;
+
+    let address = validate_address(&address, OSMOSIS_ACCOUNT_PREFIX)?;
+    ROLES.save(deps.storage, (role.as_str(), &address), &Empty {})?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "grant_role",
+        None,
+        Some(to_json_string(&(role, &address))?),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> ContractResult<Response> {
+    OWNERSHIP.assert_owner(deps.as_ref(), &info.sender)?;
+
+    let address = Addr::unchecked(address);
+    if !ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(ContractError::MissingRole {
+            sender: address.to_string(),
+            role: role.as_str().to_string(),
+        });
+    }
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "revoke_role",
+        Some(to_json_string(&(role, &address))?),
+        None,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
 pub fn check_stopped(config: &Config) -> Result<(), ContractError> {
     if config.stopped {
         return Err(ContractError::Halted {});
@@ -133,24 +511,30 @@ pub fn execute_liquid_stake(
     amount: Uint128,
     mint_to: Option<String>,
     expected_mint_amount: Option<Uint128>,
+    forward: Option<IbcForward>,
 ) -> ContractResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    apply_pending_protocol_fee_config(deps.storage, &env, &mut config)?;
 
     check_stopped(&config)?;
 
-    // a native user address is 43 chars long
-    if mint_to.is_none() && info.sender.as_str().len() != 43 {
-        return Err(ContractError::MissingMintAddress {});
+    if let Some(forward) = &forward {
+        validate_forward(forward)?;
     }
 
-    // if sent via IBC or the sender is a contract the user needs to provide an osmosis address to mint to
-    let mint_to_address = if mint_to.is_some() && info.sender.as_str().len() != 43 {
-        let mint_to_addr = mint_to.unwrap();
-        validate_address(&mint_to_addr, "osmo")?;
-
-        mint_to_addr
-    } else {
-        info.sender.to_string()
+    // a native user address is 43 chars long
+    let is_native_sender = info.sender.as_str().len() == 43;
+
+    // if sent via IBC or the sender is a contract the user needs to provide an osmosis address to mint to.
+    // When forwarding, the address controlled by the same key as the receiver is used instead
+    let mint_to_address = match (mint_to, &forward) {
+        _ if is_native_sender => info.sender.to_string(),
+        (Some(mint_to_addr), _) => {
+            validate_address(&mint_to_addr, OSMOSIS_ACCOUNT_PREFIX)?;
+            mint_to_addr
+        }
+        (None, Some(forward)) => convert_address_prefix(&forward.receiver, OSMOSIS_ACCOUNT_PREFIX)?,
+        (None, None) => return Err(ContractError::MissingMintAddress {}),
     };
 
     let mut state: State = STATE.load(deps.storage)?;
@@ -170,6 +554,15 @@ pub fn execute_liquid_stake(
         state.total_native_token = Uint128::zero();
     }
 
+    let deposit_fee = config
+        .protocol_fee_config
+        .deposit_fee
+        .as_ref()
+        .map(|fee| fee.compute(amount))
+        .unwrap_or_default();
+    let fee_msgs = collect_fee(deps.storage, &config, &mut state, deposit_fee)?;
+    let amount = amount - deposit_fee;
+
     // Compute mint amount
     let mint_amount = compute_mint_amount(
         state.total_native_token,
@@ -194,32 +587,75 @@ pub fn execute_liquid_stake(
     //       Needs testing and validation - also need to check mint_to_address
     //
     // Mint liquid staking token
+    // When forwarding, the tokens are minted to the contract and sent on from there
     let mint_msg = MsgMint {
         sender: env.contract.address.to_string(),
         amount: Some(Coin {
             denom: config.liquid_stake_token_denom.clone(),
             amount: mint_amount.to_string(),
         }),
-        mint_to_address,
+        mint_to_address: if forward.is_some() {
+            env.contract.address.to_string()
+        } else {
+            mint_to_address.clone()
+        },
     };
 
     // Transfer native token to multisig address
     let sub_msg = transfer_stake_sub_msg(&mut deps, &env, amount, None)?;
-    let update_oracle_msgs = update_oracle_msgs(deps.as_ref(), env, &config)?;
+    let update_oracle_msgs = update_oracle_msgs(&mut deps, env.clone(), &config)?;
 
     state.total_native_token += amount;
     state.total_liquid_stake_token += mint_amount;
 
-    STATE.save(deps.storage, &state)?;
-
-    Ok(Response::new()
+    let event = StakeEvent {
+        sender: info.sender.clone(),
+        recipient: mint_to_address.clone(),
+        amount,
+        mint_amount,
+        deposit_fee,
+        forward_channel: forward.as_ref().map(|f| f.channel_id.clone()),
+        forward_receiver: forward.as_ref().map(|f| f.receiver.clone()),
+    };
+    let mut response = Response::new()
         .add_message(mint_msg)
         .add_messages(update_oracle_msgs)
         .add_submessage(sub_msg)
         .add_attribute("action", "liquid_stake")
         .add_attribute("sender", info.sender.to_string())
         .add_attribute("in_amount", amount)
-        .add_attribute("mint_amount", mint_amount))
+        .add_attribute("mint_amount", mint_amount)
+        .add_event(event.to_event());
+
+    if !deposit_fee.is_zero() {
+        response = response
+            .add_messages(fee_msgs)
+            .add_attribute("deposit_fee", deposit_fee);
+    }
+
+    if let Some(forward) = forward {
+        state.ibc_id_counter += 1;
+        let forward_sub_msg = transfer_forward_sub_msg(
+            &mut deps,
+            &env,
+            ForwardTransfer {
+                channel_id: forward.channel_id.clone(),
+                receiver: forward.receiver.clone(),
+                fallback_address: Addr::unchecked(mint_to_address),
+                denom: config.liquid_stake_token_denom,
+                amount: mint_amount,
+            },
+            state.ibc_id_counter,
+        )?;
+        response = response
+            .add_submessage(forward_sub_msg)
+            .add_attribute("forward_channel", forward.channel_id)
+            .add_attribute("forward_receiver", forward.receiver);
+    }
+
+    STATE.save(deps.storage, &state)?;
+
+    Ok(response)
 }
 
 pub fn execute_liquid_unstake(
@@ -227,6 +663,7 @@ pub fn execute_liquid_unstake(
     _env: Env,
     info: MessageInfo,
     amount: Uint128,
+    owner: Option<String>,
 ) -> ContractResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -234,18 +671,30 @@ pub fn execute_liquid_unstake(
 
     STATE.load(deps.storage)?;
 
+    // a native user address is 43 chars long
+    // if sent via IBC the intermediate sender can't withdraw, so the request is bound to the owner,
+    // either an osmosis address or a celestia address the withdrawal is forwarded to
+    let user = match owner {
+        Some(owner) if info.sender.as_str().len() != 43 => {
+            validate_address(&owner, OSMOSIS_ACCOUNT_PREFIX)
+                .or_else(|_| validate_address(&owner, CELESTIA_ACCOUNT_PREFIX))?;
+            owner
+        }
+        _ => info.sender.to_string(),
+    };
+
     // Load current pending batch
     let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
 
     // Add unstake request to pending batch
     let pending_unstake_request =
-        unstake_requests().may_load(deps.storage, (pending_batch_id, info.sender.to_string()))?;
+        unstake_requests().may_load(deps.storage, (pending_batch_id, user.clone()))?;
     let is_new_request = pending_unstake_request.is_none();
     match pending_unstake_request {
         Some(_) => {
             unstake_requests().update(
                 deps.storage,
-                (pending_batch_id, info.sender.to_string()),
+                (pending_batch_id, user.clone()),
                 |or| -> Result<UnstakeRequest, ContractError> {
                     match or {
                         Some(r) => Ok(UnstakeRequest {
@@ -259,7 +708,7 @@ pub fn execute_liquid_unstake(
             )?;
         }
         None => {
-            new_unstake_request(&mut deps, info.sender.to_string(), pending_batch_id, amount)?;
+            new_unstake_request(&mut deps, user.clone(), pending_batch_id, amount)?;
         }
     }
 
@@ -271,46 +720,53 @@ pub fn execute_liquid_unstake(
             let mut batch = _batch.unwrap();
             batch.batch_total_liquid_stake += amount;
             if is_new_request {
-                batch.unstake_requests_count = Some(batch.unstake_requests_count.unwrap_or(0) + 1);
+                batch.unstake_requests_count += 1;
             }
             Ok(batch)
         },
     )?;
 
-    Ok(Response::new()
+    let event = UnstakeEvent {
+        sender: info.sender.clone(),
+        owner: user.clone(),
+        batch_id: pending_batch_id,
+        amount,
+    };
+    let mut response = Response::new()
         .add_attribute("action", "liquid_unstake")
         .add_attribute("sender", info.sender.to_string())
         .add_attribute("batch", pending_batch_id.to_string())
-        .add_attribute("amount", amount))
+        .add_attribute("amount", amount)
+        .add_event(event.to_event());
+    if user != info.sender.as_str() {
+        response = response.add_attribute("owner", user);
+    }
+
+    Ok(response)
 }
 
 // Submit batch and transition pending batch to submitted
 // Called automatically during liquidUnstake, but also can be called by anyone
 pub fn execute_submit_batch(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
 ) -> ContractResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    apply_pending_protocol_fee_config(deps.storage, &env, &mut config)?;
 
     check_stopped(&config)?;
 
     let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
     let mut batch = BATCHES.load(deps.storage, pending_batch_id)?;
+    let mut state = STATE.load(deps.storage)?;
 
-    if let Some(est_next_batch_time) = batch.next_batch_action_time {
-        // Check if the batch has been submitted
-        if env.block.time.seconds() < est_next_batch_time {
-            return Err(ContractError::BatchNotReady {
-                actual: env.block.time.seconds(),
-                expected: est_next_batch_time,
-            });
-        }
-    } else {
-        // Should not enter as pending batch should have a next batch action time
+    // Check if the batch period has passed or the batch is large enough to be submitted early
+    let readiness = batch_readiness(&config, &state, &batch, env.block.time.seconds());
+    if env.block.time < readiness.ready_at {
         return Err(ContractError::BatchNotReady {
             actual: env.block.time.seconds(),
-            expected: 0u64,
+            expected: readiness.ready_at.seconds(),
         });
     }
 
@@ -324,8 +780,6 @@ pub fn execute_submit_batch(
         return Err(ContractError::BatchEmpty {});
     }
 
-    let mut state = STATE.load(deps.storage)?;
-
     // TODO: Circuit break?
     // Need to add a test for this
     ensure!(
@@ -376,6 +830,9 @@ pub fn execute_submit_batch(
         .checked_sub(batch.batch_total_liquid_stake)
         .unwrap_or_else(|_| Uint128::zero());
 
+    let keeper_reward = take_keeper_reward(&config, &mut state, unbond_amount);
+    state.last_batch_submission = Some(env.block.time.seconds());
+
     STATE.save(deps.storage, &state)?;
 
     // Update batch status
@@ -386,57 +843,164 @@ pub fn execute_submit_batch(
     );
 
     BATCHES.save(deps.storage, batch.id, &batch)?;
+    if let Some(redemption_fee) = &config.protocol_fee_config.redemption_fee {
+        BATCH_REDEMPTION_FEES.save(deps.storage, batch.id, redemption_fee)?;
+    }
 
-    let update_oracle_msgs = update_oracle_msgs(deps.as_ref(), env, &config)?;
+    let update_oracle_msgs = update_oracle_msgs(&mut deps, env, &config)?;
 
-    Ok(Response::new()
+    let event = SubmitBatchEvent {
+        batch_id: batch.id,
+        batch_total: batch.batch_total_liquid_stake,
+        expected_native_unstaked: unbond_amount,
+        keeper: info.sender.clone(),
+        keeper_reward,
+    };
+    let mut response = Response::new()
         .add_message(tokenfactory_burn_msg)
         .add_messages(update_oracle_msgs)
         .add_attribute("action", "submit_batch")
         .add_attribute("batch_id", batch.id.to_string())
         .add_attribute("batch_total", batch.batch_total_liquid_stake)
-        .add_attribute("expected_native_unstaked", unbond_amount))
+        .add_attribute("expected_native_unstaked", unbond_amount)
+        .add_attribute("keeper", info.sender.to_string())
+        .add_event(event.to_event());
+
+    if !keeper_reward.is_zero() {
+        response = response
+            .add_message(cosmwasm_std::BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![cosmwasm_std::Coin::new(
+                    keeper_reward.u128(),
+                    config.native_token_denom,
+                )],
+            })
+            .add_attribute("keeper_reward", keeper_reward);
+    }
+
+    Ok(response)
 }
 
-// doing a "push over pool" pattern for now
-// eventually we can move this to auto-withdraw all funds upon batch completion
-// Reasoning - any one issue in the batch will cause the entire batch to fail
-pub fn execute_withdraw(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    batch_id: u64,
-) -> ContractResult<Response> {
-    let config: Config = CONFIG.load(deps.storage)?;
+// The reward is a share of the batch capped per batch, and limited to what is left
+// in the configured source
+fn take_keeper_reward(config: &Config, state: &mut State, unbond_amount: Uint128) -> Uint128 {
+    let Some(keeper_reward) = &config.keeper_reward else {
+        return Uint128::zero();
+    };
 
-    check_stopped(&config)?;
+    let reward = (unbond_amount * keeper_reward.rate).min(keeper_reward.max_reward);
+    let available = match keeper_reward.source {
+        KeeperRewardSource::Fees => &mut state.total_fees,
+        KeeperRewardSource::Bounty => &mut state.keeper_bounty,
+    };
+    let reward = reward.min(*available);
+    *available -= reward;
 
+    reward
+}
+
+pub fn fund_keeper_bounty(deps: DepsMut, amount: Uint128) -> ContractResult<Response> {
+    let mut state: State = STATE.load(deps.storage)?;
+    state.keeper_bounty += amount;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_keeper_bounty")
+        .add_attribute("amount", amount)
+        .add_attribute("keeper_bounty", state.keeper_bounty))
+}
+
+// Removes the user's request from a received batch and returns the native tokens owed for it
+// after the redemption fee, and the fee
+fn take_withdrawal(
+    deps: &mut DepsMut,
+    batch_id: u64,
+    user: String,
+) -> ContractResult<(Batch, Uint128, Uint128)> {
+    // the batch is archived once its last request was swept
+    if SWEPT_REQUESTS.has(deps.storage, (user.clone(), batch_id)) {
+        return Err(ContractError::WithdrawalSwept { batch_id });
+    }
+
+    migrate_legacy_batch(deps.storage, batch_id)?;
     let _batch = BATCHES.load(deps.storage, batch_id);
     if _batch.is_err() {
         return Err(ContractError::BatchEmpty {});
     }
-    let batch = _batch.unwrap();
+    let mut batch = _batch.unwrap();
 
     if batch.status != BatchStatus::Received {
         return Err(ContractError::TokensAlreadyClaimed { batch_id: batch.id });
     }
-    let received_native_unstaked = batch.received_native_unstaked.as_ref().unwrap();
 
     let _liquid_unstake_request =
-        unstake_requests().may_load(deps.storage, (batch.id, info.sender.to_string()))?;
+        unstake_requests().may_load(deps.storage, (batch.id, user.clone()))?;
     if _liquid_unstake_request.is_none() {
         return Err(ContractError::NoRequestInBatch {});
     }
 
     let unstake_request_amount = _liquid_unstake_request.unwrap().amount;
 
-    let amount = received_native_unstaked
-        .multiply_ratio(unstake_request_amount, batch.batch_total_liquid_stake);
+    // computed before archiving the batch drops its fee
+    let (amount, fee) = compute_withdrawal(deps.as_ref(), &batch, unstake_request_amount)?;
+
+    remove_unstake_request(deps, user, batch.id)?;
+
+    batch.withdrawn_requests_count += 1;
+    batch.withdrawn_native_unstaked += amount;
+
+    save_or_archive_batch(deps, &batch)?;
 
-    // TODO: if all liquid unstake requests have been withdrawn, delete the batch?
-    remove_unstake_request(&mut deps, info.sender.to_string(), batch.id)?;
+    Ok((batch, amount - fee, fee))
+}
+
+// Once every request has been withdrawn or swept the batch is only kept as a summary
+fn save_or_archive_batch(deps: &mut DepsMut, batch: &Batch) -> ContractResult<()> {
+    let has_requests = unstake_requests()
+        .prefix(batch.id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if has_requests {
+        BATCHES.save(deps.storage, batch.id, batch)?;
+        return Ok(());
+    }
+
+    BATCHES.remove(deps.storage, batch.id);
+    BATCH_REDEMPTION_FEES.remove(deps.storage, batch.id);
+    ARCHIVED_BATCHES.save(
+        deps.storage,
+        batch.id,
+        &ArchivedBatch {
+            id: batch.id,
+            batch_total_liquid_stake: batch.batch_total_liquid_stake,
+            received_native_unstaked: batch.received_native_unstaked.unwrap_or_default(),
+            withdrawn_native_unstaked: batch.withdrawn_native_unstaked,
+            swept_native_unstaked: batch.swept_native_unstaked,
+            unstake_requests_count: batch.unstake_requests_count,
+        },
+    )?;
+    Ok(())
+}
+
+// doing a "push over pool" pattern for now
+// eventually we can move this to auto-withdraw all funds upon batch completion
+// Reasoning - any one issue in the batch will cause the entire batch to fail
+pub fn execute_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    batch_id: u64,
+) -> ContractResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    check_stopped(&config)?;
+
+    let (batch, amount, fee) = take_withdrawal(&mut deps, batch_id, info.sender.to_string())?;
 
-    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut state: State = STATE.load(deps.storage)?;
+    let mut messages = collect_fee(deps.storage, &config, &mut state, fee)?;
+    STATE.save(deps.storage, &state)?;
     let send_msg = MsgSend {
         from_address: env.contract.address.to_string(),
         to_address: info.sender.to_string(),
@@ -447,43 +1011,346 @@ pub fn execute_withdraw(
     };
     messages.push(send_msg.into());
 
-    let update_oracle_msgs = update_oracle_msgs(deps.as_ref(), env, &config)?;
+    let update_oracle_msgs = update_oracle_msgs(&mut deps, env, &config)?;
 
-    Ok(Response::new()
+    let event = WithdrawEvent {
+        owner: info.sender.to_string(),
+        batch_id: batch.id,
+        amount,
+        redemption_fee: fee,
+        forwarded: false,
+    };
+    let mut response = Response::new()
         .add_attribute("action", "execute_withdraw")
         .add_attribute("batch", batch.id.to_string())
         .add_attribute("amount", amount.to_string())
-        .add_messages([messages, update_oracle_msgs].concat()))
+        .add_event(event.to_event());
+    if !fee.is_zero() {
+        response = response.add_attribute("redemption_fee", fee);
+    }
+
+    Ok(response.add_messages([messages, update_oracle_msgs].concat()))
+}
+
+// Sends the withdrawal of a request owned by a celestia address back to Celestia; callable by anyone.
+// If the transfer fails the tokens can be claimed by the osmo address of the owner
+pub fn execute_forward_withdrawal(
+    mut deps: DepsMut,
+    env: Env,
+    batch_id: u64,
+    owner: String,
+) -> ContractResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    check_stopped(&config)?;
+
+    validate_address(&owner, CELESTIA_ACCOUNT_PREFIX)?;
+
+    let (batch, amount, fee) = take_withdrawal(&mut deps, batch_id, owner.clone())?;
+
+    let mut state: State = STATE.load(deps.storage)?;
+    let fee_msgs = collect_fee(deps.storage, &config, &mut state, fee)?;
+    state.ibc_id_counter += 1;
+    STATE.save(deps.storage, &state)?;
+
+    let forward_sub_msg = transfer_forward_sub_msg(
+        &mut deps,
+        &env,
+        ForwardTransfer {
+            channel_id: config.ibc_channel_id.clone(),
+            receiver: owner.clone(),
+            fallback_address: Addr::unchecked(convert_address_prefix(
+                &owner,
+                OSMOSIS_ACCOUNT_PREFIX,
+            )?),
+            denom: config.native_token_denom.clone(),
+            amount,
+        },
+        state.ibc_id_counter,
+    )?;
+
+    let update_oracle_msgs = update_oracle_msgs(&mut deps, env, &config)?;
+
+    let event = WithdrawEvent {
+        owner: owner.clone(),
+        batch_id: batch.id,
+        amount,
+        redemption_fee: fee,
+        forwarded: true,
+    };
+    let mut response = Response::new()
+        .add_attribute("action", "forward_withdrawal")
+        .add_attribute("batch", batch.id.to_string())
+        .add_attribute("owner", owner)
+        .add_attribute("amount", amount.to_string())
+        .add_event(event.to_event());
+    if !fee.is_zero() {
+        response = response.add_attribute("redemption_fee", fee);
+    }
+
+    Ok(response
+        .add_submessage(forward_sub_msg)
+        .add_messages(fee_msgs)
+        .add_messages(update_oracle_msgs))
+}
+
+// Sweeps the requests of a received batch that were not withdrawn within the claim window,
+// at most `limit` per call. The owners of swept requests are paid by the treasury, which
+// settles the request with `SettleSweptRequest`
+pub fn execute_sweep_unclaimed(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    batch_id: u64,
+    limit: Option<u32>,
+) -> ContractResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    check_stopped(&config)?;
+
+    let Some(sweep) = config.unclaimed_sweep.clone() else {
+        return Err(ContractError::SweepNotEnabled {});
+    };
+    let is_admin = OWNERSHIP.is_owner(deps.as_ref(), &info.sender)?;
+    if !sweep.permissionless && !is_admin {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+
+    migrate_legacy_batch(deps.storage, batch_id)?;
+    let mut batch = BATCHES
+        .may_load(deps.storage, batch_id)?
+        .ok_or(ContractError::BatchEmpty {})?;
+    if batch.status != BatchStatus::Received {
+        return Err(ContractError::BatchNotClaimable {
+            batch_id: batch.id,
+            status: batch.status,
+        });
+    }
+    check_claim_window(&sweep, &batch, &env, &info.sender, is_admin)?;
+
+    // owners that can't claim from the treasury are left to withdraw themselves
+    let requests = unstake_requests()
+        .prefix(batch.id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, request)| request))
+        .filter(|r| {
+            r.as_ref().map_or(true, |request| {
+                can_claim_swept(deps.as_ref(), &request.user)
+            })
+        })
+        .take(limit.unwrap_or(DEFAULT_SWEEP_LIMIT) as usize)
+        .collect::<Result<Vec<UnstakeRequest>, _>>()?;
+
+    let mut swept = vec![];
+    let mut amount = Uint128::zero();
+    let mut fees = Uint128::zero();
+    for request in &requests {
+        let (owed, fee) = compute_withdrawal(deps.as_ref(), &batch, request.amount)?;
+        batch.swept_native_unstaked += owed;
+        let owed = owed - fee;
+        remove_unstake_request(&mut deps, request.user.clone(), batch.id)?;
+        swept.push((request.user.clone(), owed));
+        amount += owed;
+        fees += fee;
+    }
+
+    // restaked tokens are owed to the owners as the stTIA minted for them
+    let mint_amount = match sweep.destination {
+        SweepDestination::Restake if !amount.is_zero() => {
+            let state: State = STATE.load(deps.storage)?;
+            Some(compute_mint_amount(
+                state.total_native_token,
+                state.total_liquid_stake_token,
+                amount,
+            ))
+        }
+        _ => None,
+    };
+    for (user, owed) in swept {
+        SWEPT_REQUESTS.save(
+            deps.storage,
+            (user.clone(), batch.id),
+            &SweptRequest {
+                batch_id: batch.id,
+                user,
+                amount: owed,
+                liquid_stake_amount: mint_amount.map(|mint| mint.multiply_ratio(owed, amount)),
+                settled: false,
+            },
+        )?;
+    }
+    save_or_archive_batch(&mut deps, &batch)?;
+
+    let mut state: State = STATE.load(deps.storage)?;
+    let fee_msgs = collect_fee(deps.storage, &config, &mut state, fees)?;
+    STATE.save(deps.storage, &state)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "sweep_unclaimed")
+        .add_attribute("batch", batch.id.to_string())
+        .add_attribute("requests", requests.len().to_string())
+        .add_attribute("amount", amount)
+        .add_messages(fee_msgs);
+    if !fees.is_zero() {
+        response = response.add_attribute("redemption_fee", fees);
+    }
+    if amount.is_zero() {
+        return Ok(response);
+    }
+
+    match sweep.destination {
+        SweepDestination::Treasury => {
+            response = response
+                .add_attribute("destination", "treasury")
+                .add_message(cosmwasm_std::BankMsg::Send {
+                    to_address: config.treasury_address.to_string(),
+                    amount: vec![cosmwasm_std::Coin::new(
+                        amount.u128(),
+                        config.native_token_denom,
+                    )],
+                });
+        }
+        SweepDestination::Restake => {
+            // staked like a deposit of the treasury, so it can pay the owners who come back
+            let mint_amount = mint_amount.unwrap_or_default();
+            let mut state: State = STATE.load(deps.storage)?;
+            state.total_native_token += amount;
+            state.total_liquid_stake_token += mint_amount;
+            STATE.save(deps.storage, &state)?;
+
+            let mint_msg = MsgMint {
+                sender: env.contract.address.to_string(),
+                amount: Some(Coin {
+                    denom: config.liquid_stake_token_denom,
+                    amount: mint_amount.to_string(),
+                }),
+                mint_to_address: config.treasury_address.to_string(),
+            };
+            let sub_msg = transfer_stake_sub_msg(&mut deps, &env, amount, None)?;
+            response = response
+                .add_attribute("destination", "restake")
+                .add_attribute("mint_amount", mint_amount)
+                .add_message(mint_msg)
+                .add_submessage(sub_msg);
+        }
+    }
+
+    Ok(response)
+}
+
+// Celestia owners and contracts like the proxy can't claim a swept request from the treasury
+fn can_claim_swept(deps: Deps, user: &str) -> bool {
+    validate_address(user, CELESTIA_ACCOUNT_PREFIX).is_err()
+        && deps.querier.query_wasm_contract_info(user).is_err()
+}
+
+// Batches received before the receive time was tracked can only be swept by the owner
+fn check_claim_window(
+    sweep: &UnclaimedSweepConfig,
+    batch: &Batch,
+    env: &Env,
+    sender: &Addr,
+    is_admin: bool,
+) -> ContractResult<()> {
+    match batch.received_at {
+        Some(received_at) => {
+            let ends_at = received_at + sweep.claim_window;
+            if env.block.time.seconds() < ends_at {
+                return Err(ContractError::ClaimWindowOpen {
+                    batch_id: batch.id,
+                    ends_at,
+                });
+            }
+        }
+        None if !is_admin => {
+            return Err(ContractError::Unauthorized {
+                sender: sender.to_string(),
+            })
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+// Marks a swept request as paid; callable by the treasury once it paid the owner
+pub fn execute_settle_swept_request(
+    deps: DepsMut,
+    info: MessageInfo,
+    batch_id: u64,
+    user: String,
+) -> ContractResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.treasury_address {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+
+    let mut swept = SWEPT_REQUESTS
+        .may_load(deps.storage, (user.clone(), batch_id))?
+        .ok_or(ContractError::NoRequestInBatch {})?;
+    if swept.settled {
+        return Err(ContractError::SweptRequestSettled { batch_id });
+    }
+    swept.settled = true;
+    SWEPT_REQUESTS.save(deps.storage, (user.clone(), batch_id), &swept)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "settle_swept_request")
+        .add_attribute("batch", batch_id.to_string())
+        .add_attribute("user", user)
+        .add_attribute("amount", swept.amount))
+}
+
+// Rewrites batches left in the 0.5.0 layout by the migration; callable by anyone
+pub fn execute_migrate_batches(deps: DepsMut, limit: Option<u32>) -> ContractResult<Response> {
+    if !LEGACY_BATCHES_BEFORE.exists(deps.storage) {
+        return Err(ContractError::NoBatchMigration {});
+    }
+    let limit = limit.unwrap_or(DEFAULT_BATCH_LIMIT).max(1);
+    let (migrated, remaining) = migrate_batches(deps.storage, limit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_batches")
+        .add_attribute("migrated_batches", migrated.to_string())
+        .add_attribute("legacy_batches_remaining", remaining.to_string()))
 }
 
 // Add a validator to the list of validators; callable by the owner
 pub fn execute_add_validator(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     new_validator: String,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_role(deps.as_ref(), &info.sender, Role::ValidatorManager)?;
 
     let mut config = CONFIG.load(deps.storage)?;
     let new_validator_addr = validate_address(&new_validator, CELESTIA_VALIDATOR_PREFIX)?;
 
     // Check if the new_validator is already in the list.
-    if config
-        .validators
-        .iter()
-        .any(|validator| *validator == new_validator_addr)
-    {
+    if config.validators.contains(&new_validator_addr) {
         return Err(ContractError::DuplicateValidator {
             validator: new_validator.clone(),
         });
     }
 
     // Add the new validator to the list.
+    let before = to_json_string(&config.validators)?;
     config.validators.push(new_validator_addr.clone());
 
     // Save the updated config.
     CONFIG.save(deps.storage, &config)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "add_validator",
+        Some(before),
+        Some(to_json_string(&config.validators)?),
+    )?;
 
     Ok(Response::new()
         .add_attribute("action", "add_validator")
@@ -493,17 +1360,18 @@ pub fn execute_add_validator(
 
 pub fn execute_remove_validator(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     validator_to_remove: String,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_role(deps.as_ref(), &info.sender, Role::ValidatorManager)?;
 
     let mut config = CONFIG.load(deps.storage)?;
     let validator_addr_to_remove =
         validate_address(&validator_to_remove, CELESTIA_VALIDATOR_PREFIX)?;
 
     // Find the position of the validator to be removed.
+    let before = to_json_string(&config.validators)?;
     if let Some(pos) = config
         .validators
         .iter()
@@ -520,6 +1388,14 @@ pub fn execute_remove_validator(
 
     // Save the updated config.
     CONFIG.save(deps.storage, &config)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "remove_validator",
+        Some(before),
+        Some(to_json_string(&config.validators)?),
+    )?;
 
     Ok(Response::new()
         .add_attribute("action", "remove_validator")
@@ -527,104 +1403,81 @@ pub fn execute_remove_validator(
         .add_attribute("sender", info.sender))
 }
 
-// Transfer ownership to another account; callable by the owner
-// This will require the new owner to accept to take effect.
-// No need to handle case of overwriting the pending owner
-// Ownership can only be claimed after 7 days to mitigate fat finger errors
-pub fn execute_transfer_ownership(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    new_owner: String,
-) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
-
-    let mut state: State = STATE.load(deps.storage)?;
-    state.pending_owner = Some(deps.api.addr_validate(&new_owner)?);
-    state.owner_transfer_min_time = Some(Timestamp::from_seconds(
-        _env.block.time.seconds() + 60 * 60 * 24 * 7,
-    )); // 7 days
-
-    STATE.save(deps.storage, &state)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "transfer_ownership")
-        .add_attribute("new_owner", new_owner)
-        .add_attribute("previous_owner", info.sender))
-}
-
-// Revoke transfer ownership, callable by the owner
-pub fn execute_revoke_ownership_transfer(
-    deps: DepsMut,
-    _env: Env,
+// Ownership messages are handled by the shared ownership component, renouncing additionally
+// needs the monitors to be able to resume the contract without the owner
+pub fn execute_ownership(
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
+    msg: OwnershipMsg,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
-
-    let mut state = STATE.load(deps.storage)?;
-    state.pending_owner = None;
-    state.owner_transfer_min_time = None;
+    let action = match msg {
+        OwnershipMsg::TransferOwnership { .. } => "transfer_ownership",
+        OwnershipMsg::AcceptOwnership {} => "accept_ownership",
+        OwnershipMsg::RevokeOwnershipTransfer {} => "revoke_ownership_transfer",
+        OwnershipMsg::UpdateOwnershipConfig { .. } => "update_ownership_config",
+        OwnershipMsg::RenounceOwnership { .. } => "renounce_ownership",
+    };
+    if let OwnershipMsg::RenounceOwnership { .. } = msg {
+        OWNERSHIP.assert_owner(deps.as_ref(), &info.sender)?;
+        if CONFIG.load(deps.storage)?.monitor_quorum.is_none() {
+            return Err(OwnershipError::RenounceNotAllowed {
+                reason: "a monitor quorum is needed to resume the contract".to_string(),
+            }
+            .into());
+        }
+    }
 
-    STATE.save(deps.storage, &state)?;
+    let before = to_json_string(&OWNERSHIP.query(deps.as_ref())?)?;
+    let response = OWNERSHIP.execute(deps.branch(), env.clone(), info.clone(), msg)?;
+    let after = to_json_string(&OWNERSHIP.query(deps.as_ref())?)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        action,
+        Some(before),
+        Some(after),
+    )?;
 
-    Ok(Response::new().add_attribute("action", "revoke_ownership_transfer"))
+    Ok(response)
 }
 
-pub fn execute_accept_ownership(
+pub fn recover(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-) -> ContractResult<Response> {
-    let mut state: State = STATE.load(deps.storage)?;
-    if state.owner_transfer_min_time.is_some()
-        && state.owner_transfer_min_time.unwrap().seconds() > _env.block.time.seconds()
-    {
-        return Err(ContractError::OwnershipTransferNotReady {
-            time_to_claim: Timestamp::from_seconds(
-                state.owner_transfer_min_time.unwrap().seconds(),
-            ),
-        });
-    }
-
-    let new_owner = {
-        match state.pending_owner {
-            Some(pending_owner) if pending_owner == info.sender => {
-                state.pending_owner = None;
-                STATE.save(deps.storage, &state)?;
-                Some(pending_owner)
-            }
-            _ => None,
-        }
-    };
-
-    match new_owner {
-        Some(pending_owner) => {
-            ADMIN.set(deps, Some(pending_owner))?;
-            Ok(Response::new()
-                .add_attribute("action", "accept_ownership")
-                .add_attribute("new_owner", info.sender))
+    selected_packets: Option<Vec<u64>>,
+    page: bool,
+) -> Result<Response, ContractError> {
+    // forced recovery is dangerous and should only be done by the admin or recovery operators
+    if selected_packets.is_some() {
+        assert_role(deps.as_ref(), &info.sender, Role::RecoveryOperator)?;
+        let config = CONFIG.load(deps.storage)?;
+        if config
+            .monitor_quorum
+            .map_or(false, |quorum| quorum.recovery)
+        {
+            return Err(ContractError::MonitorQuorumRequired {});
         }
-        None => Err(ContractError::NoPendingOwner {}),
     }
+
+    recover_packets(deps, env, &info.sender, selected_packets, page)
 }
 
-pub fn recover(
+// Forced recoveries of selected packets are audited
+fn recover_packets(
     mut deps: DepsMut,
     env: Env,
-    info: MessageInfo,
+    sender: &Addr,
     selected_packets: Option<Vec<u64>>,
     page: bool,
-) -> Result<Response, ContractError> {
+) -> ContractResult<Response> {
     let page_size = 10;
 
-    // forced recovery is dangerous and should only be done by the admin
-    if selected_packets.is_some() {
-        ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
-    }
-
     // timed out and failed packets
-    let packets: Vec<IBCTransfer> = if selected_packets.is_some() {
-        let selected_packets = selected_packets.unwrap();
+    let forced = selected_packets.is_some();
+    let packets: Vec<IBCTransfer> = if let Some(selected_packets) = selected_packets {
         let mut packets: Vec<IBCTransfer> = vec![];
         for packet_id in selected_packets {
             let packet = INFLIGHT_PACKETS.load(deps.storage, packet_id)?;
@@ -666,6 +1519,16 @@ pub fn recover(
         })
         .reduce(|a, b| a + b)
         .unwrap();
+    if forced {
+        audit(
+            deps.storage,
+            &env,
+            sender,
+            "recover",
+            Some(to_json_string(&packets)?),
+            None,
+        )?;
+    }
 
     // this shouldn't collide. any committed submessage package should have enough upper room in the indexes
     // they are based on block times in nano seconds
@@ -677,9 +1540,16 @@ pub fn recover(
         Some(max_submessage_id + 1),
     )?;
 
+    let event = RecoverEvent {
+        sender: sender.clone(),
+        packets: packets.len() as u64,
+        amount: Uint128::from(total_amount),
+        forced,
+    };
     Ok(Response::new()
         .add_attribute("action", "recover")
         .add_attribute("packets", packets.len().to_string())
+        .add_event(event.to_event())
         .add_submessage(sub_msg))
 }
 
@@ -687,7 +1557,7 @@ pub fn recover(
 #[allow(clippy::too_many_arguments)]
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     batch_period: Option<u64>,
     unbonding_period: Option<u64>,
@@ -700,10 +1570,74 @@ pub fn update_config(
     treasury_address: Option<String>,
     oracle_address: Option<String>,
     send_fees_to_treasury: Option<bool>,
+    keeper_reward: Option<KeeperRewardConfig>,
+    batch_trigger: Option<BatchTriggerConfig>,
+    unclaimed_sweep: Option<UnclaimedSweepConfig>,
+    fee_recipients: Option<Vec<FeeRecipient>>,
+    extra_reward_denoms: Option<Vec<ExtraRewardDenom>>,
+    oracle_targets: Option<Vec<OracleTarget>>,
+    oracle_throttle: Option<OracleThrottleConfig>,
+    monitor_quorum: Option<MonitorQuorumConfig>,
+    clear: Option<Vec<OptionalConfig>>,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    // fee fields and whatever pays out of the contract need the fee manager role, the others
+    // the config manager role
+    let clears = clear.as_deref().unwrap_or_default();
+    let updates_fees = protocol_fee_config.is_some()
+        || send_fees_to_treasury.is_some()
+        || fee_recipients.is_some()
+        || keeper_reward.is_some()
+        || extra_reward_denoms.is_some()
+        || clears.contains(&OptionalConfig::KeeperReward);
+    let updates_config = [
+        batch_period.is_some(),
+        unbonding_period.is_some(),
+        minimum_liquid_stake_amount.is_some(),
+        multisig_address_config.is_some(),
+        native_token_denom.is_some(),
+        channel_id.is_some(),
+        monitors.is_some(),
+        treasury_address.is_some(),
+        oracle_address.is_some(),
+        batch_trigger.is_some(),
+        unclaimed_sweep.is_some(),
+        oracle_targets.is_some(),
+        oracle_throttle.is_some(),
+        monitor_quorum.is_some(),
+        clears
+            .iter()
+            .any(|feature| feature != &OptionalConfig::KeeperReward),
+    ]
+    .contains(&true);
+    if !updates_fees && !updates_config {
+        return Err(ContractError::EmptyConfigUpdate {});
+    }
+    if updates_fees {
+        assert_role(deps.as_ref(), &info.sender, Role::FeeManager)?;
+    }
+    if updates_config {
+        assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+    }
+
+    // a feature can't be both set and cleared
+    for feature in clears {
+        let set = match feature {
+            OptionalConfig::KeeperReward => keeper_reward.is_some(),
+            OptionalConfig::BatchTrigger => batch_trigger.is_some(),
+            OptionalConfig::UnclaimedSweep => unclaimed_sweep.is_some(),
+            OptionalConfig::OracleThrottle => oracle_throttle.is_some(),
+            OptionalConfig::MonitorQuorum => monitor_quorum.is_some(),
+        };
+        if set {
+            return Err(ContractError::InvalidConfigField {
+                field: "clear".to_string(),
+                reason: format!("{} is set in the same update", feature.as_str()),
+            });
+        }
+    }
 
     let mut config: Config = CONFIG.load(deps.storage)?;
+    let before_config = config.clone();
 
     if let Some(batch_period) = batch_period {
         config.batch_period = batch_period;
@@ -714,21 +1648,173 @@ pub fn update_config(
     if let Some(minimum_liquid_stake_amount) = minimum_liquid_stake_amount {
         config.minimum_liquid_stake_amount = minimum_liquid_stake_amount;
     }
-    if let Some(multisig_address_config) = multisig_address_config {
-        config.multisig_address_config = multisig_address_config;
-    }
+    let mut fee_effective_at = None;
     if let Some(protocol_fee_config) = protocol_fee_config {
-        config.protocol_fee_config = protocol_fee_config;
+        validate_protocol_fee_config(&protocol_fee_config)?;
+        apply_pending_protocol_fee_config(deps.storage, &env, &mut config)?;
+        let effective_at = env.block.time.seconds() + FEE_CHANGE_DELAY;
+        let pending = PendingProtocolFeeConfig {
+            protocol_fee_config,
+            effective_at,
+        };
+        PENDING_PROTOCOL_FEE_CONFIG.save(deps.storage, &pending)?;
+        audit(
+            deps.storage,
+            &env,
+            &info.sender,
+            "propose_protocol_fee_config",
+            Some(to_json_string(&config.protocol_fee_config)?),
+            Some(to_json_string(&pending)?),
+        )?;
+        fee_effective_at = Some(effective_at);
     }
+    if let Some(send_fees_to_treasury) = send_fees_to_treasury {
+        config.send_fees_to_treasury = send_fees_to_treasury;
+    }
+    if let Some(keeper_reward) = keeper_reward {
+        if keeper_reward.rate > MAX_KEEPER_REWARD_RATE
+            || keeper_reward.max_reward > MAX_KEEPER_REWARD
+        {
+            return Err(ContractError::InvalidKeeperReward {
+                max_rate: MAX_KEEPER_REWARD_RATE,
+                max_reward: MAX_KEEPER_REWARD,
+            });
+        }
+        config.keeper_reward = Some(keeper_reward);
+    }
+    if let Some(batch_trigger) = batch_trigger {
+        config.batch_trigger = Some(batch_trigger);
+    }
+    if let Some(unclaimed_sweep) = unclaimed_sweep {
+        config.unclaimed_sweep = Some(unclaimed_sweep);
+    }
+    if let Some(oracle_throttle) = oracle_throttle {
+        config.oracle_throttle = Some(oracle_throttle);
+    }
+    let mut clear_monitor_quorum = false;
+    for feature in clear.unwrap_or_default() {
+        match feature {
+            OptionalConfig::KeeperReward => config.keeper_reward = None,
+            OptionalConfig::BatchTrigger => config.batch_trigger = None,
+            OptionalConfig::UnclaimedSweep => config.unclaimed_sweep = None,
+            OptionalConfig::OracleThrottle => config.oracle_throttle = None,
+            OptionalConfig::MonitorQuorum => clear_monitor_quorum = true,
+        }
+    }
+
+    // Sensitive fields are proposed and only changed once the timelock passed
+    let mut change = validate_config_change(
+        multisig_address_config,
+        native_token_denom,
+        channel_id,
+        treasury_address,
+    )?;
     if let Some(monitors) = monitors {
-        config.monitors = Some(validate_addresses(&monitors, "osmo")?);
+        change.monitors =
+            Some(validate_addresses(&monitors, "osmo").map_err(invalid_field("monitors"))?);
     }
-    if let Some(treasury_address) = treasury_address {
-        validate_address(&treasury_address, "osmo")?;
-        config.treasury_address = Addr::unchecked(treasury_address);
+    change.monitor_quorum = monitor_quorum;
+    change.clear_monitor_quorum = clear_monitor_quorum;
+    if change.changes_monitors() {
+        // a stopped contract is resumed by the current monitors
+        if config.stopped {
+            return Err(ContractError::MonitorsChangeWhileStopped {});
+        }
+        let mut proposed_config = config.clone();
+        change.clone().apply(&mut proposed_config);
+        validate_monitor_quorum(&proposed_config)?;
     }
-    if let Some(send_fees_to_treasury) = send_fees_to_treasury {
-        config.send_fees_to_treasury = send_fees_to_treasury;
+    if let Some(fee_recipients) = fee_recipients {
+        change.fee_recipients = Some(validate_fee_recipients(fee_recipients)?);
+    }
+    if let Some(extra_reward_denoms) = extra_reward_denoms {
+        change.extra_reward_denoms =
+            Some(validate_extra_reward_denoms(&config, extra_reward_denoms)?);
+    }
+    if let Some(oracle_address) = oracle_address {
+        if !oracle_address.is_empty() {
+            validate_address(&oracle_address, OSMOSIS_ACCOUNT_PREFIX)
+                .map_err(invalid_field("oracle_address"))?;
+        }
+        change.oracle_address = Some(oracle_address);
+    }
+    if let Some(oracle_targets) = oracle_targets {
+        change.oracle_targets = Some(validate_oracle_targets(oracle_targets)?);
+    }
+    let proposed_attributes = change.attributes()?;
+    let pending_change = if change.is_empty() {
+        None
+    } else {
+        Some(propose_config_change(
+            deps.storage,
+            &env,
+            &info.sender,
+            change,
+        )?)
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "update_config",
+        Some(to_json_string(&before_config)?),
+        Some(to_json_string(&config)?),
+    )?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "update_config")
+        .add_attributes(config_diff(&before_config, &config)?);
+    if let Some(effective_at) = fee_effective_at {
+        let pending = PENDING_PROTOCOL_FEE_CONFIG.load(deps.storage)?;
+        response = response
+            .add_attribute(
+                "proposed_protocol_fee_config",
+                attribute_value(&pending.protocol_fee_config)?,
+            )
+            .add_attribute("fee_effective_at", effective_at.to_string());
+    }
+    if let Some(pending_change) = pending_change {
+        response = response
+            .add_attribute("config_change_id", pending_change.id.to_string())
+            .add_attribute(
+                "config_change_effective_at",
+                pending_change.effective_at.to_string(),
+            )
+            .add_attributes(proposed_attributes);
+    }
+    Ok(response)
+}
+
+pub fn validate_config_change(
+    multisig_address_config: Option<MultisigAddressConfig>,
+    native_token_denom: Option<String>,
+    channel_id: Option<String>,
+    treasury_address: Option<String>,
+) -> ContractResult<ConfigChange> {
+    if let Some(multisig_address_config) = &multisig_address_config {
+        validate_address(
+            multisig_address_config.staker_address.as_str(),
+            CELESTIA_ACCOUNT_PREFIX,
+        )
+        .map_err(invalid_field("staker_address"))?;
+        validate_address(
+            multisig_address_config.reward_collector_address.as_str(),
+            CELESTIA_ACCOUNT_PREFIX,
+        )
+        .map_err(invalid_field("reward_collector_address"))?;
+    }
+
+    let mut change = ConfigChange {
+        multisig_address_config,
+        ..ConfigChange::default()
+    };
+    if let Some(treasury_address) = treasury_address {
+        change.treasury_address = Some(
+            validate_address(&treasury_address, "osmo")
+                .map_err(invalid_field("treasury_address"))?,
+        );
     }
 
     // TODO get reserve token from channel? Maybe leave as safeguard?
@@ -739,37 +1825,309 @@ pub fn update_config(
 
         let channel_id = channel_id.unwrap();
         let native_token_denom = native_token_denom.unwrap();
-        let channel_id_correct = channel_id.starts_with("channel-")
-            && channel_id
-                .strip_prefix("channel-")
-                .unwrap()
-                .parse::<u64>()
-                .is_ok();
-        let native_token_denom_correct = native_token_denom.starts_with("ibc/")
-            && native_token_denom.strip_prefix("ibc/").unwrap().len() == 64;
-
-        if !channel_id_correct || !native_token_denom_correct {
-            return Err(ContractError::IbcChannelConfigWrong {});
+        if !is_valid_channel_id(&channel_id) {
+            return Err(ContractError::InvalidConfigField {
+                field: "channel_id".to_string(),
+                reason: format!("{channel_id} is not a channel id"),
+            });
+        }
+        let native_token_denom_correct = native_token_denom
+            .strip_prefix("ibc/")
+            .map_or(false, |hash| hash.len() == 64);
+        if !native_token_denom_correct {
+            return Err(ContractError::InvalidConfigField {
+                field: "native_token_denom".to_string(),
+                reason: format!("{native_token_denom} is not an ibc denom"),
+            });
         }
 
-        config.ibc_channel_id = channel_id;
-        config.native_token_denom = native_token_denom;
+        change.ibc_channel_id = Some(channel_id);
+        change.native_token_denom = Some(native_token_denom);
     }
 
-    if oracle_address.is_some() {
-        let oracle_address = oracle_address.unwrap();
-        let address = validate_address(&oracle_address, "osmo")?;
-        config.oracle_address = Some(address);
+    Ok(change)
+}
+
+fn propose_config_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    change: ConfigChange,
+) -> ContractResult<PendingConfigChange> {
+    let id = CONFIG_CHANGE_ID_COUNTER
+        .may_load(storage)?
+        .unwrap_or_default()
+        + 1;
+    CONFIG_CHANGE_ID_COUNTER.save(storage, &id)?;
+
+    let proposed_at = env.block.time.seconds();
+    let pending_change = PendingConfigChange {
+        id,
+        change,
+        proposed_at,
+        effective_at: proposed_at + CONFIG_CHANGE_DELAY,
+    };
+    PENDING_CONFIG_CHANGES.save(storage, id, &pending_change)?;
+    audit(
+        storage,
+        env,
+        sender,
+        "propose_config_change",
+        None,
+        Some(to_json_string(&pending_change)?),
+    )?;
+
+    Ok(pending_change)
+}
+
+// Applies a proposed config change once its timelock has passed
+pub fn execute_config_change(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response> {
+    let pending_change = PENDING_CONFIG_CHANGES
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ConfigChangeNotFound { id })?;
+    for role in pending_change.change.roles() {
+        assert_role(deps.as_ref(), &info.sender, role)?;
+    }
+    if env.block.time.seconds() < pending_change.effective_at {
+        return Err(ContractError::ConfigChangeTimelocked {
+            id,
+            effective_at: pending_change.effective_at,
+        });
     }
 
+    let mut config = CONFIG.load(deps.storage)?;
+    let before_config = config.clone();
+    if pending_change.change.changes_monitors() {
+        if config.stopped {
+            return Err(ContractError::MonitorsChangeWhileStopped {});
+        }
+        pending_change.change.clone().apply(&mut config);
+        // other changes of the monitors may have been executed since it was proposed
+        validate_monitor_quorum(&config)?;
+    } else {
+        pending_change.change.apply(&mut config);
+    }
+    // the native denom may have changed
+    if let Some(extra_reward_denoms) = config.extra_reward_denoms.clone() {
+        validate_extra_reward_denoms(&config, extra_reward_denoms)?;
+    }
     CONFIG.save(deps.storage, &config)?;
+    PENDING_CONFIG_CHANGES.remove(deps.storage, id);
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "execute_config_change",
+        Some(to_json_string(&before_config)?),
+        Some(to_json_string(&config)?),
+    )?;
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+    Ok(Response::new()
+        .add_attribute("action", "execute_config_change")
+        .add_attribute("config_change_id", id.to_string())
+        .add_attributes(config_diff(&before_config, &config)?))
 }
 
-pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> ContractResult<Response> {
-    let config: Config = CONFIG.load(deps.storage)?;
-    let mut state: State = STATE.load(deps.storage)?;
+pub fn cancel_config_change(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response> {
+    let pending_change = PENDING_CONFIG_CHANGES
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ConfigChangeNotFound { id })?;
+    for role in pending_change.change.roles() {
+        assert_role(deps.as_ref(), &info.sender, role)?;
+    }
+    PENDING_CONFIG_CHANGES.remove(deps.storage, id);
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "cancel_config_change",
+        Some(to_json_string(&pending_change)?),
+        None,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_config_change")
+        .add_attribute("config_change_id", id.to_string()))
+}
+
+// Monitors can veto a proposed config change they don't agree with
+pub fn veto_config_change(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config
+        .monitors
+        .as_deref()
+        .unwrap_or_default()
+        .contains(&info.sender)
+    {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+
+    let pending_change = remove_config_change(deps.storage, id)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "veto_config_change",
+        Some(to_json_string(&pending_change)?),
+        None,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "veto_config_change")
+        .add_attribute("config_change_id", id.to_string())
+        .add_attribute("monitor", info.sender))
+}
+
+fn remove_config_change(storage: &mut dyn Storage, id: u64) -> ContractResult<PendingConfigChange> {
+    let pending_change = PENDING_CONFIG_CHANGES
+        .may_load(storage, id)?
+        .ok_or(ContractError::ConfigChangeNotFound { id })?;
+    PENDING_CONFIG_CHANGES.remove(storage, id);
+    Ok(pending_change)
+}
+
+fn validate_monitor_quorum(config: &Config) -> ContractResult<()> {
+    if let Some(monitor_quorum) = &config.monitor_quorum {
+        let monitors = config.monitors.as_deref().unwrap_or_default().len();
+        if monitor_quorum.threshold == 0 || monitor_quorum.threshold as usize > monitors {
+            return Err(ContractError::InvalidMonitorQuorum {});
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_protocol_fee_config(protocol_fee_config: &ProtocolFeeConfig) -> ContractResult<()> {
+    if protocol_fee_config.dao_treasury_fee > MAX_DAO_TREASURY_FEE {
+        return Err(ContractError::DaoTreasuryFeeTooHigh {
+            fee: protocol_fee_config.dao_treasury_fee,
+            max: MAX_DAO_TREASURY_FEE,
+        });
+    }
+    let fees = [
+        &protocol_fee_config.deposit_fee,
+        &protocol_fee_config.redemption_fee,
+    ];
+    if fees.into_iter().flatten().any(|fee| fee.bps > 10_000) {
+        return Err(ContractError::InvalidFee {});
+    }
+    Ok(())
+}
+
+// Recipients need valid osmo addresses without duplicates, and weights summing up to 100%.
+// An empty list is allowed and sends all fees to the treasury
+fn validate_fee_recipients(recipients: Vec<FeeRecipient>) -> ContractResult<Vec<FeeRecipient>> {
+    if recipients.is_empty() {
+        return Ok(recipients);
+    }
+
+    let addresses = recipients
+        .iter()
+        .map(|recipient| recipient.address.to_string())
+        .collect();
+    validate_addresses(&addresses, OSMOSIS_ACCOUNT_PREFIX)
+        .map_err(|_| ContractError::InvalidFeeRecipients {})?;
+
+    let total_weight = recipients
+        .iter()
+        .try_fold(Decimal::zero(), |total, recipient| {
+            total.checked_add(recipient.weight)
+        })
+        .map_err(|_| ContractError::InvalidFeeRecipients {})?;
+    if total_weight != Decimal::one() || recipients.iter().any(|r| r.weight.is_zero()) {
+        return Err(ContractError::InvalidFeeRecipients {});
+    }
+
+    Ok(recipients)
+}
+
+// Denoms need to be unique and can't be the native or liquid stake denom
+fn validate_extra_reward_denoms(
+    config: &Config,
+    extra_reward_denoms: Vec<ExtraRewardDenom>,
+) -> ContractResult<Vec<ExtraRewardDenom>> {
+    let mut seen = HashSet::new();
+    for extra_reward_denom in &extra_reward_denoms {
+        let denom = extra_reward_denom.denom.as_str();
+        if denom.is_empty()
+            || denom == config.native_token_denom
+            || denom == config.liquid_stake_token_denom
+            || !seen.insert(denom)
+        {
+            return Err(ContractError::InvalidExtraRewardDenoms {});
+        }
+        if let ExtraRewardDestination::Contract { address, .. } = &extra_reward_denom.destination {
+            validate_address(address.as_str(), OSMOSIS_ACCOUNT_PREFIX)
+                .map_err(|_| ContractError::InvalidExtraRewardDenoms {})?;
+        }
+    }
+
+    Ok(extra_reward_denoms)
+}
+
+// Forwards the rewards received in the extra reward denoms, other denoms than the native one
+// that are not listed go to the treasury so they don't get stuck in the contract
+fn forward_extra_rewards(
+    storage: &mut dyn Storage,
+    config: &Config,
+    funds: &[cosmwasm_std::Coin],
+) -> ContractResult<(Vec<CosmosMsg>, Vec<cosmwasm_std::Coin>)> {
+    let extra_reward_denoms = config.extra_reward_denoms.as_deref().unwrap_or_default();
+    let mut msgs = vec![];
+    let mut forwarded = vec![];
+    for coin in funds {
+        if coin.denom == config.native_token_denom || coin.amount.is_zero() {
+            continue;
+        }
+        let destination = extra_reward_denoms
+            .iter()
+            .find(|extra| extra.denom == coin.denom)
+            .map_or(&ExtraRewardDestination::Treasury, |extra| {
+                &extra.destination
+            });
+
+        msgs.push(match destination {
+            ExtraRewardDestination::Treasury => cosmwasm_std::BankMsg::Send {
+                to_address: config.treasury_address.to_string(),
+                amount: vec![coin.clone()],
+            }
+            .into(),
+            ExtraRewardDestination::Contract { address, msg } => cosmwasm_std::WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: msg.clone(),
+                funds: vec![coin.clone()],
+            }
+            .into(),
+        });
+        FORWARDED_EXTRA_REWARDS.update(storage, &coin.denom, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default() + coin.amount)
+        })?;
+        forwarded.push(coin.clone());
+    }
+
+    Ok((msgs, forwarded))
+}
+
+pub fn receive_rewards(deps: DepsMut, env: Env, info: MessageInfo) -> ContractResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    apply_pending_protocol_fee_config(deps.storage, &env, &mut config)?;
+    let state: State = STATE.load(deps.storage)?;
 
     check_stopped(&config)?;
 
@@ -796,19 +2154,51 @@ pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> Contra
         });
     }
 
+    let (extra_reward_msgs, extra_rewards) =
+        forward_extra_rewards(deps.storage, &config, &info.funds)?;
+
     let coin = info
         .funds
         .iter()
         .find(|c| c.denom == config.native_token_denom);
-    if coin.is_none() {
-        return Err(ContractError::Payment(PaymentError::NoFunds {}));
+    let (response, mut event) = match coin {
+        Some(coin) => receive_native_rewards(deps, env, config, state, coin.amount)?,
+        None if !extra_rewards.is_empty() => (
+            Response::new().add_attribute("action", "receive_rewards"),
+            RewardsEvent {
+                amount: Uint128::zero(),
+                amount_after_fees: Uint128::zero(),
+                fee: Uint128::zero(),
+                fee_mint_amount: Uint128::zero(),
+                extra_rewards: vec![],
+            },
+        ),
+        None => return Err(ContractError::Payment(PaymentError::NoFunds {})),
+    };
+    if extra_rewards.is_empty() {
+        return Ok(response.add_event(event.to_event()));
     }
 
-    let amount = coin.unwrap().amount;
-    let fee = config
-        .protocol_fee_config
-        .dao_treasury_fee
-        .multiply_ratio(amount, 100_000u128);
+    event.extra_rewards = extra_rewards.clone();
+    let extra_rewards = extra_rewards
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok(response
+        .add_messages(extra_reward_msgs)
+        .add_attribute("extra_rewards", extra_rewards)
+        .add_event(event.to_event()))
+}
+
+fn receive_native_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    config: Config,
+    mut state: State,
+    amount: Uint128,
+) -> ContractResult<(Response, RewardsEvent)> {
+    let fee = amount * config.protocol_fee_config.dao_treasury_fee;
     let amount_after_fees = amount.checked_sub(fee);
     if amount_after_fees.is_err() {
         return Err(ContractError::ReceiveRewardsTooSmall {
@@ -818,6 +2208,10 @@ pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> Contra
     }
     let amount_after_fees = amount_after_fees.unwrap();
 
+    if config.protocol_fee_config.reward_fee_mode == RewardFeeMode::Mint {
+        return receive_rewards_minting_fee(deps, env, config, state, amount, fee);
+    }
+
     // update the accounting of tokens
     state.total_native_token += amount_after_fees;
     state.total_reward_amount += amount;
@@ -829,27 +2223,90 @@ pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> Contra
 
     // transfer the funds to Celestia to be staked
     let ibc_transfer_msg = transfer_stake_sub_msg(&mut deps, &env, amount_after_fees, None)?;
-    let update_oracle_msgs = update_oracle_msgs(deps.as_ref(), env, &config)?;
+    let update_oracle_msgs = update_oracle_msgs(&mut deps, env, &config)?;
 
     let mut response = Response::new()
         .add_attribute("action", "receive_rewards")
-        .add_attribute("action", "transfer_stake")
         .add_attribute("amount", amount)
         .add_attribute("amount_after_fees", amount_after_fees)
         .add_messages(update_oracle_msgs)
         .add_submessage(ibc_transfer_msg);
 
     if config.send_fees_to_treasury {
-        response = response.add_message(cosmwasm_std::BankMsg::Send {
-            to_address: config.treasury_address.to_string(),
-            amount: vec![cosmwasm_std::Coin::new(
-                fee.u128(),
-                config.native_token_denom,
-            )],
-        });
+        response = response.add_messages(fee_bank_msgs(deps.storage, &config, fee)?);
     }
 
-    Ok(response)
+    let event = RewardsEvent {
+        amount,
+        amount_after_fees,
+        fee,
+        fee_mint_amount: Uint128::zero(),
+        extra_rewards: vec![],
+    };
+    Ok((response, event))
+}
+
+// Stakes the whole reward and mints the stTIA worth the fee at the post-reward rate to the
+// treasury: m / (L + m) * N' = fee, so m = fee * L / (N' - fee)
+fn receive_rewards_minting_fee(
+    mut deps: DepsMut,
+    env: Env,
+    config: Config,
+    mut state: State,
+    amount: Uint128,
+    fee: Uint128,
+) -> ContractResult<(Response, RewardsEvent)> {
+    state.total_native_token += amount;
+    state.total_reward_amount += amount;
+    let fee_mint_amount = if fee.is_zero() {
+        Uint128::zero()
+    } else {
+        fee.checked_multiply_ratio(
+            state.total_liquid_stake_token,
+            state.total_native_token - fee,
+        )
+        .map_err(|_| ContractError::ReceiveRewardsTooSmall {
+            amount,
+            minimum: fee,
+        })?
+    };
+    state.total_liquid_stake_token += fee_mint_amount;
+
+    STATE.save(deps.storage, &state)?;
+
+    let ibc_transfer_msg = transfer_stake_sub_msg(&mut deps, &env, amount, None)?;
+    let update_oracle_msgs = update_oracle_msgs(&mut deps, env.clone(), &config)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "receive_rewards")
+        .add_attribute("amount", amount)
+        .add_attribute("amount_after_fees", amount)
+        .add_attribute("fee_mint_amount", fee_mint_amount)
+        .add_messages(update_oracle_msgs)
+        .add_submessage(ibc_transfer_msg);
+
+    // minted to the fee recipients like the fees sent in native tokens
+    if !fee_mint_amount.is_zero() {
+        for (address, share) in fee_shares(&config, fee_mint_amount) {
+            response = response.add_message(MsgMint {
+                sender: env.contract.address.to_string(),
+                amount: Some(Coin {
+                    denom: config.liquid_stake_token_denom.clone(),
+                    amount: share.to_string(),
+                }),
+                mint_to_address: address.to_string(),
+            });
+        }
+    }
+
+    let event = RewardsEvent {
+        amount,
+        amount_after_fees: amount,
+        fee,
+        fee_mint_amount,
+        extra_rewards: vec![],
+    };
+    Ok((response, event))
 }
 
 pub fn receive_unstaked_tokens(
@@ -888,6 +2345,7 @@ pub fn receive_unstaked_tokens(
 
     let amount = coin.unwrap().amount;
 
+    migrate_legacy_batch(deps.storage, batch_id)?;
     let mut batch: Batch = BATCHES.load(deps.storage, batch_id)?;
 
     if batch.status != BatchStatus::Submitted {
@@ -897,37 +2355,33 @@ pub fn receive_unstaked_tokens(
         });
     }
 
-    if batch.next_batch_action_time.is_none() {
-        return Err(ContractError::BatchNotClaimable {
-            batch_id: batch.id,
-            status: batch.status,
-        });
-    }
-    let next_batch_action_time = batch.next_batch_action_time.unwrap();
-    if next_batch_action_time > env.block.time.seconds() {
+    if batch.next_batch_action_time > env.block.time.seconds() {
         return Err(ContractError::BatchNotReady {
             actual: env.block.time.seconds(),
-            expected: next_batch_action_time,
+            expected: batch.next_batch_action_time,
         });
     }
 
     batch.received_native_unstaked = Some(amount);
+    batch.received_at = Some(env.block.time.seconds());
     batch.update_status(BatchStatus::Received, None);
 
     BATCHES.save(deps.storage, batch.id, &batch)?;
 
+    let event = ReceiveUnstakedEvent { batch_id, amount };
     Ok(Response::new()
         .add_attribute("action", "receive_unstaked_tokens")
         .add_attribute("batch", batch_id.to_string())
-        .add_attribute("amount", amount))
+        .add_attribute("amount", amount)
+        .add_event(event.to_event()))
 }
 
-pub fn circuit_breaker(deps: DepsMut, _env: Env, info: MessageInfo) -> ContractResult<Response> {
+pub fn circuit_breaker(deps: DepsMut, env: Env, info: MessageInfo) -> ContractResult<Response> {
     let sender = info.sender.to_string();
 
     let mut config: Config = CONFIG.load(deps.storage)?;
 
-    if ADMIN.assert_admin(deps.as_ref(), &info.sender).is_err()
+    if assert_role(deps.as_ref(), &info.sender, Role::Pauser).is_err()
         && !config
             .monitors
             .as_deref()
@@ -938,8 +2392,17 @@ pub fn circuit_breaker(deps: DepsMut, _env: Env, info: MessageInfo) -> ContractR
         return Err(ContractError::Unauthorized { sender });
     }
 
+    let before = to_json_string(&config.stopped)?;
     config.stopped = true;
     CONFIG.save(deps.storage, &config)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "circuit_breaker",
+        Some(before),
+        Some(to_json_string(&config.stopped)?),
+    )?;
 
     Ok(Response::new().add_attribute("action", "circuit_breaker"))
 }
@@ -952,20 +2415,50 @@ pub fn resume_contract(
     total_liquid_stake_token: Uint128,
     total_reward_amount: Uint128,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_role(deps.as_ref(), &info.sender, Role::Resumer)?;
+    if CONFIG.load(deps.storage)?.monitor_quorum.is_some() {
+        return Err(ContractError::MonitorQuorumRequired {});
+    }
 
+    resume(
+        deps,
+        env,
+        &info.sender,
+        total_native_token,
+        total_liquid_stake_token,
+        total_reward_amount,
+    )
+}
+
+fn resume(
+    mut deps: DepsMut,
+    env: Env,
+    sender: &Addr,
+    total_native_token: Uint128,
+    total_liquid_stake_token: Uint128,
+    total_reward_amount: Uint128,
+) -> ContractResult<Response> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     config.stopped = false;
     CONFIG.save(deps.storage, &config)?;
 
     let mut state: State = STATE.load(deps.storage)?;
+    let before = to_json_string(&state)?;
 
     state.total_native_token = total_native_token;
     state.total_liquid_stake_token = total_liquid_stake_token;
     state.total_reward_amount = total_reward_amount;
 
-    let update_oracle_msgs = update_oracle_msgs(deps.as_ref(), env, &config)?;
+    audit(
+        deps.storage,
+        &env,
+        sender,
+        "resume_contract",
+        Some(before),
+        Some(to_json_string(&state)?),
+    )?;
+    let update_oracle_msgs = update_oracle_msgs(&mut deps, env, &config)?;
 
     STATE.save(deps.storage, &state)?;
 
@@ -977,6 +2470,168 @@ pub fn resume_contract(
         .add_messages(update_oracle_msgs))
 }
 
+fn assert_monitor(config: &Config, sender: &Addr) -> ContractResult<()> {
+    if !config
+        .monitors
+        .as_deref()
+        .unwrap_or_default()
+        .contains(sender)
+    {
+        return Err(ContractError::Unauthorized {
+            sender: sender.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn propose_monitor_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: MonitorAction,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_monitor(&config, &info.sender)?;
+    let quorum = config
+        .monitor_quorum
+        .as_ref()
+        .ok_or(ContractError::MonitorQuorumNotEnabled {})?;
+    if matches!(action, MonitorAction::RecoverPendingIbcTransfers { .. }) && !quorum.recovery {
+        return Err(ContractError::MonitorQuorumNotEnabled {});
+    }
+
+    // expired proposals are dropped, the open ones count towards the proposer's limit
+    let now = env.block.time.seconds();
+    let proposals = MONITOR_PROPOSALS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut open_proposals = 0;
+    for (id, proposal) in proposals {
+        if proposal.expires_at <= now {
+            MONITOR_PROPOSALS.remove(deps.storage, id);
+        } else if proposal.approvals.first() == Some(&info.sender) {
+            open_proposals += 1;
+        }
+    }
+    if open_proposals >= MAX_OPEN_MONITOR_PROPOSALS {
+        return Err(ContractError::TooManyMonitorProposals {
+            monitor: info.sender.to_string(),
+            max: MAX_OPEN_MONITOR_PROPOSALS,
+        });
+    }
+
+    let id = MONITOR_PROPOSAL_ID_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    MONITOR_PROPOSAL_ID_COUNTER.save(deps.storage, &id)?;
+    let proposal = MonitorProposal {
+        id,
+        action,
+        approvals: vec![info.sender.clone()],
+        expires_at: now + quorum.proposal_ttl,
+    };
+
+    let response = Response::new()
+        .add_attribute("action", "propose_monitor_action")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("monitor", info.sender.clone());
+    execute_monitor_proposal_if_approved(deps, env, &info.sender, &config, proposal, response)
+}
+
+pub fn approve_monitor_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_monitor(&config, &info.sender)?;
+
+    let mut proposal = MONITOR_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::MonitorProposalNotFound { id })?;
+    if env.block.time.seconds() >= proposal.expires_at {
+        return Err(ContractError::MonitorProposalExpired {
+            id,
+            expires_at: proposal.expires_at,
+        });
+    }
+    if proposal.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {
+            id,
+            sender: info.sender.to_string(),
+        });
+    }
+    proposal.approvals.push(info.sender.clone());
+
+    let response = Response::new()
+        .add_attribute("action", "approve_monitor_action")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("monitor", info.sender.clone());
+    execute_monitor_proposal_if_approved(deps, env, &info.sender, &config, proposal, response)
+}
+
+// Only approvals of current monitors count towards the quorum, the monitor whose approval
+// reached it is audited as the actor
+fn execute_monitor_proposal_if_approved(
+    deps: DepsMut,
+    env: Env,
+    sender: &Addr,
+    config: &Config,
+    proposal: MonitorProposal,
+    response: Response,
+) -> ContractResult<Response> {
+    let threshold = config
+        .monitor_quorum
+        .as_ref()
+        .ok_or(ContractError::MonitorQuorumNotEnabled {})?
+        .threshold;
+    let monitors = config.monitors.as_deref().unwrap_or_default();
+    let approvals = proposal
+        .approvals
+        .iter()
+        .filter(|approval| monitors.contains(approval))
+        .count();
+    if approvals < threshold as usize {
+        MONITOR_PROPOSALS.save(deps.storage, proposal.id, &proposal)?;
+        return Ok(response.add_attribute("approvals", approvals.to_string()));
+    }
+
+    MONITOR_PROPOSALS.remove(deps.storage, proposal.id);
+    let executed = match proposal.action {
+        MonitorAction::ResumeContract {
+            total_native_token,
+            total_liquid_stake_token,
+            total_reward_amount,
+        } => resume(
+            deps,
+            env,
+            sender,
+            total_native_token,
+            total_liquid_stake_token,
+            total_reward_amount,
+        )?,
+        MonitorAction::RecoverPendingIbcTransfers { selected_packets } => {
+            recover_packets(deps, env, sender, Some(selected_packets), false)?
+        }
+    };
+
+    // the response keeps its own `action`, the executed one is reported as `executed_action`
+    let attributes = executed
+        .attributes
+        .into_iter()
+        .map(|attr| match attr.key.as_str() {
+            "action" => Attribute::new("executed_action", attr.value),
+            _ => attr,
+        });
+    Ok(response
+        .add_attribute("approvals", approvals.to_string())
+        .add_attributes(attributes)
+        .add_events(executed.events)
+        .add_submessages(executed.messages))
+}
+
 pub fn handle_ibc_reply(deps: DepsMut, msg: cosmwasm_std::Reply) -> ContractResult<Response> {
     // Parse the result from the underlying chain call (IBC send)
     let SubMsgResult::Ok(SubMsgResponse { data: Some(b), .. }) = msg.result else {
@@ -1016,6 +2671,76 @@ pub fn handle_ibc_reply(deps: DepsMut, msg: cosmwasm_std::Reply) -> ContractResu
     Ok(response)
 }
 
+pub fn handle_forward_reply(deps: DepsMut, msg: cosmwasm_std::Reply) -> ContractResult<Response> {
+    let SubMsgResult::Ok(SubMsgResponse { data: Some(b), .. }) = msg.result else {
+        return Err(ContractError::FailedIBCTransfer {
+            msg: format!("failed reply: {:?}", msg.result),
+        });
+    };
+
+    let transfer_response =
+        MsgTransferResponse::decode(&b[..]).map_err(|_e| ContractError::FailedIBCTransfer {
+            msg: format!("could not decode response: {b}"),
+        })?;
+
+    let forward = FORWARD_WAITING_FOR_REPLY.load(deps.storage, msg.id)?;
+    FORWARD_WAITING_FOR_REPLY.remove(deps.storage, msg.id);
+
+    // Save as in-flight to be able to credit the fallback address if the transfer fails
+    INFLIGHT_FORWARDS.save(
+        deps.storage,
+        (forward.channel_id.clone(), transfer_response.sequence),
+        &forward,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "handle_forward_reply")
+        .add_attribute("status", "ibc_message_successfully_submitted")
+        .add_attribute("channel", forward.channel_id)
+        .add_attribute(
+            "packet_sequence",
+            format!("{:?}", transfer_response.sequence),
+        ))
+}
+
+// Sends all funds of failed forward transfers held for the sender
+pub fn execute_claim_funds(deps: DepsMut, info: MessageInfo) -> ContractResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    check_stopped(&config)?;
+
+    let claimable = CLAIMABLE_FUNDS
+        .prefix(info.sender.to_string())
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if claimable.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let mut coins = vec![];
+    for (denom, amount) in claimable {
+        CLAIMABLE_FUNDS.remove(deps.storage, (info.sender.to_string(), denom.clone()));
+        coins.push(cosmwasm_std::Coin::new(amount.u128(), denom));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_funds")
+        .add_attribute("receiver", info.sender.to_string())
+        .add_attribute(
+            "amount",
+            coins
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .add_message(cosmwasm_std::BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins,
+        }))
+}
+
 fn save_ibc_waiting_for_reply(
     deps: &mut DepsMut,
     id: u64,
@@ -1043,7 +2768,7 @@ pub fn fee_withdraw(
     info: MessageInfo,
     amount: Uint128,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_role(deps.as_ref(), &info.sender, Role::FeeManager)?;
 
     let config: Config = CONFIG.load(deps.storage)?;
     let mut state: State = STATE.load(deps.storage)?;
@@ -1052,21 +2777,36 @@ pub fn fee_withdraw(
         return Err(ContractError::InsufficientFunds {});
     }
 
+    let before = to_json_string(&state.total_fees)?;
     state.total_fees = state.total_fees.checked_sub(amount).unwrap();
     STATE.save(deps.storage, &state)?;
+    audit(
+        deps.storage,
+        &env,
+        &info.sender,
+        "fee_withdraw",
+        Some(before),
+        Some(to_json_string(&state.total_fees)?),
+    )?;
 
-    let send_msg = MsgSend {
+    let shares = split_fees(deps.storage, &config, amount)?;
+    let receivers = shares
+        .iter()
+        .map(|(address, _)| address.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let send_msgs = shares.into_iter().map(|(address, share)| MsgSend {
         from_address: env.contract.address.to_string(),
-        to_address: config.treasury_address.to_string(),
+        to_address: address.to_string(),
         amount: vec![Coin {
-            denom: config.native_token_denom,
-            amount: amount.to_string(),
+            denom: config.native_token_denom.clone(),
+            amount: share.to_string(),
         }],
-    };
+    });
 
     Ok(Response::new()
         .add_attribute("action", "fee_withdraw")
-        .add_attribute("receiver", config.treasury_address.to_string())
+        .add_attribute("receiver", receivers)
         .add_attribute("amount", amount)
-        .add_message(send_msg))
+        .add_messages(send_msgs))
 }
@@ -1,9 +1,49 @@
-use cosmwasm_std::{Addr, Decimal, Deps, Order, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    to_json_string, Addr, Attribute, Decimal, Decimal256, Deps, Env, Order, StdError, StdResult,
+    Storage, Timestamp, Uint128,
+};
 use cw_storage_plus::{Bound, Bounder, KeyDeserialize, Map};
+use milky_way::staking::Batch;
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 
-use crate::state::STATE;
+use crate::error::ContractError;
+use crate::msg::BatchReadiness;
+use crate::state::{
+    Config, FeeRecipient, IbcForward, PendingProtocolFeeConfig, ProtocolFeeConfig, RateSnapshot,
+    Role, State, BATCH_REDEMPTION_FEES, OWNERSHIP, PENDING_PROTOCOL_FEE_CONFIG, RATE_SNAPSHOTS,
+    ROLES, STATE,
+};
+
+// The admin has every role
+pub fn assert_role(deps: Deps, sender: &Addr, role: Role) -> Result<(), ContractError> {
+    if OWNERSHIP.is_owner(deps, sender)? || ROLES.has(deps.storage, (role.as_str(), sender)) {
+        return Ok(());
+    }
+    Err(ContractError::MissingRole {
+        sender: sender.to_string(),
+        role: role.as_str().to_string(),
+    })
+}
+
+// Native tokens a request of `shares` withdraws from a received batch, and the redemption fee
+// withheld from them
+pub fn compute_withdrawal(
+    deps: Deps,
+    batch: &Batch,
+    shares: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let amount = batch
+        .received_native_unstaked
+        .unwrap_or_default()
+        .multiply_ratio(shares, batch.batch_total_liquid_stake);
+    let fee = BATCH_REDEMPTION_FEES
+        .may_load(deps.storage, batch.id)?
+        .map(|fee| fee.compute(amount))
+        .unwrap_or_default();
+    Ok((amount, fee))
+}
 
 pub fn validate_address(address: &str, prefix: &str) -> StdResult<Addr> {
     if let Ok((decoded_prefix, _, _)) = bech32::decode(address) {
@@ -17,6 +57,163 @@ pub fn validate_address(address: &str, prefix: &str) -> StdResult<Addr> {
     }
 }
 
+// `old_<field>` and `new_<field>` for each config field that changed
+pub fn config_diff(before: &Config, after: &Config) -> StdResult<Vec<Attribute>> {
+    let mut attrs = vec![];
+    diff(
+        &mut attrs,
+        "native_token_denom",
+        &before.native_token_denom,
+        &after.native_token_denom,
+    )?;
+    diff(
+        &mut attrs,
+        "treasury_address",
+        &before.treasury_address,
+        &after.treasury_address,
+    )?;
+    diff(&mut attrs, "monitors", &before.monitors, &after.monitors)?;
+    diff(
+        &mut attrs,
+        "validators",
+        &before.validators,
+        &after.validators,
+    )?;
+    diff(
+        &mut attrs,
+        "batch_period",
+        &before.batch_period,
+        &after.batch_period,
+    )?;
+    diff(
+        &mut attrs,
+        "unbonding_period",
+        &before.unbonding_period,
+        &after.unbonding_period,
+    )?;
+    diff(
+        &mut attrs,
+        "protocol_fee_config",
+        &before.protocol_fee_config,
+        &after.protocol_fee_config,
+    )?;
+    diff(
+        &mut attrs,
+        "staker_address",
+        &before.multisig_address_config.staker_address,
+        &after.multisig_address_config.staker_address,
+    )?;
+    diff(
+        &mut attrs,
+        "reward_collector_address",
+        &before.multisig_address_config.reward_collector_address,
+        &after.multisig_address_config.reward_collector_address,
+    )?;
+    diff(
+        &mut attrs,
+        "minimum_liquid_stake_amount",
+        &before.minimum_liquid_stake_amount,
+        &after.minimum_liquid_stake_amount,
+    )?;
+    diff(
+        &mut attrs,
+        "ibc_channel_id",
+        &before.ibc_channel_id,
+        &after.ibc_channel_id,
+    )?;
+    diff(&mut attrs, "stopped", &before.stopped, &after.stopped)?;
+    diff(
+        &mut attrs,
+        "oracle_address",
+        &before.oracle_address,
+        &after.oracle_address,
+    )?;
+    diff(
+        &mut attrs,
+        "send_fees_to_treasury",
+        &before.send_fees_to_treasury,
+        &after.send_fees_to_treasury,
+    )?;
+    diff(
+        &mut attrs,
+        "keeper_reward",
+        &before.keeper_reward,
+        &after.keeper_reward,
+    )?;
+    diff(
+        &mut attrs,
+        "batch_trigger",
+        &before.batch_trigger,
+        &after.batch_trigger,
+    )?;
+    diff(
+        &mut attrs,
+        "unclaimed_sweep",
+        &before.unclaimed_sweep,
+        &after.unclaimed_sweep,
+    )?;
+    diff(
+        &mut attrs,
+        "fee_recipients",
+        &before.fee_recipients,
+        &after.fee_recipients,
+    )?;
+    diff(
+        &mut attrs,
+        "extra_reward_denoms",
+        &before.extra_reward_denoms,
+        &after.extra_reward_denoms,
+    )?;
+    diff(
+        &mut attrs,
+        "oracle_targets",
+        &before.oracle_targets,
+        &after.oracle_targets,
+    )?;
+    diff(
+        &mut attrs,
+        "oracle_throttle",
+        &before.oracle_throttle,
+        &after.oracle_throttle,
+    )?;
+    diff(
+        &mut attrs,
+        "monitor_quorum",
+        &before.monitor_quorum,
+        &after.monitor_quorum,
+    )?;
+    Ok(attrs)
+}
+
+fn diff<T: Serialize + PartialEq>(
+    attrs: &mut Vec<Attribute>,
+    field: &str,
+    old: &T,
+    new: &T,
+) -> StdResult<()> {
+    if old != new {
+        attrs.push(Attribute::new(
+            format!("old_{field}"),
+            attribute_value(old)?,
+        ));
+        attrs.push(Attribute::new(
+            format!("new_{field}"),
+            attribute_value(new)?,
+        ));
+    }
+    Ok(())
+}
+
+// JSON of the value, without the quotes around strings and addresses
+pub fn attribute_value<T: Serialize>(value: &T) -> StdResult<String> {
+    let json = to_json_string(value)?;
+    Ok(json
+        .strip_prefix('"')
+        .and_then(|json| json.strip_suffix('"'))
+        .map(str::to_string)
+        .unwrap_or(json))
+}
+
 // Validates addresses are valid and unique and returns a vector of validated addresses
 pub fn validate_addresses(addresses: &Vec<String>, prefix: &str) -> StdResult<Vec<Addr>> {
     let mut validated = Vec::new();
@@ -36,6 +233,96 @@ pub fn validate_addresses(addresses: &Vec<String>, prefix: &str) -> StdResult<Ve
     Ok(validated)
 }
 
+// Re-encodes a bech32 address with a different prefix, e.g. a celestia address into the
+// osmo address controlled by the same key
+pub fn convert_address_prefix(address: &str, prefix: &str) -> StdResult<String> {
+    let (_, data, variant) =
+        bech32::decode(address).map_err(|_| StdError::generic_err("Invalid address"))?;
+    bech32::encode(prefix, data, variant).map_err(|_| StdError::generic_err("Invalid address"))
+}
+
+pub fn is_valid_channel_id(channel_id: &str) -> bool {
+    channel_id
+        .strip_prefix("channel-")
+        .map(|id| id.parse::<u64>().is_ok())
+        .unwrap_or(false)
+}
+
+pub fn validate_forward(forward: &IbcForward) -> Result<(), ContractError> {
+    if !is_valid_channel_id(&forward.channel_id) || bech32::decode(&forward.receiver).is_err() {
+        return Err(ContractError::InvalidForward {});
+    }
+    Ok(())
+}
+
+// A pending batch can be submitted once the batch period has passed, or earlier if it
+// exceeds one of the size limits of the batch trigger. Either way submissions are spaced
+// by the minimum submission interval
+pub fn batch_readiness(config: &Config, state: &State, batch: &Batch, now: u64) -> BatchReadiness {
+    let next_batch_action_time = batch.next_batch_action_time;
+    let time_triggered = now >= next_batch_action_time;
+
+    let (size_triggered, min_submission_interval) = match &config.batch_trigger {
+        Some(trigger) => {
+            let exceeds_liquid_stake = trigger
+                .max_batch_liquid_stake
+                .map(|max| batch.batch_total_liquid_stake > max)
+                .unwrap_or(false);
+            let exceeds_requests = trigger
+                .max_unstake_requests
+                .map(|max| batch.unstake_requests_count > max)
+                .unwrap_or(false);
+            (
+                exceeds_liquid_stake || exceeds_requests,
+                trigger.min_submission_interval,
+            )
+        }
+        None => (false, 0),
+    };
+
+    let spaced_from = state
+        .last_batch_submission
+        .map(|last| last + min_submission_interval)
+        .unwrap_or(0);
+    let ready_at = if size_triggered {
+        spaced_from
+    } else {
+        next_batch_action_time.max(spaced_from)
+    };
+
+    BatchReadiness {
+        ready: now >= ready_at && batch.unstake_requests_count > 0,
+        time_triggered,
+        size_triggered,
+        ready_at: Timestamp::from_seconds(ready_at),
+    }
+}
+
+// Recipients of the fees sent out of the contract, the treasury if none are configured
+pub fn fee_recipients(config: &Config) -> Vec<FeeRecipient> {
+    match &config.fee_recipients {
+        Some(recipients) if !recipients.is_empty() => recipients.clone(),
+        _ => vec![FeeRecipient {
+            address: config.treasury_address.clone(),
+            weight: Decimal::one(),
+        }],
+    }
+}
+
+// Returns the fee config in effect and the staged one if its delay hasn't passed yet
+pub fn protocol_fee_configs(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> StdResult<(ProtocolFeeConfig, Option<PendingProtocolFeeConfig>)> {
+    match PENDING_PROTOCOL_FEE_CONFIG.may_load(deps.storage)? {
+        Some(pending) if pending.effective_at <= env.block.time.seconds() => {
+            Ok((pending.protocol_fee_config, None))
+        }
+        pending => Ok((config.protocol_fee_config.clone(), pending)),
+    }
+}
+
 pub fn compute_mint_amount(
     total_native_token: Uint128,
     total_liquid_stake_token: Uint128,
@@ -165,6 +452,65 @@ pub fn get_rates(deps: &Deps) -> (Decimal, Decimal) {
     }
 }
 
+// Same as `get_rates`, without the precision loss of converting to `Decimal`
+pub fn get_rates_256(state: &State) -> (Decimal256, Decimal256) {
+    if state.total_liquid_stake_token.is_zero() || state.total_native_token.is_zero() {
+        (Decimal256::zero(), Decimal256::zero())
+    } else {
+        // return redemption_rate, purchase_rate
+        (
+            Decimal256::from_ratio(state.total_native_token, state.total_liquid_stake_token),
+            Decimal256::from_ratio(state.total_liquid_stake_token, state.total_native_token),
+        )
+    }
+}
+
+// Averages the rate snapshots over the `window` seconds before `now`, weighted by how long
+// each was in effect. Returns None without snapshots
+pub fn compute_twap(
+    storage: &dyn Storage,
+    now: u64,
+    window: u64,
+) -> StdResult<Option<RateSnapshot>> {
+    let start = now.saturating_sub(window);
+    let mut end = now;
+    let mut total_time = 0u64;
+    let mut redemption_rate = Decimal256::zero();
+    let mut purchase_rate = Decimal256::zero();
+    let mut latest = None;
+
+    let snapshots = RATE_SNAPSHOTS.range(
+        storage,
+        None,
+        Some(Bound::inclusive(now)),
+        Order::Descending,
+    );
+    for snapshot in snapshots {
+        let (time, snapshot) = snapshot?;
+        let from = time.max(start);
+        let duration = Decimal256::from_ratio(end - from, 1u64);
+        redemption_rate += snapshot.redemption_rate * duration;
+        purchase_rate += snapshot.purchase_rate * duration;
+        total_time += end - from;
+        end = from;
+        if latest.is_none() {
+            latest = Some(snapshot);
+        }
+        if time <= start {
+            break;
+        }
+    }
+
+    if total_time == 0 {
+        return Ok(latest);
+    }
+    let total_time = Decimal256::from_ratio(total_time, 1u64);
+    Ok(Some(RateSnapshot {
+        redemption_rate: redemption_rate / total_time,
+        purchase_rate: purchase_rate / total_time,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,6 +563,28 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn convert_address_prefix_roundtrip() {
+        let osmo =
+            convert_address_prefix("celestia1sfhy3emrgp26wnzuu64p06kpkxd9phel74e0yx", "osmo")
+                .unwrap();
+        assert_eq!(osmo, "osmo1sfhy3emrgp26wnzuu64p06kpkxd9phel8ym0ge");
+
+        let celestia = convert_address_prefix(&osmo, "celestia").unwrap();
+        assert_eq!(celestia, "celestia1sfhy3emrgp26wnzuu64p06kpkxd9phel74e0yx");
+
+        assert!(convert_address_prefix("invalid", "osmo").is_err());
+    }
+
+    #[test]
+    fn channel_id_validation() {
+        assert!(is_valid_channel_id("channel-0"));
+        assert!(is_valid_channel_id("channel-123"));
+        assert!(!is_valid_channel_id("channel-"));
+        assert!(!is_valid_channel_id("channel-abc"));
+        assert!(!is_valid_channel_id("connection-1"));
+    }
+
     // Basic test - based on figures from excalidraw
     #[test]
     fn test_compute_mint_amount() {
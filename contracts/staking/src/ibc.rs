@@ -1,6 +1,8 @@
-use cosmwasm_std::{DepsMut, Response};
+use cosmwasm_std::{DepsMut, Response, Uint128};
 
-use crate::state::{self, Config, CONFIG};
+use crate::state::{
+    self, ibc::ForwardTransfer, Config, CLAIMABLE_FUNDS, CONFIG, INFLIGHT_FORWARDS,
+};
 use crate::{error::ContractError, state::INFLIGHT_PACKETS};
 
 /// Called by the chain when the ack for a packet that has configured this contract as its
@@ -32,6 +34,12 @@ pub fn receive_ack(
     //     "received ack for packet {source_channel:?} {sequence:?}: {ack:?}, {success:?}"
     // ));
 
+    // Forwarded packets can be sent on any channel, including the staking one
+    let forward = INFLIGHT_FORWARDS.may_load(deps.storage, (source_channel.clone(), sequence))?;
+    if let Some(forward) = forward {
+        return receive_forward_lifecycle(deps, source_channel, sequence, forward, success);
+    }
+
     let config: Config = CONFIG.load(deps.storage)?;
     if source_channel != config.ibc_channel_id {
         // If the ack is not for this contract, return a success
@@ -71,6 +79,11 @@ pub fn receive_timeout(
     source_channel: String,
     sequence: u64,
 ) -> Result<Response, ContractError> {
+    let forward = INFLIGHT_FORWARDS.may_load(deps.storage, (source_channel.clone(), sequence))?;
+    if let Some(forward) = forward {
+        return receive_forward_lifecycle(deps, source_channel, sequence, forward, false);
+    }
+
     let config: Config = CONFIG.load(deps.storage)?;
     if source_channel != config.ibc_channel_id {
         // If the ack is not for this contract, return a success
@@ -93,3 +106,38 @@ pub fn receive_timeout(
 
     Ok(response.add_attribute("error", "ibc packet timed out"))
 }
+
+// A forwarded packet is no longer tracked once it completed. If it failed, the tokens
+// are back in the contract and are credited to the fallback address
+fn receive_forward_lifecycle(
+    deps: DepsMut,
+    source_channel: String,
+    sequence: u64,
+    forward: ForwardTransfer,
+    success: bool,
+) -> Result<Response, ContractError> {
+    INFLIGHT_FORWARDS.remove(deps.storage, (source_channel.clone(), sequence));
+
+    let response = Response::new()
+        .add_attribute("action", "receive_forward_lifecycle")
+        .add_attribute("channel", source_channel)
+        .add_attribute("sequence", sequence.to_string());
+
+    if success {
+        return Ok(response.add_attribute("msg", "success"));
+    }
+
+    CLAIMABLE_FUNDS.update(
+        deps.storage,
+        (forward.fallback_address.to_string(), forward.denom.clone()),
+        |claimable| -> Result<Uint128, ContractError> {
+            Ok(claimable.unwrap_or_default() + forward.amount)
+        },
+    )?;
+
+    Ok(response
+        .add_attribute("error", "ibc forward failed")
+        .add_attribute("claimable_by", forward.fallback_address)
+        .add_attribute("amount", forward.amount)
+        .add_attribute("denom", forward.denom))
+}
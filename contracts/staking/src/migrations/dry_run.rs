@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use cosmwasm_std::{Attribute, Deps, DepsMut, Env, Order, Record, StdResult, Storage};
+
+use crate::contract::CONTRACT_VERSION;
+use crate::migrations::{migrate, migration_plan};
+use crate::msg::MigrateMsg;
+
+#[derive(Debug, PartialEq)]
+pub struct MigrationDryRun {
+    pub from_version: String,
+    pub to_version: String,
+    /// Migration steps as `<from>-><to>`, empty if the layout didn't change
+    pub steps: Vec<String>,
+    /// Attributes the migration would emit
+    pub attributes: Vec<Attribute>,
+    /// Why the migration or the checks of the migrated state would fail
+    pub error: Option<String>,
+}
+
+// Runs the migration of this build on a contract's storage, e.g. an export of the deployed
+// contract, without committing it
+pub fn dry_run(deps: Deps, env: &Env, msg: &MigrateMsg) -> StdResult<MigrationDryRun> {
+    let from_version = cw2::get_contract_version(deps.storage)?.version;
+    let mut response = MigrationDryRun {
+        from_version,
+        to_version: CONTRACT_VERSION.to_string(),
+        steps: vec![],
+        attributes: vec![],
+        error: None,
+    };
+
+    let mut storage = DryRunStorage {
+        base: deps.storage,
+        changes: BTreeMap::new(),
+    };
+    let deps = DepsMut {
+        storage: &mut storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+    if let Ok((_, steps)) = migration_plan(deps.storage) {
+        response.steps = steps
+            .iter()
+            .map(|step| format!("{}->{}", step.from, step.to))
+            .collect();
+    }
+    match migrate(deps, env, msg) {
+        Ok(res) => response.attributes = res.attributes,
+        Err(err) => response.error = Some(err.to_string()),
+    }
+    Ok(response)
+}
+
+// Writes of a dry run are kept in memory on top of the contract storage
+struct DryRunStorage<'a> {
+    base: &'a dyn Storage,
+    changes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl Storage for DryRunStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.changes.get(key) {
+            Some(value) => value.clone(),
+            None => self.base.get(key),
+        }
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        if let (Some(start), Some(end)) = (start, end) {
+            if start >= end {
+                return Box::new(std::iter::empty());
+            }
+        }
+
+        let mut records: BTreeMap<Vec<u8>, Vec<u8>> =
+            self.base.range(start, end, Order::Ascending).collect();
+        let lower = start.map_or(Bound::Unbounded, |start| Bound::Included(start.to_vec()));
+        let upper = end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.to_vec()));
+        for (key, value) in self.changes.range((lower, upper)) {
+            match value {
+                Some(value) => records.insert(key.clone(), value.clone()),
+                None => records.remove(key),
+            };
+        }
+
+        match order {
+            Order::Ascending => Box::new(records.into_iter()),
+            Order::Descending => Box::new(records.into_iter().rev()),
+        }
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.changes.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.changes.insert(key.to_vec(), None);
+    }
+}
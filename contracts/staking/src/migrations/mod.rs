@@ -1 +1,139 @@
+#[cfg(test)]
+pub mod dry_run;
 pub mod v0_4_20;
+pub mod v0_5_0;
+pub mod v0_6_0;
+
+use cosmwasm_std::{Attribute, Deps, DepsMut, Env, Response, StdError, Storage};
+use cw2::set_contract_version;
+use milky_way::staking::BatchStatus;
+use semver::Version;
+
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION, MAX_DAO_TREASURY_FEE};
+use crate::error::{ContractError, ContractResult};
+use crate::msg::MigrateMsg;
+use crate::state::{BATCHES, CONFIG, OWNERSHIP, PENDING_BATCH_ID, STATE};
+
+pub struct MigrationStep {
+    pub from: &'static str,
+    pub to: &'static str,
+    pub migrate: fn(DepsMut, &Env, &MigrateMsg) -> ContractResult<Vec<Attribute>>,
+}
+
+// Storage layout changes, oldest first. Each step reads the layout of its `from` version and
+// writes the one of its `to` version. Versions in between share the layout of the previous step,
+// so upgrading between them only bumps the contract version
+pub const MIGRATIONS: [MigrationStep; 3] = [
+    MigrationStep {
+        from: "0.4.18",
+        to: "0.4.20",
+        migrate: v0_4_20::migrate,
+    },
+    MigrationStep {
+        from: "0.4.20",
+        to: "0.5.0",
+        migrate: v0_5_0::migrate,
+    },
+    MigrationStep {
+        from: "0.5.0",
+        to: "0.6.0",
+        migrate: v0_6_0::migrate,
+    },
+];
+
+fn parse_version(version: &str) -> ContractResult<Version> {
+    version
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid contract version").into())
+}
+
+// Steps migrating the stored version to this one
+pub fn migration_plan(
+    storage: &dyn Storage,
+) -> ContractResult<(String, Vec<&'static MigrationStep>)> {
+    let current_version = cw2::get_contract_version(storage)?;
+    if CONTRACT_NAME != current_version.contract.as_str() {
+        return Err(StdError::generic_err("Cannot upgrade to a different contract").into());
+    }
+
+    let version = parse_version(&current_version.version)?;
+    let new_version = parse_version(CONTRACT_VERSION)?;
+    if version > new_version {
+        return Err(StdError::generic_err("Cannot upgrade to a previous contract version").into());
+    }
+    if version == new_version {
+        return Err(StdError::generic_err("Cannot migrate to the same version.").into());
+    }
+    if version < parse_version(MIGRATIONS[0].from)? {
+        return Err(ContractError::NoMigrationPath {
+            from: current_version.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    let mut steps = vec![];
+    for step in MIGRATIONS.iter() {
+        let to = parse_version(step.to)?;
+        if to > version && to <= new_version {
+            steps.push(step);
+        }
+    }
+    Ok((current_version.version, steps))
+}
+
+// Runs the migration steps, bumps the version and checks the migrated state
+pub fn migrate(mut deps: DepsMut, env: &Env, msg: &MigrateMsg) -> ContractResult<Response> {
+    let (from_version, steps) = migration_plan(deps.storage)?;
+
+    let mut attributes = vec![];
+    for step in &steps {
+        attributes.extend((step.migrate)(deps.branch(), env, msg)?);
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    check_invariants(deps.as_ref())?;
+
+    let steps = steps
+        .iter()
+        .map(|step| format!("{}->{}", step.from, step.to))
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("steps", steps)
+        .add_attributes(attributes))
+}
+
+// The stored state has to be readable in the current layout and consistent
+pub fn check_invariants(deps: Deps) -> ContractResult<()> {
+    let invariant = |reason: &str| ContractError::MigrationInvariant {
+        reason: reason.to_string(),
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    OWNERSHIP.query(deps)?;
+
+    if config.protocol_fee_config.dao_treasury_fee > MAX_DAO_TREASURY_FEE {
+        return Err(invariant("dao treasury fee above the maximum"));
+    }
+    if let Some(monitor_quorum) = &config.monitor_quorum {
+        let monitors = config.monitors.as_deref().unwrap_or_default().len();
+        if monitor_quorum.threshold == 0 || monitor_quorum.threshold as usize > monitors {
+            return Err(invariant("monitor quorum threshold out of range"));
+        }
+    }
+    if state.pending_owner.is_some() || state.owner_transfer_min_time.is_some() {
+        return Err(invariant("pending owner left in the state"));
+    }
+
+    let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
+    let pending_batch = BATCHES
+        .may_load(deps.storage, pending_batch_id)?
+        .ok_or_else(|| invariant("pending batch not found"))?;
+    if pending_batch.status != BatchStatus::Pending {
+        return Err(invariant("pending batch is not pending"));
+    }
+    Ok(())
+}
@@ -1,19 +1,16 @@
 use crate::{
-    contract::{CONTRACT_NAME, CONTRACT_VERSION},
-    error::ContractResult,
-    state::{Config, CONFIG},
+    error::{ContractError, ContractResult},
+    migrations::v0_5_0::v0_4_20_state,
+    msg::MigrateMsg,
 };
-use cosmwasm_std::{DepsMut, Env, Response};
-use cw2::assert_contract_version;
-
-const FROM_VERSION: &str = "0.4.18";
+use cosmwasm_std::{attr, Attribute, DepsMut, Env};
 
 pub mod v0_4_18_state {
     use cosmwasm_schema::cw_serde;
     use cosmwasm_std::{Addr, Uint128};
     use cw_storage_plus::Item;
 
-    use crate::state::{MultisigAddressConfig, ProtocolFeeConfig};
+    use crate::migrations::v0_5_0::v0_4_20_state::{MultisigAddressConfig, ProtocolFeeConfig};
 
     pub const CONFIG: Item<Config> = Item::new("config");
 
@@ -38,13 +35,16 @@ pub mod v0_4_18_state {
     }
 }
 
-pub fn migrate(deps: DepsMut, _env: Env, send_fees_to_treasury: bool) -> ContractResult<Response> {
-    // Ensure that we are migrating from the correct version.
-    assert_contract_version(deps.storage, CONTRACT_NAME, FROM_VERSION)?;
+pub fn migrate(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> ContractResult<Vec<Attribute>> {
+    let send_fees_to_treasury =
+        msg.send_fees_to_treasury()
+            .ok_or(ContractError::MissingMigrationOption {
+                option: "send_fees_to_treasury".to_string(),
+            })?;
 
     let old_config = v0_4_18_state::CONFIG.load(deps.storage)?;
-    // Convert the old config format to the new one.
-    let new_config = Config {
+    // Convert the old config format to the one of 0.4.20.
+    let new_config = v0_4_20_state::Config {
         native_token_denom: old_config.native_token_denom,
         liquid_stake_token_denom: old_config.liquid_stake_token_denom,
         treasury_address: old_config.treasury_address,
@@ -61,10 +61,10 @@ pub fn migrate(deps: DepsMut, _env: Env, send_fees_to_treasury: bool) -> Contrac
         send_fees_to_treasury,
     };
     // Save the new config.
-    CONFIG.save(deps.storage, &new_config)?;
+    v0_4_20_state::CONFIG.save(deps.storage, &new_config)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "migrate")
-        .add_attribute("from_version", FROM_VERSION)
-        .add_attribute("to_version", CONTRACT_VERSION))
+    Ok(vec![attr(
+        "send_fees_to_treasury",
+        send_fees_to_treasury.to_string(),
+    )])
 }
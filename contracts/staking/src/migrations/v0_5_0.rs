@@ -0,0 +1,128 @@
+use crate::{
+    error::ContractResult,
+    msg::MigrateMsg,
+    state::{Config, CONFIG, OWNERSHIP, STATE},
+};
+use cosmwasm_std::{attr, Attribute, DepsMut, Env};
+use milky_way::ownership::PendingOwnership;
+
+pub mod v0_4_20_state {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Decimal, Uint128};
+    use cw_storage_plus::Item;
+
+    // Maximum of the 0.5.0 fee, a later change of the limit doesn't apply to this step
+    const MAX_DAO_TREASURY_FEE: Decimal = Decimal::percent(20);
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+
+    #[cw_serde]
+    pub struct ProtocolFeeConfig {
+        pub dao_treasury_fee: Uint128, // not using a fraction, fee percentage=x/100000
+    }
+
+    impl ProtocolFeeConfig {
+        // Fees above the maximum are lowered to it
+        pub fn migrate(self) -> crate::state::ProtocolFeeConfig {
+            let dao_treasury_fee = Decimal::from_ratio(self.dao_treasury_fee, 100_000u128);
+            crate::state::ProtocolFeeConfig {
+                dao_treasury_fee: dao_treasury_fee.min(MAX_DAO_TREASURY_FEE),
+                deposit_fee: None,
+                redemption_fee: None,
+                reward_fee_mode: crate::state::RewardFeeMode::Native,
+            }
+        }
+    }
+
+    #[cw_serde]
+    pub struct MultisigAddressConfig {
+        pub staker_address: Addr,
+        pub reward_collector_address: Addr,
+    }
+
+    impl MultisigAddressConfig {
+        pub fn migrate(self) -> crate::state::MultisigAddressConfig {
+            crate::state::MultisigAddressConfig {
+                staker_address: self.staker_address,
+                reward_collector_address: self.reward_collector_address,
+            }
+        }
+    }
+
+    #[cw_serde]
+    pub struct Config {
+        pub native_token_denom: String,
+        pub liquid_stake_token_denom: String,
+        pub treasury_address: Addr,
+        pub monitors: Option<Vec<Addr>>,
+        pub validators: Vec<Addr>,
+        pub batch_period: u64,
+        pub unbonding_period: u64,
+        pub protocol_fee_config: ProtocolFeeConfig,
+        pub multisig_address_config: MultisigAddressConfig,
+        pub minimum_liquid_stake_amount: Uint128,
+        pub ibc_channel_id: String,
+        pub stopped: bool,
+        pub oracle_address: Option<Addr>,
+        pub send_fees_to_treasury: bool,
+    }
+}
+
+pub fn migrate(deps: DepsMut, env: &Env, _msg: &MigrateMsg) -> ContractResult<Vec<Attribute>> {
+    let old_config = v0_4_20_state::CONFIG.load(deps.storage)?;
+    // Convert the fee from x/100000 to a decimal.
+    let new_config = Config {
+        native_token_denom: old_config.native_token_denom,
+        liquid_stake_token_denom: old_config.liquid_stake_token_denom,
+        treasury_address: old_config.treasury_address,
+        monitors: old_config.monitors,
+        validators: old_config.validators,
+        batch_period: old_config.batch_period,
+        unbonding_period: old_config.unbonding_period,
+        protocol_fee_config: old_config.protocol_fee_config.migrate(),
+        multisig_address_config: old_config.multisig_address_config.migrate(),
+        minimum_liquid_stake_amount: old_config.minimum_liquid_stake_amount,
+        ibc_channel_id: old_config.ibc_channel_id,
+        stopped: old_config.stopped,
+        oracle_address: old_config.oracle_address,
+        send_fees_to_treasury: old_config.send_fees_to_treasury,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+    };
+    // Save the new config.
+    CONFIG.save(deps.storage, &new_config)?;
+
+    // Move a pending ownership transfer to the ownership component, it lapses after the
+    // default transfer expiry
+    let mut state = STATE.load(deps.storage)?;
+    if let Some(owner) = state.pending_owner.take() {
+        let min_time = state
+            .owner_transfer_min_time
+            .take()
+            .unwrap_or(env.block.time);
+        let expiry = min_time
+            .max(env.block.time)
+            .plus_seconds(OWNERSHIP.config(deps.storage)?.transfer_expiry);
+        OWNERSHIP.set_pending(
+            deps.storage,
+            &PendingOwnership {
+                owner,
+                min_time,
+                expiry,
+            },
+        )?;
+    }
+    state.owner_transfer_min_time = None;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(vec![attr(
+        "dao_treasury_fee",
+        new_config.protocol_fee_config.dao_treasury_fee.to_string(),
+    )])
+}
@@ -0,0 +1,182 @@
+use crate::{
+    error::ContractResult,
+    msg::MigrateMsg,
+    state::{unstake_requests, UnstakeRequest, BATCHES, LEGACY_BATCHES_BEFORE, PENDING_BATCH_ID},
+};
+use cosmwasm_std::{attr, Attribute, DepsMut, Env, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+use milky_way::staking::Batch;
+
+pub const DEFAULT_BATCH_LIMIT: u32 = 50;
+
+pub mod v0_5_0_state {
+    use std::collections::BTreeMap;
+
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_storage_plus::Map;
+    use milky_way::staking::BatchStatus;
+
+    pub const BATCHES: Map<u64, Batch> = Map::new("batches");
+
+    #[cw_serde]
+    pub struct LiquidUnstakeRequest {
+        pub user: Addr,
+        pub shares: Uint128,
+        pub redeemed: bool,
+    }
+
+    #[cw_serde]
+    pub struct Batch {
+        pub id: u64,
+        pub batch_total_liquid_stake: Uint128,
+        pub expected_native_unstaked: Option<Uint128>,
+        pub received_native_unstaked: Option<Uint128>,
+        pub liquid_unstake_requests: Option<BTreeMap<String, LiquidUnstakeRequest>>,
+        pub unstake_requests_count: Option<u64>,
+        #[serde(default)]
+        pub withdrawn_requests_count: u64,
+        #[serde(default)]
+        pub withdrawn_native_unstaked: Uint128,
+        #[serde(default)]
+        pub swept_native_unstaked: Uint128,
+        #[serde(default)]
+        pub received_at: Option<u64>,
+        pub next_batch_action_time: Option<u64>,
+        pub status: BatchStatus,
+    }
+}
+
+// Rewrites the pending batch right away, older batches are left to `MigrateBatches`
+pub fn migrate(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> ContractResult<Vec<Attribute>> {
+    let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
+    LEGACY_BATCHES_BEFORE.save(deps.storage, &(pending_batch_id + 1))?;
+
+    let limit = msg.batch_limit().unwrap_or(DEFAULT_BATCH_LIMIT);
+    let (migrated, remaining) = migrate_batches(deps.storage, limit.max(1))?;
+    Ok(vec![
+        attr("migrated_batches", migrated.to_string()),
+        attr("legacy_batches_remaining", remaining.to_string()),
+    ])
+}
+
+// Rewrites up to `limit` legacy batches, newest first. Returns the number of migrated and
+// remaining batches, the cursor is removed once none are left
+pub fn migrate_batches(storage: &mut dyn Storage, limit: u32) -> StdResult<(u64, u64)> {
+    let Some(before) = LEGACY_BATCHES_BEFORE.may_load(storage)? else {
+        return Ok((0, 0));
+    };
+
+    let batches = v0_5_0_state::BATCHES
+        .range(
+            storage,
+            None,
+            Some(Bound::exclusive(before)),
+            Order::Descending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut cursor = before;
+    for (id, legacy) in &batches {
+        save_migrated_batch(storage, legacy)?;
+        cursor = *id;
+    }
+
+    let remaining = v0_5_0_state::BATCHES
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(cursor)),
+            Order::Ascending,
+        )
+        .count() as u64;
+    if remaining == 0 {
+        LEGACY_BATCHES_BEFORE.remove(storage);
+    } else {
+        LEGACY_BATCHES_BEFORE.save(storage, &cursor)?;
+    }
+    Ok((batches.len() as u64, remaining))
+}
+
+// Whether batch `id` may still be stored in the 0.5.0 layout
+pub fn is_legacy_batch(storage: &dyn Storage, id: u64) -> StdResult<bool> {
+    Ok(LEGACY_BATCHES_BEFORE
+        .may_load(storage)?
+        .map_or(false, |before| id < before))
+}
+
+// Rewrites batch `id` if it's still in the 0.5.0 layout, so a single batch can be used before
+// `MigrateBatches` reaches it
+pub fn migrate_legacy_batch(storage: &mut dyn Storage, id: u64) -> StdResult<()> {
+    if !is_legacy_batch(storage, id)? {
+        return Ok(());
+    }
+    if let Some(legacy) = v0_5_0_state::BATCHES.may_load(storage, id)? {
+        save_migrated_batch(storage, &legacy)?;
+    }
+    Ok(())
+}
+
+// Moves the open embedded requests to the unstake requests index and saves the batch in the
+// current layout
+fn save_migrated_batch(storage: &mut dyn Storage, legacy: &v0_5_0_state::Batch) -> StdResult<()> {
+    let (batch, requests) = read_legacy_batch(storage, legacy)?;
+    for request in requests {
+        unstake_requests().save(storage, (legacy.id, request.user.clone()), &request)?;
+    }
+    BATCHES.save(storage, legacy.id, &batch)
+}
+
+// The batch as it's migrated and its open embedded requests not in the unstake requests index
+// yet, without writing anything. Batches already in the current layout parse as 0.5.0 batches
+// without embedded requests and are returned unchanged
+pub fn read_legacy_batch(
+    storage: &dyn Storage,
+    legacy: &v0_5_0_state::Batch,
+) -> StdResult<(Batch, Vec<UnstakeRequest>)> {
+    let mut redeemed = 0u64;
+    let mut requests = vec![];
+    for (user, request) in legacy.liquid_unstake_requests.iter().flatten() {
+        if request.redeemed {
+            redeemed += 1;
+            continue;
+        }
+        if !unstake_requests().has(storage, (legacy.id, user.clone())) {
+            requests.push(UnstakeRequest {
+                batch_id: legacy.id,
+                user: user.clone(),
+                amount: request.shares,
+            });
+        }
+    }
+
+    let indexed = unstake_requests()
+        .prefix(legacy.id)
+        .keys(storage, None, None, Order::Ascending)
+        .count() as u64;
+    let open = indexed + requests.len() as u64;
+    let withdrawn_requests_count = legacy.withdrawn_requests_count.max(redeemed);
+    let unstake_requests_count = legacy
+        .unstake_requests_count
+        .unwrap_or_default()
+        .max(open + withdrawn_requests_count);
+
+    let batch = Batch {
+        id: legacy.id,
+        batch_total_liquid_stake: legacy.batch_total_liquid_stake,
+        expected_native_unstaked: legacy.expected_native_unstaked,
+        received_native_unstaked: legacy.received_native_unstaked,
+        unstake_requests_count,
+        withdrawn_requests_count,
+        withdrawn_native_unstaked: legacy.withdrawn_native_unstaked,
+        swept_native_unstaked: legacy.swept_native_unstaked,
+        received_at: legacy.received_at,
+        next_batch_action_time: legacy
+            .next_batch_action_time
+            .or(legacy.received_at)
+            .unwrap_or_default(),
+        status: legacy.status.clone(),
+    };
+    Ok((batch, requests))
+}
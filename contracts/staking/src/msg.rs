@@ -1,8 +1,14 @@
+use crate::oracle::OracleTarget;
 use crate::state::{
-    ibc::IBCTransfer, IbcWaitingForReply, MultisigAddressConfig, ProtocolFeeConfig, UnstakeRequest,
+    ibc::IBCTransfer, ArchivedBatch, AuditEntry, BatchTriggerConfig, ExtraRewardDenom,
+    FeeRecipient, IbcForward, IbcWaitingForReply, KeeperRewardConfig, MonitorAction,
+    MonitorProposal, MonitorQuorumConfig, MultisigAddressConfig, OptionalConfig,
+    OracleThrottleConfig, PendingConfigChange, PendingProtocolFeeConfig, PostedRates,
+    ProtocolFeeConfig, Role, SweptRequest, UnclaimedSweepConfig, UnstakeRequest,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Decimal256, Timestamp, Uint128};
+use milky_way::ownership::OwnershipResponse;
 use milky_way::staking::BatchStatus;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -43,12 +49,26 @@ pub enum ExecuteMsg {
     LiquidStake {
         mint_to: Option<String>,
         expected_mint_amount: Option<Uint128>,
+        /// Sends the minted tokens to a receiver on another chain. If the transfer fails
+        /// the tokens can be claimed by `mint_to`, or by the osmo address of the receiver
+        forward: Option<IbcForward>,
+    },
+    LiquidUnstake {
+        /// Owner of the unstake request when unstaking through IBC hooks or from a contract,
+        /// defaults to the sender. Only osmo and celestia addresses are accepted, withdrawals of
+        /// celestia owners are sent with `ForwardWithdrawal`
+        owner: Option<String>,
     },
-    LiquidUnstake {},
     SubmitBatch {},
     Withdraw {
         batch_id: u64,
     },
+    /// Sends the withdrawal of a request owned by a celestia address to its owner, over the
+    /// configured `ibc_channel_id` to Celestia. Owners on other chains aren't supported
+    ForwardWithdrawal {
+        batch_id: u64,
+        owner: String,
+    },
     AddValidator {
         new_validator: String,
     },
@@ -60,19 +80,87 @@ pub enum ExecuteMsg {
     },
     AcceptOwnership {},
     RevokeOwnershipTransfer {},
+    /// Sets how long a pending owner has to wait before accepting the ownership, and how long
+    /// they can accept it for
+    UpdateOwnershipConfig {
+        transfer_delay: Option<u64>,
+        transfer_expiry: Option<u64>,
+    },
+    /// Removes the owner for good, `confirm` has to be the contract address
+    RenounceOwnership {
+        confirm: String,
+    },
     UpdateConfig {
         batch_period: Option<u64>,
         unbonding_period: Option<u64>,
         minimum_liquid_stake_amount: Option<Uint128>,
+        /// Proposed as a timelocked config change, like the IBC config, the treasury, the fee
+        /// recipients, the extra reward denoms, the oracles and the monitors
         multisig_address_config: Option<MultisigAddressConfig>,
         protocol_fee_config: Option<ProtocolFeeConfig>,
         native_token_denom: Option<String>,
         channel_id: Option<String>,
         monitors: Option<Vec<String>>,
         treasury_address: Option<String>,
+        /// An empty address removes the oracle
         oracle_address: Option<String>,
         send_fees_to_treasury: Option<bool>,
+        /// Set by the fee manager, at most `MAX_KEEPER_REWARD_RATE` of a batch and
+        /// `MAX_KEEPER_REWARD` per batch
+        keeper_reward: Option<KeeperRewardConfig>,
+        batch_trigger: Option<BatchTriggerConfig>,
+        unclaimed_sweep: Option<UnclaimedSweepConfig>,
+        /// An empty list sends all fees to the treasury
+        fee_recipients: Option<Vec<FeeRecipient>>,
+        extra_reward_denoms: Option<Vec<ExtraRewardDenom>>,
+        oracle_targets: Option<Vec<OracleTarget>>,
+        oracle_throttle: Option<OracleThrottleConfig>,
+        /// Once set, the contract is only resumed with the approval of the monitors. Like the
+        /// monitors, it can't be changed or cleared while the contract is stopped
+        monitor_quorum: Option<MonitorQuorumConfig>,
+        /// Turns the listed optional features off, they can't be set in the same update
+        #[serde(default)]
+        clear: Option<Vec<OptionalConfig>>,
+    },
+    /// Proposes an action needing the approval of the monitors, callable by the monitors.
+    /// The proposal counts as the first approval
+    ProposeMonitorAction {
+        action: MonitorAction,
+    },
+    /// Approves a monitor proposal, the action is executed once the quorum is reached
+    ApproveMonitorAction {
+        id: u64,
+    },
+    /// Grants a role to an address, callable by the admin
+    GrantRole {
+        role: Role,
+        address: String,
     },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    /// Applies a config change proposed with `UpdateConfig` once its timelock passed, callable
+    /// by the admin and the managers of the changed fields
+    ExecuteConfigChange {
+        id: u64,
+    },
+    /// Drops a proposed config change, callable by the admin and the managers of the changed
+    /// fields
+    CancelConfigChange {
+        id: u64,
+    },
+    /// Drops a proposed config change, callable by the monitors
+    VetoConfigChange {
+        id: u64,
+    },
+    /// Enables or disables posting the rates to one of the oracle targets
+    SetOracleTargetEnabled {
+        address: String,
+        enabled: bool,
+    },
+    /// Adds the sent native tokens to the bounty keeper rewards can be paid from
+    FundKeeperBounty {},
     ReceiveRewards {},
     ReceiveUnstakedTokens {
         batch_id: u64,
@@ -90,6 +178,25 @@ pub enum ExecuteMsg {
     FeeWithdraw {
         amount: Uint128,
     },
+    /// Claims the tokens of failed forward transfers held for the sender
+    ClaimFunds {},
+    /// Sweeps the requests of a received batch not withdrawn within the claim window,
+    /// at most `limit` requests per call. Requests of celestia owners and contracts are
+    /// not swept, they can't claim from the treasury
+    SweepUnclaimed {
+        batch_id: u64,
+        limit: Option<u32>,
+    },
+    /// Marks a swept request as paid, callable by the treasury
+    SettleSweptRequest {
+        batch_id: u64,
+        user: String,
+    },
+    /// Rewrites up to `limit` batches still in the 0.5.0 layout. Withdrawals, sweeps and
+    /// received unstaked tokens migrate their batch on their own meanwhile
+    MigrateBatches {
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
@@ -105,10 +212,20 @@ pub struct ConfigResponse {
     pub staker_address: String,
     pub reward_collector_address: String,
     pub protocol_fee_config: ProtocolFeeConfig,
+    /// Fee config staged by the owner, not in effect yet
+    pub pending_protocol_fee_config: Option<PendingProtocolFeeConfig>,
     pub ibc_channel_id: String,
     pub stopped: bool,
     pub oracle_address: String,
     pub send_fees_to_treasury: bool,
+    pub keeper_reward: Option<KeeperRewardConfig>,
+    pub batch_trigger: Option<BatchTriggerConfig>,
+    pub unclaimed_sweep: Option<UnclaimedSweepConfig>,
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
+    pub extra_reward_denoms: Option<Vec<ExtraRewardDenom>>,
+    pub oracle_targets: Option<Vec<OracleTarget>>,
+    pub oracle_throttle: Option<OracleThrottleConfig>,
+    pub monitor_quorum: Option<MonitorQuorumConfig>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
@@ -119,6 +236,66 @@ pub struct StateResponse {
     pub pending_owner: String,
     pub total_reward_amount: Uint128,
     pub total_fees: Uint128,
+    pub keeper_bounty: Uint128,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct FeeDistributionResponse {
+    /// Current recipients, the treasury receives all fees if none are configured
+    pub recipients: Vec<FeeRecipient>,
+    /// Fees sent to each address so far
+    pub distributed: Vec<DistributedFees>,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct RatesResponse {
+    /// Native tokens per stTIA
+    pub redemption_rate: Decimal256,
+    /// stTIA per native token
+    pub purchase_rate: Decimal256,
+    /// When the rates last changed
+    pub updated_at: Option<u64>,
+    pub twap_window: u64,
+    pub twap_redemption_rate: Decimal256,
+    pub twap_purchase_rate: Decimal256,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct ExtraRewardsResponse {
+    pub denoms: Vec<ExtraRewardDenom>,
+    pub forwarded: Vec<Coin>,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoleMembers {
+    pub role: Role,
+    pub addresses: Vec<Addr>,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RolesResponse {
+    pub admin: Option<Addr>,
+    pub roles: Vec<RoleMembers>,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DistributedFees {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct SimulateLiquidStakeResponse {
+    pub deposit_fee: Uint128,
+    pub mint_amount: Uint128,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct SimulateLiquidUnstakeResponse {
+    /// Native tokens unbonded for the stTIA at the current rate
+    pub expected_native_unstaked: Uint128,
+    /// Redemption fee as currently configured, the fee is fixed when the batch is submitted
+    pub redemption_fee: Uint128,
+    pub expected_withdrawal: Uint128,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct WithdrawalResponse {
+    /// Native tokens sent to the owner
+    pub amount: Uint128,
+    /// Redemption fee withheld from the withdrawal
+    pub redemption_fee: Uint128,
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 pub struct BatchResponse {
@@ -129,12 +306,30 @@ pub struct BatchResponse {
     pub unstake_request_count: u64,
     pub next_batch_action_time: Timestamp,
     pub status: String,
+    /// Only set for the pending batch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readiness: Option<BatchReadiness>,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct BatchReadiness {
+    /// Whether the batch can be submitted now
+    pub ready: bool,
+    /// The batch period has passed
+    pub time_triggered: bool,
+    /// The batch exceeds one of the size limits of the batch trigger
+    pub size_triggered: bool,
+    /// Earliest time the batch can be submitted
+    pub ready_at: Timestamp,
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 pub struct BatchesResponse {
     pub batches: Vec<BatchResponse>,
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct ArchivedBatchesResponse {
+    pub batches: Vec<ArchivedBatch>,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 pub struct UnstakeRequestResponse {
     pub batch_id: u64,
     pub batch_total_liquid_stake: Uint128,
@@ -161,6 +356,8 @@ pub enum QueryMsg {
     Config {},
     #[returns(StateResponse)]
     State {},
+    /// Archived batches are returned with the `archived` status, batches not migrated yet
+    /// by `MigrateBatches` as they will be migrated
     #[returns(BatchResponse)]
     Batch { id: u64 },
     #[returns(BatchesResponse)]
@@ -173,6 +370,12 @@ pub enum QueryMsg {
     BatchesByIds { ids: Vec<u64> },
     #[returns(BatchResponse)]
     PendingBatch {},
+    /// Batches whose unstake requests have all been withdrawn
+    #[returns(ArchivedBatchesResponse)]
+    ArchivedBatches {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     #[returns(Vec<UnstakeRequest>)]
     UnstakeRequests { user: Addr },
     #[returns(Vec<UnstakeRequestResponse>)]
@@ -195,11 +398,93 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    #[returns(Vec<Coin>)]
+    ClaimableFunds { address: String },
+    /// Requests of the user swept after the claim window, paid by the treasury
+    #[returns(Vec<SweptRequest>)]
+    SweptRequests { user: String },
+    /// Amount of stTIA minted for liquid staking `amount`, after the deposit fee
+    #[returns(SimulateLiquidStakeResponse)]
+    SimulateLiquidStake { amount: Uint128 },
+    /// Amount of native tokens withdrawn for liquid unstaking `amount`, after the redemption fee
+    #[returns(SimulateLiquidUnstakeResponse)]
+    SimulateLiquidUnstake { amount: Uint128 },
+    /// Native tokens `user` withdraws from a received batch, after the redemption fee
+    #[returns(WithdrawalResponse)]
+    Withdrawal { batch_id: u64, user: String },
+    #[returns(FeeDistributionResponse)]
+    FeeDistribution {},
+    /// Accepted reward denoms other than the native token, and the amounts forwarded so far
+    #[returns(ExtraRewardsResponse)]
+    ExtraRewards {},
+    /// Current rates and their time-weighted average over the last `twap_window` seconds,
+    /// one day by default and at most a week
+    #[returns(RatesResponse)]
+    Rates { twap_window: Option<u64> },
+    /// Rates last posted to the oracles, if any
+    #[returns(Option<PostedRates>)]
+    LastPostedRates {},
+    /// Monitor proposals waiting for approvals, expired ones are left out
+    #[returns(Vec<MonitorProposal>)]
+    MonitorProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Owner, pending owner and when the pending owner can accept the ownership
+    #[returns(OwnershipResponse)]
+    Ownership {},
+    /// Admin and the addresses granted each role
+    #[returns(RolesResponse)]
+    Roles {},
+    /// Proposed config changes waiting to be executed
+    #[returns(Vec<PendingConfigChange>)]
+    PendingConfigChanges {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Privileged actions with the values they changed, oldest first
+    #[returns(Vec<AuditEntry>)]
+    AuditLog {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub enum MigrateMsg {
+    /// Runs the migration steps from the stored version to this one. `send_fees_to_treasury`
+    /// is needed when migrating from before 0.4.20, `batch_limit` caps the batches rewritten
+    /// when migrating from before 0.6.0
+    Migrate {
+        send_fees_to_treasury: Option<bool>,
+        batch_limit: Option<u32>,
+    },
+    /// Same as Migrate, kept for compatibility
     V0_4_18ToV0_4_20 { send_fees_to_treasury: bool },
+    /// Same as Migrate, kept for compatibility
+    V0_4_20ToV0_5_0 {},
+}
+
+impl MigrateMsg {
+    pub fn send_fees_to_treasury(&self) -> Option<bool> {
+        match self {
+            MigrateMsg::Migrate {
+                send_fees_to_treasury,
+                ..
+            } => *send_fees_to_treasury,
+            MigrateMsg::V0_4_18ToV0_4_20 {
+                send_fees_to_treasury,
+            } => Some(*send_fees_to_treasury),
+            MigrateMsg::V0_4_20ToV0_5_0 {} => None,
+        }
+    }
+
+    pub fn batch_limit(&self) -> Option<u32> {
+        match self {
+            MigrateMsg::Migrate { batch_limit, .. } => *batch_limit,
+            _ => None,
+        }
+    }
 }
 
 #[cw_serde]
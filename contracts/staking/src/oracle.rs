@@ -1,5 +1,6 @@
 // Types for the Oracle contract
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Timestamp, Uint128, Uint64};
 
 #[cw_serde]
 pub enum Oracle {
@@ -8,4 +9,73 @@ pub enum Oracle {
         purchase_rate: String,
         redemption_rate: String,
     },
-}
\ No newline at end of file
+}
+
+// Band standard reference style relay, as used by Band and Redstone style oracles
+#[cw_serde]
+pub enum StdReference {
+    Relay {
+        symbols: Vec<String>,
+        rates: Vec<Uint64>,
+        resolve_time: Uint64,
+        request_id: Uint64,
+    },
+}
+
+/// Oracle contract the rates are posted to
+#[cw_serde]
+pub struct OracleTarget {
+    pub address: Addr,
+    pub format: OracleFormat,
+    pub enabled: bool,
+}
+
+/// Message format an oracle contract expects
+#[cw_serde]
+pub enum OracleFormat {
+    /// `Oracle::PostRates`
+    PostRates,
+    /// `StdReference::Relay` of the redemption rate under `symbol`, scaled by 1e9
+    Relay { symbol: String },
+    /// JSON with `{denom}`, `{purchase_rate}`, `{redemption_rate}` and `{time}` replaced
+    Template { template: String },
+}
+
+impl OracleFormat {
+    pub fn msg(
+        &self,
+        denom: &str,
+        redemption_rate: Decimal,
+        purchase_rate: Decimal,
+        time: Timestamp,
+    ) -> StdResult<Vec<u8>> {
+        match self {
+            OracleFormat::PostRates => serde_json::to_vec(&Oracle::PostRates {
+                denom: denom.to_string(),
+                purchase_rate: purchase_rate.to_string(),
+                redemption_rate: redemption_rate.to_string(),
+            })
+            .map_err(|e| StdError::serialize_err("Oracle", e)),
+            OracleFormat::Relay { symbol } => {
+                let rate = redemption_rate.atomics() / Uint128::new(1_000_000_000);
+                serde_json::to_vec(&StdReference::Relay {
+                    symbols: vec![symbol.clone()],
+                    rates: vec![Uint64::try_from(rate)?],
+                    resolve_time: Uint64::new(time.seconds()),
+                    request_id: Uint64::zero(),
+                })
+                .map_err(|e| StdError::serialize_err("StdReference", e))
+            }
+            OracleFormat::Template { template } => {
+                let msg = template
+                    .replace("{denom}", denom)
+                    .replace("{purchase_rate}", &purchase_rate.to_string())
+                    .replace("{redemption_rate}", &redemption_rate.to_string())
+                    .replace("{time}", &time.seconds().to_string());
+                serde_json::from_str::<serde_json::Value>(&msg)
+                    .map_err(|e| StdError::parse_err("oracle template", e))?;
+                Ok(msg.into_bytes())
+            }
+        }
+    }
+}
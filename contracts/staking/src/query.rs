@@ -1,19 +1,32 @@
-use crate::helpers::{get_rates, paginate_map};
+use crate::contract::{DEFAULT_TWAP_WINDOW, MAX_TWAP_WINDOW};
+use crate::helpers::{
+    batch_readiness, compute_mint_amount, compute_twap, compute_unbond_amount, compute_withdrawal,
+    fee_recipients, get_rates, get_rates_256, paginate_map, protocol_fee_configs,
+};
+use crate::migrations::v0_6_0::{is_legacy_batch, read_legacy_batch, v0_5_0_state};
 use crate::msg::{
-    BatchResponse, BatchesResponse, ConfigResponse, IBCQueueResponse, IBCReplyQueueResponse,
-    StateResponse,
+    ArchivedBatchesResponse, BatchResponse, BatchesResponse, ConfigResponse, DistributedFees,
+    ExtraRewardsResponse, FeeDistributionResponse, IBCQueueResponse, IBCReplyQueueResponse,
+    RatesResponse, RoleMembers, RolesResponse, SimulateLiquidStakeResponse,
+    SimulateLiquidUnstakeResponse, StateResponse, WithdrawalResponse,
 };
 use crate::state::ibc::IBCTransfer;
 use crate::state::{
-    unstake_requests, UnstakeRequest, BATCHES, CONFIG, IBC_WAITING_FOR_REPLY, INFLIGHT_PACKETS,
-    PENDING_BATCH_ID, STATE,
+    unstake_requests, AuditEntry, MonitorProposal, PendingConfigChange, RateSnapshot, Role,
+    SweptRequest, UnstakeRequest, ARCHIVED_BATCHES, AUDIT_LOG, BATCHES, CLAIMABLE_FUNDS, CONFIG,
+    DISTRIBUTED_FEES, FORWARDED_EXTRA_REWARDS, IBC_WAITING_FOR_REPLY, INFLIGHT_PACKETS,
+    LEGACY_BATCHES_BEFORE, MONITOR_PROPOSALS, OWNERSHIP, PENDING_BATCH_ID, PENDING_CONFIG_CHANGES,
+    RATE_SNAPSHOTS, ROLES, STATE, SWEPT_REQUESTS,
 };
-use cosmwasm_std::{Deps, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{Coin, Deps, Env, StdError, StdResult, Timestamp, Uint128};
 use cw_storage_plus::Bound;
+use milky_way::ownership::OwnershipResponse;
 use milky_way::staking::{Batch, BatchStatus};
 
-pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
+    let (protocol_fee_config, pending_protocol_fee_config) =
+        protocol_fee_configs(deps, &env, &config)?;
 
     let res = ConfigResponse {
         native_token_denom: config.native_token_denom,
@@ -38,7 +51,8 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
             .multisig_address_config
             .reward_collector_address
             .to_string(),
-        protocol_fee_config: config.protocol_fee_config,
+        protocol_fee_config,
+        pending_protocol_fee_config,
         ibc_channel_id: config.ibc_channel_id,
         stopped: config.stopped,
         oracle_address: config
@@ -46,6 +60,14 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
             .map(|v| v.to_string())
             .unwrap_or_default(),
         send_fees_to_treasury: config.send_fees_to_treasury,
+        keeper_reward: config.keeper_reward,
+        batch_trigger: config.batch_trigger,
+        unclaimed_sweep: config.unclaimed_sweep,
+        fee_recipients: config.fee_recipients,
+        extra_reward_denoms: config.extra_reward_denoms,
+        oracle_targets: config.oracle_targets,
+        oracle_throttle: config.oracle_throttle,
+        monitor_quorum: config.monitor_quorum,
     };
     Ok(res)
 }
@@ -57,12 +79,13 @@ pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
         total_native_token: state.total_native_token,
         total_liquid_stake_token: state.total_liquid_stake_token,
         rate: purchase_rate,
-        pending_owner: state
-            .pending_owner
-            .map(|v| v.to_string())
+        pending_owner: OWNERSHIP
+            .pending(deps.storage)?
+            .map(|p| p.owner.to_string())
             .unwrap_or_default(),
         total_reward_amount: state.total_reward_amount,
         total_fees: state.total_fees,
+        keeper_bounty: state.keeper_bounty,
     };
     Ok(res)
 }
@@ -73,17 +96,43 @@ fn batch_to_response(batch: Batch) -> BatchResponse {
         batch_total_liquid_stake: batch.batch_total_liquid_stake,
         expected_native_unstaked: batch.expected_native_unstaked.unwrap_or(Uint128::zero()),
         received_native_unstaked: batch.received_native_unstaked.unwrap_or(Uint128::zero()),
-        next_batch_action_time: Timestamp::from_seconds(
-            batch.next_batch_action_time.unwrap_or(0u64),
-        ),
+        next_batch_action_time: Timestamp::from_seconds(batch.next_batch_action_time),
         status: batch.status.as_str().to_string(),
-        unstake_request_count: batch.unstake_requests_count.unwrap_or(0), // Fallback. Only is none if migration failed. Would be set in updates for new batches though
+        unstake_request_count: batch.unstake_requests_count,
+        readiness: None,
+    }
+}
+
+// Batches below the cursor of `MigrateBatches` may still be in the 0.5.0 layout, they're read as
+// they will be migrated along with their requests that aren't in the unstake requests index yet
+fn load_batch(deps: Deps, id: u64) -> StdResult<Option<(Batch, Vec<UnstakeRequest>)>> {
+    if is_legacy_batch(deps.storage, id)? {
+        return v0_5_0_state::BATCHES
+            .may_load(deps.storage, id)?
+            .map(|legacy| read_legacy_batch(deps.storage, &legacy))
+            .transpose();
     }
+    Ok(BATCHES
+        .may_load(deps.storage, id)?
+        .map(|batch| (batch, vec![])))
 }
 
+// Falls back to the archived summary once all of the batch's requests are withdrawn
 pub fn query_batch(deps: Deps, id: u64) -> StdResult<BatchResponse> {
-    let batch: Batch = BATCHES.load(deps.storage, id)?;
-    Ok(batch_to_response(batch))
+    if let Some((batch, _)) = load_batch(deps, id)? {
+        return Ok(batch_to_response(batch));
+    }
+    let archived = ARCHIVED_BATCHES.load(deps.storage, id)?;
+    Ok(BatchResponse {
+        id: archived.id,
+        batch_total_liquid_stake: archived.batch_total_liquid_stake,
+        expected_native_unstaked: archived.received_native_unstaked,
+        received_native_unstaked: archived.received_native_unstaked,
+        unstake_request_count: archived.unstake_requests_count,
+        next_batch_action_time: Timestamp::from_seconds(0),
+        status: "archived".to_string(),
+        readiness: None,
+    })
 }
 
 pub fn query_batches(
@@ -92,6 +141,28 @@ pub fn query_batches(
     limit: Option<u32>,
     status: Option<BatchStatus>,
 ) -> StdResult<BatchesResponse> {
+    // batches of both layouts parse as 0.5.0 batches until the migration is done
+    if LEGACY_BATCHES_BEFORE.exists(deps.storage) {
+        let legacy_filter = status.map(|s| {
+            Box::new(move |v: &v0_5_0_state::Batch| v.status == s)
+                as Box<dyn Fn(&v0_5_0_state::Batch) -> bool>
+        });
+        let batches = paginate_map(
+            deps,
+            &v0_5_0_state::BATCHES,
+            start_after,
+            limit,
+            cosmwasm_std::Order::Ascending,
+            legacy_filter,
+        )?
+        .iter()
+        .map(|legacy| {
+            read_legacy_batch(deps.storage, legacy).map(|(batch, _)| batch_to_response(batch))
+        })
+        .collect::<StdResult<_>>()?;
+        return Ok(BatchesResponse { batches });
+    }
+
     let filter_closure =
         status.map(|s| Box::new(move |v: &Batch| v.status == s) as Box<dyn Fn(&Batch) -> bool>);
 
@@ -113,14 +184,8 @@ pub fn query_batches(
 pub fn query_batches_by_ids(deps: Deps, ids: Vec<u64>) -> StdResult<BatchesResponse> {
     let batches: Vec<Batch> = ids
         .into_iter()
-        .map(|id| BATCHES.load(deps.storage, id))
-        .filter_map(|r| {
-            if r.is_ok() {
-                let batch = r.unwrap();
-                return Some(batch);
-            }
-            None
-        })
+        .filter_map(|id| load_batch(deps, id).ok().flatten())
+        .map(|(batch, _)| batch)
         .collect();
 
     let res = BatchesResponse {
@@ -129,11 +194,83 @@ pub fn query_batches_by_ids(deps: Deps, ids: Vec<u64>) -> StdResult<BatchesRespo
     Ok(res)
 }
 
-pub fn query_pending_batch(deps: Deps) -> StdResult<BatchResponse> {
+// Expired proposals are left out until they are pruned by the next proposal
+pub fn query_monitor_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<MonitorProposal>> {
+    let now = env.block.time.seconds();
+    paginate_map(
+        deps,
+        &MONITOR_PROPOSALS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+        Some(Box::new(move |proposal: &MonitorProposal| {
+            proposal.expires_at > now
+        })),
+    )
+}
+
+pub fn query_pending_config_changes(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingConfigChange>> {
+    paginate_map(
+        deps,
+        &PENDING_CONFIG_CHANGES,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+        None,
+    )
+}
+
+pub fn query_audit_log(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AuditEntry>> {
+    paginate_map(
+        deps,
+        &AUDIT_LOG,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+        None,
+    )
+}
+
+pub fn query_archived_batches(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ArchivedBatchesResponse> {
+    let batches = paginate_map(
+        deps,
+        &ARCHIVED_BATCHES,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+        None,
+    )?;
+
+    Ok(ArchivedBatchesResponse { batches })
+}
+
+pub fn query_pending_batch(deps: Deps, env: Env) -> StdResult<BatchResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
     let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
     let pending_batch = BATCHES.load(deps.storage, pending_batch_id)?;
 
-    Ok(batch_to_response(pending_batch))
+    let readiness = batch_readiness(&config, &state, &pending_batch, env.block.time.seconds());
+    let mut response = batch_to_response(pending_batch);
+    response.readiness = Some(readiness);
+    Ok(response)
 }
 
 pub fn query_ibc_queue(
@@ -176,20 +313,42 @@ pub fn query_reply_queue(
 }
 
 pub fn query_unstake_requests(deps: Deps, user: String) -> StdResult<Vec<UnstakeRequest>> {
-    let unstaking_requests = unstake_requests()
+    let mut unstaking_requests: Vec<UnstakeRequest> = unstake_requests()
         .idx
         .by_user
         .prefix(user.to_string())
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .filter_map(|r| {
-            if r.is_ok() {
-                let request = r.unwrap().1;
-                return Some(request);
-            }
-            None
-        })
+        .filter_map(|r| r.ok().map(|(_, request)| request))
         .collect();
 
+    // requests of batches still in the 0.5.0 layout aren't in the index yet
+    if let Some(before) = LEGACY_BATCHES_BEFORE.may_load(deps.storage)? {
+        let legacy_batches = v0_5_0_state::BATCHES.range(
+            deps.storage,
+            None,
+            Some(Bound::exclusive(before)),
+            cosmwasm_std::Order::Ascending,
+        );
+        for legacy in legacy_batches {
+            let (id, legacy) = legacy?;
+            let Some(request) = legacy
+                .liquid_unstake_requests
+                .as_ref()
+                .and_then(|requests| requests.get(&user))
+            else {
+                continue;
+            };
+            if !request.redeemed && !unstake_requests().has(deps.storage, (id, user.clone())) {
+                unstaking_requests.push(UnstakeRequest {
+                    batch_id: id,
+                    user: user.clone(),
+                    amount: request.shares,
+                });
+            }
+        }
+        unstaking_requests.sort_by_key(|request| request.batch_id);
+    }
+
     Ok(unstaking_requests)
 }
 
@@ -209,13 +368,7 @@ pub fn query_all_unstake_requests(
             cosmwasm_std::Order::Ascending,
         )
         .take(limit.unwrap_or(u32::MAX) as usize)
-        .filter_map(|r| {
-            if r.is_ok() {
-                let request = r.unwrap().1;
-                return Some(request);
-            }
-            None
-        })
+        .filter_map(|r| r.ok().map(|(_, request)| request))
         .collect();
 
     Ok(unstaking_requests)
@@ -237,13 +390,184 @@ pub fn query_all_unstake_requests_v2(
         )
         .take(limit.unwrap_or(u32::MAX) as usize)
         .filter_map(|r| {
-            if r.is_ok() {
-                let request = r.unwrap().1;
-                return Some((request.user, request.batch_id, request.amount));
-            }
-            None
+            r.ok()
+                .map(|(_, request)| (request.user, request.batch_id, request.amount))
         })
         .collect();
 
     Ok(unstaking_requests)
 }
+
+pub fn query_claimable_funds(deps: Deps, address: String) -> StdResult<Vec<Coin>> {
+    CLAIMABLE_FUNDS
+        .prefix(address)
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|r| r.map(|(denom, amount)| Coin::new(amount.u128(), denom)))
+        .collect()
+}
+
+pub fn query_swept_requests(deps: Deps, user: String) -> StdResult<Vec<SweptRequest>> {
+    SWEPT_REQUESTS
+        .prefix(user)
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|r| r.map(|(_, request)| request))
+        .collect()
+}
+
+pub fn query_simulate_liquid_stake(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+) -> StdResult<SimulateLiquidStakeResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let (protocol_fee_config, _) = protocol_fee_configs(deps, &env, &config)?;
+
+    let deposit_fee = protocol_fee_config
+        .deposit_fee
+        .map(|fee| fee.compute(amount))
+        .unwrap_or_default();
+    // native tokens without any stTIA are claimed as fees on the next liquid stake
+    let total_native_token = if state.total_liquid_stake_token.is_zero() {
+        Uint128::zero()
+    } else {
+        state.total_native_token
+    };
+    let mint_amount = compute_mint_amount(
+        total_native_token,
+        state.total_liquid_stake_token,
+        amount - deposit_fee,
+    );
+
+    Ok(SimulateLiquidStakeResponse {
+        deposit_fee,
+        mint_amount,
+    })
+}
+
+pub fn query_simulate_liquid_unstake(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+) -> StdResult<SimulateLiquidUnstakeResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let (protocol_fee_config, _) = protocol_fee_configs(deps, &env, &config)?;
+
+    let expected_native_unstaked = compute_unbond_amount(
+        state.total_native_token,
+        state.total_liquid_stake_token,
+        amount,
+    );
+    let redemption_fee = protocol_fee_config
+        .redemption_fee
+        .map(|fee| fee.compute(expected_native_unstaked))
+        .unwrap_or_default();
+
+    Ok(SimulateLiquidUnstakeResponse {
+        expected_native_unstaked,
+        redemption_fee,
+        expected_withdrawal: expected_native_unstaked - redemption_fee,
+    })
+}
+
+pub fn query_withdrawal(deps: Deps, batch_id: u64, user: String) -> StdResult<WithdrawalResponse> {
+    let (batch, legacy_requests) =
+        load_batch(deps, batch_id)?.ok_or_else(|| StdError::not_found("Batch"))?;
+    if batch.status != BatchStatus::Received {
+        return Err(StdError::generic_err(format!(
+            "Batch {batch_id} has not been received yet"
+        )));
+    }
+    let request = match unstake_requests().may_load(deps.storage, (batch_id, user.clone()))? {
+        Some(request) => request,
+        None => legacy_requests
+            .into_iter()
+            .find(|request| request.user == user)
+            .ok_or_else(|| StdError::not_found("UnstakeRequest"))?,
+    };
+    let (amount, redemption_fee) = compute_withdrawal(deps, &batch, request.amount)?;
+    Ok(WithdrawalResponse {
+        amount: amount - redemption_fee,
+        redemption_fee,
+    })
+}
+
+pub fn query_fee_distribution(deps: Deps) -> StdResult<FeeDistributionResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let distributed = DISTRIBUTED_FEES
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|r| r.map(|(address, amount)| DistributedFees { address, amount }))
+        .collect::<StdResult<_>>()?;
+
+    Ok(FeeDistributionResponse {
+        recipients: fee_recipients(&config),
+        distributed,
+    })
+}
+
+pub fn query_extra_rewards(deps: Deps) -> StdResult<ExtraRewardsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let forwarded = FORWARDED_EXTRA_REWARDS
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|r| r.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<_>>()?;
+
+    Ok(ExtraRewardsResponse {
+        denoms: config.extra_reward_denoms.unwrap_or_default(),
+        forwarded,
+    })
+}
+
+pub fn query_ownership(deps: Deps) -> StdResult<OwnershipResponse> {
+    OWNERSHIP.query(deps)
+}
+
+pub fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let roles = Role::ALL
+        .into_iter()
+        .map(|role| {
+            let addresses = ROLES
+                .prefix(role.as_str())
+                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .collect::<StdResult<_>>()?;
+            Ok(RoleMembers { role, addresses })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(RolesResponse {
+        admin: OWNERSHIP.owner(deps)?,
+        roles,
+    })
+}
+
+pub fn query_rates(deps: Deps, env: Env, twap_window: Option<u64>) -> StdResult<RatesResponse> {
+    let twap_window = twap_window.unwrap_or(DEFAULT_TWAP_WINDOW);
+    if twap_window > MAX_TWAP_WINDOW {
+        return Err(StdError::generic_err(format!(
+            "TWAP window can be at most {MAX_TWAP_WINDOW} seconds"
+        )));
+    }
+
+    let state = STATE.load(deps.storage)?;
+    let (redemption_rate, purchase_rate) = get_rates_256(&state);
+    let updated_at = RATE_SNAPSHOTS
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
+        .next()
+        .transpose()?;
+    let twap = compute_twap(deps.storage, env.block.time.seconds(), twap_window)?.unwrap_or(
+        RateSnapshot {
+            redemption_rate,
+            purchase_rate,
+        },
+    );
+
+    Ok(RatesResponse {
+        redemption_rate,
+        purchase_rate,
+        updated_at,
+        twap_window,
+        twap_redemption_rate: twap.redemption_rate,
+        twap_purchase_rate: twap.purchase_rate,
+    })
+}
@@ -1,9 +1,14 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, DepsMut, StdError, Timestamp, Uint128};
-use cw_controllers::Admin;
+use cosmwasm_std::{
+    attr, to_json_string, Addr, Attribute, Binary, Decimal, Decimal256, DepsMut, Empty, StdError,
+    StdResult, Timestamp, Uint128,
+};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, UniqueIndex};
+use milky_way::ownership::Ownership;
 use milky_way::staking::Batch;
 
+use crate::oracle::OracleTarget;
+
 #[cw_serde]
 pub struct Config {
     pub native_token_denom: String,
@@ -22,6 +27,28 @@ pub struct Config {
     // Tells if the contract will automatically send the collected fees
     // to the treasury.
     pub send_fees_to_treasury: bool,
+    #[serde(default)]
+    pub keeper_reward: Option<KeeperRewardConfig>,
+    #[serde(default)]
+    pub batch_trigger: Option<BatchTriggerConfig>,
+    #[serde(default)]
+    pub unclaimed_sweep: Option<UnclaimedSweepConfig>,
+    // Fees sent out of the contract are split between these recipients,
+    // all fees go to the treasury if there are none
+    #[serde(default)]
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
+    // Reward denoms other than the native token accepted from the reward collector
+    #[serde(default)]
+    pub extra_reward_denoms: Option<Vec<ExtraRewardDenom>>,
+    // Oracles the rates are posted to besides `oracle_address`
+    #[serde(default)]
+    pub oracle_targets: Option<Vec<OracleTarget>>,
+    // Rates are posted on every change if not set
+    #[serde(default)]
+    pub oracle_throttle: Option<OracleThrottleConfig>,
+    // Resuming the contract needs the approval of the monitors if set
+    #[serde(default)]
+    pub monitor_quorum: Option<MonitorQuorumConfig>,
 }
 // TODO: PENDING - DOCS DEFINE THESE AS MAPS?
 // Discuss: Do we want to add or remove any state?
@@ -29,18 +56,291 @@ pub struct Config {
 pub struct State {
     pub total_native_token: Uint128,
     pub total_liquid_stake_token: Uint128,
+    // Deprecated, pending transfers are kept by `OWNERSHIP` since v0.5.0
     pub pending_owner: Option<Addr>,
     pub owner_transfer_min_time: Option<Timestamp>,
     pub total_reward_amount: Uint128,
-    pub rate: Uint128,
+    pub rate: Uint128, // never updated, rates are returned by the Rates query
     pub total_fees: Uint128,
     pub ibc_id_counter: u64,
+    // Funded with `FundKeeperBounty` to pay keeper rewards
+    #[serde(default)]
+    pub keeper_bounty: Uint128,
+    #[serde(default)]
+    pub last_batch_submission: Option<u64>,
 }
 
 #[cw_serde]
 #[derive(Default)]
 pub struct ProtocolFeeConfig {
-    pub dao_treasury_fee: Uint128, // not using a fraction, fee percentage=x/100000
+    /// Share of the rewards taken as fee
+    pub dao_treasury_fee: Decimal,
+    /// Fee taken from the liquid staked tokens
+    #[serde(default)]
+    pub deposit_fee: Option<CappedFee>,
+    /// Fee taken from withdrawals, fixed for a batch when it is submitted
+    #[serde(default)]
+    pub redemption_fee: Option<CappedFee>,
+    /// How the fee on rewards is collected
+    #[serde(default)]
+    pub reward_fee_mode: RewardFeeMode,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum RewardFeeMode {
+    /// Kept in native tokens, or sent to the treasury
+    #[default]
+    Native,
+    /// The whole reward is staked and stTIA worth the fee is minted to the treasury
+    Mint,
+}
+
+/// Fee config replacing the current one once `effective_at` has passed
+#[cw_serde]
+pub struct PendingProtocolFeeConfig {
+    pub protocol_fee_config: ProtocolFeeConfig,
+    pub effective_at: u64,
+}
+
+/// Permission the admin can grant to other addresses, the admin has all of them
+#[cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    /// Updates the config, except for the fees and payouts, and executes or cancels changes
+    /// of it
+    ConfigManager,
+    /// Adds and removes validators
+    ValidatorManager,
+    /// Updates the fee config, fee recipients, keeper reward and extra reward denoms, and
+    /// withdraws the fees
+    FeeManager,
+    /// Recovers selected IBC transfers
+    RecoveryOperator,
+    /// Stops the contract with `CircuitBreaker`
+    Pauser,
+    /// Resumes the contract
+    Resumer,
+}
+
+impl Role {
+    pub const ALL: [Role; 6] = [
+        Role::ConfigManager,
+        Role::ValidatorManager,
+        Role::FeeManager,
+        Role::RecoveryOperator,
+        Role::Pauser,
+        Role::Resumer,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::ConfigManager => "config_manager",
+            Role::ValidatorManager => "validator_manager",
+            Role::FeeManager => "fee_manager",
+            Role::RecoveryOperator => "recovery_operator",
+            Role::Pauser => "pauser",
+            Role::Resumer => "resumer",
+        }
+    }
+}
+
+/// Sensitive config fields, only changed once a proposed change passed its timelock
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigChange {
+    pub multisig_address_config: Option<MultisigAddressConfig>,
+    pub native_token_denom: Option<String>,
+    pub ibc_channel_id: Option<String>,
+    pub treasury_address: Option<Addr>,
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
+    pub extra_reward_denoms: Option<Vec<ExtraRewardDenom>>,
+    /// An empty address removes the oracle
+    pub oracle_address: Option<String>,
+    pub oracle_targets: Option<Vec<OracleTarget>>,
+    pub monitors: Option<Vec<Addr>>,
+    pub monitor_quorum: Option<MonitorQuorumConfig>,
+    #[serde(default)]
+    pub clear_monitor_quorum: bool,
+}
+
+impl ConfigChange {
+    pub fn is_empty(&self) -> bool {
+        self.multisig_address_config.is_none()
+            && self.native_token_denom.is_none()
+            && self.ibc_channel_id.is_none()
+            && self.treasury_address.is_none()
+            && self.fee_recipients.is_none()
+            && self.extra_reward_denoms.is_none()
+            && self.oracle_address.is_none()
+            && self.oracle_targets.is_none()
+            && !self.changes_monitors()
+    }
+
+    pub fn changes_monitors(&self) -> bool {
+        self.monitors.is_some() || self.monitor_quorum.is_some() || self.clear_monitor_quorum
+    }
+
+    // Fee recipients and extra reward denoms are changed by the fee manager, the other fields
+    // by the config manager
+    pub fn roles(&self) -> Vec<Role> {
+        let mut roles = vec![];
+        if self.fee_recipients.is_some() || self.extra_reward_denoms.is_some() {
+            roles.push(Role::FeeManager);
+        }
+        let config_fields = ConfigChange {
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            ..self.clone()
+        };
+        if !config_fields.is_empty() {
+            roles.push(Role::ConfigManager);
+        }
+        roles
+    }
+
+    pub fn apply(self, config: &mut Config) {
+        if let Some(multisig_address_config) = self.multisig_address_config {
+            config.multisig_address_config = multisig_address_config;
+        }
+        if let Some(native_token_denom) = self.native_token_denom {
+            config.native_token_denom = native_token_denom;
+        }
+        if let Some(ibc_channel_id) = self.ibc_channel_id {
+            config.ibc_channel_id = ibc_channel_id;
+        }
+        if let Some(treasury_address) = self.treasury_address {
+            config.treasury_address = treasury_address;
+        }
+        if let Some(fee_recipients) = self.fee_recipients {
+            config.fee_recipients = Some(fee_recipients);
+        }
+        if let Some(extra_reward_denoms) = self.extra_reward_denoms {
+            config.extra_reward_denoms = Some(extra_reward_denoms);
+        }
+        if let Some(oracle_address) = self.oracle_address {
+            config.oracle_address = if oracle_address.is_empty() {
+                None
+            } else {
+                Some(Addr::unchecked(oracle_address))
+            };
+        }
+        if let Some(oracle_targets) = self.oracle_targets {
+            config.oracle_targets = Some(oracle_targets);
+        }
+        if let Some(monitors) = self.monitors {
+            config.monitors = Some(monitors);
+        }
+        if let Some(monitor_quorum) = self.monitor_quorum {
+            config.monitor_quorum = Some(monitor_quorum);
+        }
+        if self.clear_monitor_quorum {
+            config.monitor_quorum = None;
+        }
+    }
+
+    // `proposed_<field>` for each field the change sets
+    pub fn attributes(&self) -> StdResult<Vec<Attribute>> {
+        let mut attrs = vec![];
+        if let Some(multisig_address_config) = &self.multisig_address_config {
+            attrs.push(attr(
+                "proposed_staker_address",
+                &multisig_address_config.staker_address,
+            ));
+            attrs.push(attr(
+                "proposed_reward_collector_address",
+                &multisig_address_config.reward_collector_address,
+            ));
+        }
+        if let Some(native_token_denom) = &self.native_token_denom {
+            attrs.push(attr("proposed_native_token_denom", native_token_denom));
+        }
+        if let Some(ibc_channel_id) = &self.ibc_channel_id {
+            attrs.push(attr("proposed_ibc_channel_id", ibc_channel_id));
+        }
+        if let Some(treasury_address) = &self.treasury_address {
+            attrs.push(attr("proposed_treasury_address", treasury_address));
+        }
+        if let Some(fee_recipients) = &self.fee_recipients {
+            attrs.push(attr(
+                "proposed_fee_recipients",
+                to_json_string(fee_recipients)?,
+            ));
+        }
+        if let Some(extra_reward_denoms) = &self.extra_reward_denoms {
+            attrs.push(attr(
+                "proposed_extra_reward_denoms",
+                to_json_string(extra_reward_denoms)?,
+            ));
+        }
+        if let Some(oracle_address) = &self.oracle_address {
+            attrs.push(attr("proposed_oracle_address", oracle_address));
+        }
+        if let Some(oracle_targets) = &self.oracle_targets {
+            attrs.push(attr(
+                "proposed_oracle_targets",
+                to_json_string(oracle_targets)?,
+            ));
+        }
+        if let Some(monitors) = &self.monitors {
+            attrs.push(attr("proposed_monitors", to_json_string(monitors)?));
+        }
+        if let Some(monitor_quorum) = &self.monitor_quorum {
+            attrs.push(attr(
+                "proposed_monitor_quorum",
+                to_json_string(monitor_quorum)?,
+            ));
+        }
+        if self.clear_monitor_quorum {
+            attrs.push(attr("proposed_clear_monitor_quorum", "true"));
+        }
+        Ok(attrs)
+    }
+}
+
+/// Config change proposed by the admin, can be executed once `effective_at` has passed
+#[cw_serde]
+pub struct PendingConfigChange {
+    pub id: u64,
+    pub change: ConfigChange,
+    pub proposed_at: u64,
+    pub effective_at: u64,
+}
+
+/// Privileged action, with the values it changed serialized as JSON
+#[cw_serde]
+pub struct AuditEntry {
+    pub id: u64,
+    pub actor: Addr,
+    pub action: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+/// Receives the share `weight` of the fees sent out of the contract
+#[cw_serde]
+pub struct FeeRecipient {
+    pub address: Addr,
+    pub weight: Decimal,
+}
+
+/// Fee in basis points of an amount, capped per deposit or withdrawal
+#[cw_serde]
+pub struct CappedFee {
+    pub bps: u64,
+    pub max_fee: Option<Uint128>,
+}
+
+impl CappedFee {
+    pub fn compute(&self, amount: Uint128) -> Uint128 {
+        let fee = amount.multiply_ratio(self.bps, 10_000u128);
+        match self.max_fee {
+            Some(max_fee) => fee.min(max_fee),
+            None => fee,
+        }
+    }
 }
 
 #[cw_serde]
@@ -49,11 +349,222 @@ pub struct MultisigAddressConfig {
     pub reward_collector_address: Addr,
 }
 
+/// Optional feature that `UpdateConfig` turns off again by clearing its config
+#[cw_serde]
+#[derive(Copy)]
+pub enum OptionalConfig {
+    KeeperReward,
+    BatchTrigger,
+    UnclaimedSweep,
+    OracleThrottle,
+    MonitorQuorum,
+}
+
+impl OptionalConfig {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OptionalConfig::KeeperReward => "keeper_reward",
+            OptionalConfig::BatchTrigger => "batch_trigger",
+            OptionalConfig::UnclaimedSweep => "unclaimed_sweep",
+            OptionalConfig::OracleThrottle => "oracle_throttle",
+            OptionalConfig::MonitorQuorum => "monitor_quorum",
+        }
+    }
+}
+
+/// Reward paid to whoever submits a ready batch
+#[cw_serde]
+pub struct KeeperRewardConfig {
+    /// Share of the batch's expected native unstaked paid as reward
+    pub rate: Decimal,
+    /// Maximum reward paid per batch
+    pub max_reward: Uint128,
+    /// Where the reward is paid from
+    pub source: KeeperRewardSource,
+}
+
+/// Rates are only posted once they moved by more than `threshold` relative to the last
+/// posted rates, or `heartbeat` seconds passed since then
+#[cw_serde]
+pub struct OracleThrottleConfig {
+    pub threshold: Decimal,
+    pub heartbeat: u64,
+}
+
+/// Number of monitors needed to approve a `MonitorAction`, proposals expire after
+/// `proposal_ttl` seconds
+#[cw_serde]
+pub struct MonitorQuorumConfig {
+    pub threshold: u32,
+    pub proposal_ttl: u64,
+    /// Recovering selected IBC transfers needs the approval of the monitors as well
+    #[serde(default)]
+    pub recovery: bool,
+}
+
+/// Action executed once enough monitors approved it
+#[cw_serde]
+pub enum MonitorAction {
+    ResumeContract {
+        total_native_token: Uint128,
+        total_liquid_stake_token: Uint128,
+        total_reward_amount: Uint128,
+    },
+    RecoverPendingIbcTransfers {
+        selected_packets: Vec<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct MonitorProposal {
+    pub id: u64,
+    pub action: MonitorAction,
+    pub approvals: Vec<Addr>,
+    pub expires_at: u64,
+}
+
+/// Rates in effect from the time it is keyed by until the next snapshot
+#[cw_serde]
+pub struct RateSnapshot {
+    pub redemption_rate: Decimal256,
+    pub purchase_rate: Decimal256,
+}
+
+/// Rates last posted to the oracles
+#[cw_serde]
+pub struct PostedRates {
+    pub redemption_rate: Decimal,
+    pub purchase_rate: Decimal,
+    pub posted_at: u64,
+}
+
+/// Reward denom forwarded to `destination` when received with the rewards
+#[cw_serde]
+pub struct ExtraRewardDenom {
+    pub denom: String,
+    pub destination: ExtraRewardDestination,
+}
+
+#[cw_serde]
+pub enum ExtraRewardDestination {
+    Treasury,
+    /// Contract executed with `msg` and the tokens, e.g. a swap route
+    Contract {
+        address: Addr,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+pub enum KeeperRewardSource {
+    /// Paid from the collected protocol fees
+    Fees,
+    /// Paid from the bounty funded with `FundKeeperBounty`
+    Bounty,
+}
+
+/// Lets a pending batch be submitted before the batch period has passed once it grows
+/// too large, while keeping a minimum spacing between submissions
+#[cw_serde]
+pub struct BatchTriggerConfig {
+    /// Batch can be submitted early once its stTIA exceeds this amount
+    pub max_batch_liquid_stake: Option<Uint128>,
+    /// Batch can be submitted early once its unstake requests exceed this count
+    pub max_unstake_requests: Option<u64>,
+    /// Minimum seconds between two batch submissions
+    pub min_submission_interval: u64,
+}
+
+/// Lets the withdrawals of a received batch that were not claimed within the claim window
+/// be swept out of the contract
+#[cw_serde]
+pub struct UnclaimedSweepConfig {
+    /// Seconds after a batch was received during which its requests can be withdrawn
+    pub claim_window: u64,
+    /// Where the swept tokens are sent
+    pub destination: SweepDestination,
+    /// Whether anyone can sweep, otherwise only the owner
+    pub permissionless: bool,
+}
+
+#[cw_serde]
+pub enum SweepDestination {
+    /// Sent to the treasury
+    Treasury,
+    /// Staked again, with the stTIA minted to the treasury which pays the owners in stTIA
+    Restake,
+}
+
+/// Destination on a remote chain that tokens are forwarded to over IBC
+#[cw_serde]
+pub struct IbcForward {
+    /// IBC channel id on Osmosis to the destination chain
+    pub channel_id: String,
+    /// Address on the destination chain receiving the tokens
+    pub receiver: String,
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const ADMIN: Admin = Admin::new("admin");
+pub const OWNERSHIP: Ownership = Ownership::new("admin", "pending_ownership", "ownership_config");
 pub const STATE: Item<State> = Item::new("state");
 pub const BATCHES: Map<u64, Batch> = Map::new("batches");
 pub const PENDING_BATCH_ID: Item<u64> = Item::new("pending_batch_id");
+pub const ARCHIVED_BATCHES: Map<u64, ArchivedBatch> = Map::new("archived_batches");
+/// Fees sent to each fee recipient so far
+pub const DISTRIBUTED_FEES: Map<&Addr, Uint128> = Map::new("distributed_fees");
+/// Redemption fee of submitted batches, as configured when the batch was submitted
+pub const BATCH_REDEMPTION_FEES: Map<u64, CappedFee> = Map::new("batch_redemption_fees");
+/// Extra rewards forwarded so far, by denom
+pub const FORWARDED_EXTRA_REWARDS: Map<&str, Uint128> = Map::new("forwarded_extra_rewards");
+/// Rates by the time they changed, kept for `MAX_TWAP_WINDOW`
+pub const RATE_SNAPSHOTS: Map<u64, RateSnapshot> = Map::new("rate_snapshots");
+pub const LAST_POSTED_RATES: Item<PostedRates> = Item::new("last_posted_rates");
+pub const PENDING_PROTOCOL_FEE_CONFIG: Item<PendingProtocolFeeConfig> =
+    Item::new("pending_protocol_fee_config");
+pub const PENDING_CONFIG_CHANGES: Map<u64, PendingConfigChange> =
+    Map::new("pending_config_changes");
+pub const CONFIG_CHANGE_ID_COUNTER: Item<u64> = Item::new("config_change_id_counter");
+pub const MONITOR_PROPOSALS: Map<u64, MonitorProposal> = Map::new("monitor_proposals");
+pub const MONITOR_PROPOSAL_ID_COUNTER: Item<u64> = Item::new("monitor_proposal_id_counter");
+pub const AUDIT_LOG: Map<u64, AuditEntry> = Map::new("audit_log");
+pub const AUDIT_LOG_ID_COUNTER: Item<u64> = Item::new("audit_log_id_counter");
+/// Addresses granted a role, by role
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
+/// Batches with a lower id still have the 0.5.0 layout, set until they are all migrated
+pub const LEGACY_BATCHES_BEFORE: Item<u64> = Item::new("legacy_batches_before");
+
+/// Summary of a batch kept once all of its unstake requests have been withdrawn
+#[cw_serde]
+pub struct ArchivedBatch {
+    pub id: u64,
+    /// Total amount of stTIA burned in the batch
+    pub batch_total_liquid_stake: Uint128,
+    /// Amount of native tokens received after unbonding
+    pub received_native_unstaked: Uint128,
+    /// Amount of native tokens withdrawn, the rest is rounding dust
+    pub withdrawn_native_unstaked: Uint128,
+    /// Amount of native tokens swept after the claim window passed
+    #[serde(default)]
+    pub swept_native_unstaked: Uint128,
+    pub unstake_requests_count: u64,
+}
+
+/// Unstake request swept after the claim window. Its owner can still be paid by the
+/// treasury, which settles the request
+#[cw_serde]
+pub struct SweptRequest {
+    pub batch_id: u64,
+    pub user: String,
+    /// Amount of native tokens owed to the user
+    pub amount: Uint128,
+    /// Amount of stTIA owed instead, when the swept tokens were restaked
+    #[serde(default)]
+    pub liquid_stake_amount: Option<Uint128>,
+    pub settled: bool,
+}
+
+/// Swept requests by (user, batch_id)
+pub const SWEPT_REQUESTS: Map<(String, u64), SweptRequest> = Map::new("swept_requests");
 
 #[cw_serde]
 pub struct UnstakeRequest {
@@ -136,8 +647,29 @@ pub mod ibc {
         pub amount: u128,
         pub status: PacketLifecycleStatus,
     }
+
+    /// A transfer packet forwarding tokens to a user on a remote chain.
+    /// If the receive fails or times-out the tokens are credited to the
+    /// fallback address, which can then claim them on Osmosis
+    #[cw_serde]
+    pub struct ForwardTransfer {
+        pub channel_id: String,
+        pub receiver: String,
+        pub fallback_address: Addr,
+        pub denom: String,
+        pub amount: Uint128,
+    }
 }
 
 /// In-Flight packets by (source_channel_id, sequence)
 pub const INFLIGHT_PACKETS: Map<u64, ibc::IBCTransfer> = Map::new("inflight");
 pub const IBC_WAITING_FOR_REPLY: Map<u64, IbcWaitingForReply> = Map::new("ibc_waiting_for_reply");
+
+/// Forward transfers waiting for the reply containing their packet sequence
+pub const FORWARD_WAITING_FOR_REPLY: Map<u64, ibc::ForwardTransfer> =
+    Map::new("forward_waiting_for_reply");
+/// In-Flight forward packets by (source_channel_id, sequence)
+pub const INFLIGHT_FORWARDS: Map<(String, u64), ibc::ForwardTransfer> =
+    Map::new("inflight_forwards");
+/// Funds of failed forward transfers by (fallback_address, denom)
+pub const CLAIMABLE_FUNDS: Map<(String, String), Uint128> = Map::new("claimable_funds");
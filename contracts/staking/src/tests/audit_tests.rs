@@ -0,0 +1,102 @@
+use crate::contract::{execute, query};
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::{AuditEntry, Config, State, CONFIG};
+use crate::tests::test_helper::{init, CELESTIAVAL1, CELESTIAVAL3, OSMO2, OSMO3};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{from_json, Addr, OwnedDeps, Uint128};
+
+fn audit_log(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Vec<AuditEntry> {
+    let msg = QueryMsg::AuditLog { start_after, limit };
+    from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn audit_validator_changes() {
+    let mut deps = init();
+    let info = mock_info(OSMO3, &[]);
+    let msg = ExecuteMsg::AddValidator {
+        new_validator: CELESTIAVAL3.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::RemoveValidator {
+        validator: CELESTIAVAL1.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the first entry is the config set at instantiation
+    let entries = audit_log(&deps, Some(1), None);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id, 2);
+    assert_eq!(entries[0].actor, Addr::unchecked(OSMO3));
+    assert_eq!(entries[0].action, "add_validator");
+    assert_eq!(entries[0].height, mock_env().block.height);
+    let before: Vec<Addr> = from_json(entries[0].before.as_ref().unwrap()).unwrap();
+    let after: Vec<Addr> = from_json(entries[0].after.as_ref().unwrap()).unwrap();
+    assert_eq!(after.len(), before.len() + 1);
+    assert_eq!(after.last(), Some(&Addr::unchecked(CELESTIAVAL3)));
+    assert_eq!(entries[1].action, "remove_validator");
+    let after: Vec<Addr> = from_json(entries[1].after.as_ref().unwrap()).unwrap();
+    assert!(!after.contains(&Addr::unchecked(CELESTIAVAL1)));
+
+    // paginated by id
+    let entries = audit_log(&deps, Some(2), Some(1));
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].action, "remove_validator");
+}
+
+#[test]
+fn audit_config_and_resume() {
+    let mut deps = init();
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: Some(7200),
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+
+    let entries = audit_log(&deps, Some(1), None);
+    assert_eq!(entries[0].action, "update_config");
+    let before: Config = from_json(entries[0].before.as_ref().unwrap()).unwrap();
+    let after: Config = from_json(entries[0].after.as_ref().unwrap()).unwrap();
+    assert_eq!(before.batch_period, 86400);
+    assert_eq!(after, CONFIG.load(&deps.storage).unwrap());
+
+    // pausing by a monitor and resuming are audited as well
+    let msg = ExecuteMsg::CircuitBreaker {};
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO2, &[]), msg).unwrap();
+    let msg = ExecuteMsg::ResumeContract {
+        total_native_token: Uint128::from(100u128),
+        total_liquid_stake_token: Uint128::from(100u128),
+        total_reward_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+
+    let entries = audit_log(&deps, Some(2), None);
+    assert_eq!(entries[0].action, "circuit_breaker");
+    assert_eq!(entries[0].actor, Addr::unchecked(OSMO2));
+    assert_eq!(entries[0].after.as_deref(), Some("true"));
+    assert_eq!(entries[1].action, "resume_contract");
+    let after: State = from_json(entries[1].after.as_ref().unwrap()).unwrap();
+    assert_eq!(after.total_native_token, Uint128::from(100u128));
+}
@@ -1,10 +1,14 @@
-use crate::contract::execute;
+use crate::contract::{execute, query, CONFIG_CHANGE_DELAY, MAX_OPEN_MONITOR_PROPOSALS};
+use crate::error::ContractError;
 use crate::helpers::derive_intermediate_sender;
-use crate::msg::ExecuteMsg;
-use crate::state::{new_unstake_request, State, BATCHES, CONFIG, STATE};
-use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO2, OSMO3};
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::{
+    ibc, new_unstake_request, MonitorAction, MonitorProposal, MonitorQuorumConfig, OptionalConfig,
+    State, BATCHES, CONFIG, INFLIGHT_PACKETS, MONITOR_PROPOSALS, STATE,
+};
+use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3};
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{coins, Coin, Uint128};
+use cosmwasm_std::{coins, from_json, Addr, Coin, Order, Uint128};
 use milky_way::staking::Batch;
 
 #[test]
@@ -46,6 +50,7 @@ fn circuit_breaker() {
     let msg = ExecuteMsg::LiquidStake {
         mint_to: None,
         expected_mint_amount: None,
+        forward: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert!(res.is_err());
@@ -55,7 +60,7 @@ fn circuit_breaker() {
     state.total_native_token = Uint128::from(300_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
     let info = mock_info("bob", &coins(1000, "factory/cosmos2contract/stTIA"));
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert!(res.is_err());
 
@@ -65,7 +70,8 @@ fn circuit_breaker() {
         &config.ibc_channel_id,
         config
             .multisig_address_config
-            .reward_collector_address.as_ref(),
+            .reward_collector_address
+            .as_ref(),
         "osmo",
     )
     .unwrap();
@@ -144,7 +150,418 @@ fn circuit_breaker() {
     let msg = ExecuteMsg::LiquidStake {
         expected_mint_amount: None,
         mint_to: None,
+        forward: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert!(res.is_ok());
 }
+
+#[test]
+fn monitor_quorum_resume() {
+    let mut deps = init();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.monitor_quorum = Some(MonitorQuorumConfig {
+        threshold: 2,
+        proposal_ttl: 3600,
+        recovery: false,
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::CircuitBreaker {},
+    )
+    .unwrap();
+
+    // the admin can't resume alone
+    let msg = ExecuteMsg::ResumeContract {
+        total_native_token: Uint128::from(300_000u128),
+        total_liquid_stake_token: Uint128::from(100_000u128),
+        total_reward_amount: Uint128::zero(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OSMO3, &[]), msg);
+    assert!(matches!(res, Err(ContractError::MonitorQuorumRequired {})));
+
+    let propose = ExecuteMsg::ProposeMonitorAction {
+        action: MonitorAction::ResumeContract {
+            total_native_token: Uint128::from(300_000u128),
+            total_liquid_stake_token: Uint128::from(100_000u128),
+            total_reward_amount: Uint128::zero(),
+        },
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO1, &[]),
+        propose.clone(),
+    );
+    assert!(matches!(res, Err(ContractError::Unauthorized { .. })));
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        propose.clone(),
+    )
+    .unwrap();
+    assert!(CONFIG.load(&deps.storage).unwrap().stopped);
+
+    let approve = ExecuteMsg::ApproveMonitorAction { id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        approve.clone(),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::AlreadyApproved { id: 1, .. })
+    ));
+
+    // expired proposals can't be approved
+    let mut expired_env = mock_env();
+    expired_env.block.time = expired_env.block.time.plus_seconds(3600);
+    let res = execute(deps.as_mut(), expired_env, mock_info(OSMO3, &[]), approve);
+    assert!(matches!(
+        res,
+        Err(ContractError::MonitorProposalExpired { id: 1, .. })
+    ));
+
+    execute(deps.as_mut(), env.clone(), mock_info(OSMO2, &[]), propose).unwrap();
+    let msg = QueryMsg::MonitorProposals {
+        start_after: None,
+        limit: None,
+    };
+    let proposals: Vec<MonitorProposal> =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(proposals.len(), 2);
+    assert_eq!(proposals[1].approvals, vec![OSMO2.to_string()]);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::ApproveMonitorAction { id: 2 },
+    )
+    .unwrap();
+    let actions: Vec<_> = res
+        .attributes
+        .iter()
+        .filter(|a| a.key == "action")
+        .collect();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].value, "approve_monitor_action");
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "executed_action" && a.value == "resume_contract"));
+    assert!(!CONFIG.load(&deps.storage).unwrap().stopped);
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_native_token, Uint128::from(300_000u128));
+
+    let proposals: Vec<MonitorProposal> =
+        from_json(query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(proposals.len(), 1);
+}
+
+#[test]
+fn monitor_quorum_cant_be_cleared_to_resume() {
+    let mut deps = init();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.monitor_quorum = Some(MonitorQuorumConfig {
+        threshold: 2,
+        proposal_ttl: 3600,
+        recovery: false,
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let update_monitors = |monitors: Option<Vec<String>>, clear: Option<Vec<OptionalConfig>>| {
+        ExecuteMsg::UpdateConfig {
+            batch_period: None,
+            unbonding_period: None,
+            minimum_liquid_stake_amount: None,
+            multisig_address_config: None,
+            protocol_fee_config: None,
+            native_token_denom: None,
+            channel_id: None,
+            monitors,
+            treasury_address: None,
+            oracle_address: None,
+            send_fees_to_treasury: None,
+            keeper_reward: None,
+            batch_trigger: None,
+            unclaimed_sweep: None,
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            oracle_targets: None,
+            oracle_throttle: None,
+            monitor_quorum: None,
+            clear,
+        }
+    };
+
+    // clearing the quorum is proposed while running, and can't be executed once stopped
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &[]),
+        update_monitors(None, Some(vec![OptionalConfig::MonitorQuorum])),
+    )
+    .unwrap();
+    assert!(CONFIG.load(&deps.storage).unwrap().monitor_quorum.is_some());
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::CircuitBreaker {},
+    )
+    .unwrap();
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(CONFIG_CHANGE_DELAY);
+    let res = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::ExecuteConfigChange { id: 1 },
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MonitorsChangeWhileStopped {})
+    ));
+
+    // neither the quorum nor the monitors can be changed while stopped
+    let res = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(OSMO3, &[]),
+        update_monitors(None, Some(vec![OptionalConfig::MonitorQuorum])),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MonitorsChangeWhileStopped {})
+    ));
+    let res = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(OSMO3, &[]),
+        update_monitors(Some(vec![OSMO3.to_string()]), None),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MonitorsChangeWhileStopped {})
+    ));
+
+    let msg = ExecuteMsg::ResumeContract {
+        total_native_token: Uint128::from(300_000u128),
+        total_liquid_stake_token: Uint128::from(100_000u128),
+        total_reward_amount: Uint128::zero(),
+    };
+    let res = execute(deps.as_mut(), later, mock_info(OSMO3, &[]), msg);
+    assert!(matches!(res, Err(ContractError::MonitorQuorumRequired {})));
+    assert!(CONFIG.load(&deps.storage).unwrap().monitor_quorum.is_some());
+}
+
+#[test]
+fn monitor_changes_are_timelocked() {
+    let mut deps = init();
+    let update_monitors = |threshold: u32| ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: Some(vec![OSMO1.to_string(), OSMO2.to_string()]),
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: Some(MonitorQuorumConfig {
+            threshold,
+            proposal_ttl: 3600,
+            recovery: false,
+        }),
+        clear: None,
+    };
+
+    // the quorum is checked against the proposed monitors
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        update_monitors(3),
+    );
+    assert!(matches!(res, Err(ContractError::InvalidMonitorQuorum {})));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        update_monitors(2),
+    )
+    .unwrap();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.monitor_quorum, None);
+    assert!(!config.monitors.unwrap().contains(&Addr::unchecked(OSMO1)));
+
+    // the current monitors can veto
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::VetoConfigChange { id: 1 },
+    );
+    assert!(matches!(res, Err(ContractError::Unauthorized { .. })));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_CHANGE_DELAY);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::ExecuteConfigChange { id: 1 },
+    )
+    .unwrap();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.monitor_quorum.unwrap().threshold, 2);
+    assert_eq!(
+        config.monitors,
+        Some(vec![Addr::unchecked(OSMO1), Addr::unchecked(OSMO2)])
+    );
+}
+
+#[test]
+fn monitor_proposals_expire_and_are_limited() {
+    let mut deps = init();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.monitor_quorum = Some(MonitorQuorumConfig {
+        threshold: 2,
+        proposal_ttl: 3600,
+        recovery: false,
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let propose = ExecuteMsg::ProposeMonitorAction {
+        action: MonitorAction::ResumeContract {
+            total_native_token: Uint128::from(300_000u128),
+            total_liquid_stake_token: Uint128::from(100_000u128),
+            total_reward_amount: Uint128::zero(),
+        },
+    };
+    let env = mock_env();
+    for _ in 0..MAX_OPEN_MONITOR_PROPOSALS {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(OSMO2, &[]),
+            propose.clone(),
+        )
+        .unwrap();
+    }
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        propose.clone(),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::TooManyMonitorProposals { .. })
+    ));
+    // the limit is per monitor
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &[]),
+        propose.clone(),
+    )
+    .unwrap();
+
+    // expired proposals are no longer listed and don't count towards the limit
+    let mut expired_env = mock_env();
+    expired_env.block.time = expired_env.block.time.plus_seconds(3600);
+    let msg = QueryMsg::MonitorProposals {
+        start_after: None,
+        limit: None,
+    };
+    let proposals: Vec<MonitorProposal> =
+        from_json(query(deps.as_ref(), expired_env.clone(), msg).unwrap()).unwrap();
+    assert!(proposals.is_empty());
+
+    execute(deps.as_mut(), expired_env, mock_info(OSMO2, &[]), propose).unwrap();
+    assert_eq!(
+        MONITOR_PROPOSALS
+            .range(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn monitor_quorum_recover_keeps_event() {
+    let mut deps = init();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.monitor_quorum = Some(MonitorQuorumConfig {
+        threshold: 2,
+        proposal_ttl: 3600,
+        recovery: true,
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+    INFLIGHT_PACKETS
+        .save(
+            &mut deps.storage,
+            1,
+            &ibc::IBCTransfer {
+                sequence: 1,
+                amount: 1000,
+                status: ibc::PacketLifecycleStatus::TimedOut,
+            },
+        )
+        .unwrap();
+
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::ProposeMonitorAction {
+            action: MonitorAction::RecoverPendingIbcTransfers {
+                selected_packets: vec![1],
+            },
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::ApproveMonitorAction { id: 1 },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert!(res.events.iter().any(|e| e.ty == "milkyway-recover"));
+    let actions: Vec<_> = res
+        .attributes
+        .iter()
+        .filter(|a| a.key == "action")
+        .collect();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].value, "approve_monitor_action");
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "executed_action" && a.value == "recover"));
+    assert!(INFLIGHT_PACKETS.is_empty(&deps.storage));
+}
@@ -0,0 +1,472 @@
+use crate::contract::{execute, query, CONFIG_CHANGE_DELAY};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::oracle::{OracleFormat, OracleTarget};
+use crate::state::{
+    Config, ExtraRewardDenom, ExtraRewardDestination, FeeRecipient, MultisigAddressConfig,
+    PendingConfigChange, Role, CONFIG,
+};
+use crate::tests::test_helper::{
+    init, CELESTIA2, CHANNEL_ID, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3, OSMO4,
+};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{from_json, to_json_binary, Addr, Decimal, Env, OwnedDeps, Response, Uint128};
+
+fn update_config(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    env: Env,
+    minimum_liquid_stake_amount: Option<Uint128>,
+    treasury_address: Option<String>,
+) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    execute(deps.as_mut(), env, mock_info(OSMO3, &[]), msg)
+}
+
+fn pending_changes(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+) -> Vec<PendingConfigChange> {
+    let msg = QueryMsg::PendingConfigChanges {
+        start_after: None,
+        limit: None,
+    };
+    from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn timelocked_config_change() {
+    let mut deps = init();
+    let env = mock_env();
+
+    // non sensitive fields are changed right away
+    let res = update_config(
+        &mut deps,
+        env.clone(),
+        Some(Uint128::from(500u128)),
+        Some(OSMO4.to_string()),
+    )
+    .unwrap();
+    let effective_at = env.block.time.seconds() + CONFIG_CHANGE_DELAY;
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "config_change_effective_at" && a.value == effective_at.to_string()));
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.minimum_liquid_stake_amount, Uint128::from(500u128));
+    assert_eq!(config.treasury_address, Addr::unchecked(OSMO1));
+
+    let pending = pending_changes(&deps);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, 1);
+    assert_eq!(pending[0].effective_at, effective_at);
+    assert_eq!(
+        pending[0].change.treasury_address,
+        Some(Addr::unchecked(OSMO4))
+    );
+
+    // not before the timelock passed
+    let msg = ExecuteMsg::ExecuteConfigChange { id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &[]),
+        msg.clone(),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::ConfigChangeTimelocked { id: 1, effective_at: e }) if e == effective_at
+    ));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_CHANGE_DELAY);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO1, &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.treasury_address, Addr::unchecked(OSMO4));
+    assert_eq!(config.native_token_denom, NATIVE_TOKEN);
+    assert_eq!(config.ibc_channel_id, CHANNEL_ID);
+    assert!(pending_changes(&deps).is_empty());
+
+    // executed only once
+    let res = execute(deps.as_mut(), env, mock_info(OSMO3, &[]), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::ConfigChangeNotFound { id: 1 })
+    ));
+}
+
+#[test]
+fn cancel_and_veto_config_change() {
+    let mut deps = init();
+
+    update_config(&mut deps, mock_env(), None, Some(OSMO4.to_string())).unwrap();
+    update_config(&mut deps, mock_env(), None, Some(OSMO2.to_string())).unwrap();
+    assert_eq!(pending_changes(&deps).len(), 2);
+
+    // only the admin can cancel
+    let msg = ExecuteMsg::CancelConfigChange { id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+
+    // only monitors can veto
+    let msg = ExecuteMsg::VetoConfigChange { id: 2 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        msg.clone(),
+    );
+    assert!(matches!(res, Err(ContractError::Unauthorized { .. })));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(pending_changes(&deps).is_empty());
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info(OSMO2, &[]), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::ConfigChangeNotFound { id: 2 })
+    ));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_CHANGE_DELAY);
+    let msg = ExecuteMsg::ExecuteConfigChange { id: 2 };
+    let res = execute(deps.as_mut(), env, mock_info(OSMO3, &[]), msg);
+    assert!(res.is_err());
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.treasury_address, Addr::unchecked(OSMO1));
+}
+
+#[test]
+fn config_change_attributes() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let res = update_config(
+        &mut deps,
+        env.clone(),
+        Some(Uint128::from(500u128)),
+        Some(OSMO4.to_string()),
+    )
+    .unwrap();
+    let attribute = |res: &Response, key: &str| {
+        res.attributes
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.clone())
+    };
+    assert_eq!(
+        attribute(&res, "old_minimum_liquid_stake_amount"),
+        Some("100".to_string())
+    );
+    assert_eq!(
+        attribute(&res, "new_minimum_liquid_stake_amount"),
+        Some("500".to_string())
+    );
+    assert_eq!(
+        attribute(&res, "proposed_treasury_address"),
+        Some(OSMO4.to_string())
+    );
+    // unchanged fields are left out
+    assert_eq!(attribute(&res, "old_batch_period"), None);
+    assert_eq!(attribute(&res, "old_treasury_address"), None);
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_CHANGE_DELAY);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::ExecuteConfigChange { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        attribute(&res, "old_treasury_address"),
+        Some(OSMO1.to_string())
+    );
+    assert_eq!(
+        attribute(&res, "new_treasury_address"),
+        Some(OSMO4.to_string())
+    );
+}
+
+#[test]
+fn config_field_errors() {
+    let mut deps = init();
+
+    let res = update_config(
+        &mut deps,
+        mock_env(),
+        None,
+        Some("osmo1invalid".to_string()),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::InvalidConfigField { field, .. }) if field == "treasury_address"
+    ));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: Some(NATIVE_TOKEN.to_string()),
+        channel_id: Some("chan-0".to_string()),
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::InvalidConfigField { field, .. }) if field == "channel_id"
+    ));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: Some(MultisigAddressConfig {
+            staker_address: Addr::unchecked(OSMO1),
+            reward_collector_address: Addr::unchecked(CELESTIA2),
+        }),
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::InvalidConfigField { field, .. }) if field == "staker_address"
+    ));
+}
+
+fn update_sensitive_config(
+    fee_recipients: Option<Vec<FeeRecipient>>,
+    extra_reward_denoms: Option<Vec<ExtraRewardDenom>>,
+    oracle_address: Option<String>,
+    oracle_targets: Option<Vec<OracleTarget>>,
+) -> ExecuteMsg {
+    ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients,
+        extra_reward_denoms,
+        oracle_targets,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    }
+}
+
+// Proposes `msg` twice, vetoes the first proposal and executes the second one after the timelock
+fn assert_timelocked(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    msg: ExecuteMsg,
+    applied: impl Fn(&Config) -> bool,
+) {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(!applied(&CONFIG.load(&deps.storage).unwrap()));
+    let msg_veto = ExecuteMsg::VetoConfigChange { id: 1 };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO2, &[]), msg_veto).unwrap();
+    assert!(pending_changes(deps).is_empty());
+
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+    let msg = ExecuteMsg::ExecuteConfigChange { id: 2 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        msg.clone(),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::ConfigChangeTimelocked { id: 2, .. })
+    ));
+    assert!(!applied(&CONFIG.load(&deps.storage).unwrap()));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_CHANGE_DELAY);
+    execute(deps.as_mut(), env, mock_info(OSMO3, &[]), msg).unwrap();
+    assert!(applied(&CONFIG.load(&deps.storage).unwrap()));
+}
+
+#[test]
+fn timelocked_fee_recipients() {
+    let mut deps = init();
+    let recipients = vec![FeeRecipient {
+        address: Addr::unchecked(OSMO4),
+        weight: Decimal::one(),
+    }];
+    let msg = update_sensitive_config(Some(recipients.clone()), None, None, None);
+
+    // fee recipients are changed by fee managers, not config managers
+    let grant = ExecuteMsg::GrantRole {
+        role: Role::ConfigManager,
+        address: OSMO1.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), grant).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        msg.clone(),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MissingRole { role, .. }) if role == Role::FeeManager.as_str()
+    ));
+
+    assert_timelocked(&mut deps, msg, |config| {
+        config.fee_recipients.as_ref() == Some(&recipients)
+    });
+}
+
+#[test]
+fn timelocked_extra_reward_denoms() {
+    let mut deps = init();
+    let denoms = vec![ExtraRewardDenom {
+        denom: "uosmo".to_string(),
+        destination: ExtraRewardDestination::Contract {
+            address: Addr::unchecked(OSMO4),
+            msg: to_json_binary(&"swap").unwrap(),
+        },
+    }];
+    let msg = update_sensitive_config(None, Some(denoms.clone()), None, None);
+
+    // their destinations are changed by fee managers, not config managers
+    let grant = ExecuteMsg::GrantRole {
+        role: Role::ConfigManager,
+        address: OSMO1.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), grant).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        msg.clone(),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MissingRole { role, .. }) if role == Role::FeeManager.as_str()
+    ));
+
+    assert_timelocked(&mut deps, msg, |config| {
+        config.extra_reward_denoms.as_ref() == Some(&denoms)
+    });
+}
+
+#[test]
+fn timelocked_oracle_address() {
+    let mut deps = init();
+    let msg = update_sensitive_config(None, None, Some(OSMO1.to_string()), None);
+    assert_timelocked(&mut deps, msg, |config| {
+        config.oracle_address == Some(Addr::unchecked(OSMO1))
+    });
+
+    // removing the oracle is timelocked as well
+    let mut deps = init();
+    let msg = update_sensitive_config(None, None, Some("".to_string()), None);
+    assert_timelocked(&mut deps, msg, |config| config.oracle_address.is_none());
+}
+
+#[test]
+fn timelocked_oracle_targets() {
+    let mut deps = init();
+    let targets = vec![OracleTarget {
+        address: Addr::unchecked(OSMO1),
+        format: OracleFormat::Relay {
+            symbol: "STTIA".to_string(),
+        },
+        enabled: true,
+    }];
+    let msg = update_sensitive_config(None, None, None, Some(targets.clone()));
+    assert_timelocked(&mut deps, msg, |config| {
+        config.oracle_targets.as_ref() == Some(&targets)
+    });
+}
@@ -0,0 +1,108 @@
+use crate::contract::execute;
+use crate::helpers::derive_intermediate_sender;
+use crate::msg::ExecuteMsg;
+use crate::state::{CONFIG, STATE};
+use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO3};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, Event, Response, Uint128};
+use milky_way::events::EVENT_SCHEMA_VERSION;
+
+fn event<'a>(res: &'a Response, ty: &str) -> &'a Event {
+    res.events
+        .iter()
+        .find(|e| e.ty == ty)
+        .unwrap_or_else(|| panic!("missing {ty} event"))
+}
+
+fn attribute(event: &Event, key: &str) -> String {
+    event
+        .attributes
+        .iter()
+        .find(|a| a.key == key)
+        .map(|a| a.value.clone())
+        .unwrap_or_else(|| panic!("missing {key} attribute"))
+}
+
+#[test]
+fn stake_event() {
+    let mut deps = init();
+    let msg = ExecuteMsg::LiquidStake {
+        mint_to: None,
+        expected_mint_amount: None,
+        forward: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        msg,
+    )
+    .unwrap();
+
+    let event = event(&res, "milkyway-stake");
+    assert_eq!(attribute(event, "schema_version"), EVENT_SCHEMA_VERSION);
+    assert_eq!(attribute(event, "sender"), OSMO3);
+    assert_eq!(attribute(event, "recipient"), OSMO3);
+    assert_eq!(attribute(event, "amount"), "1000");
+    assert_eq!(attribute(event, "mint_amount"), "1000");
+    assert!(!event.attributes.iter().any(|a| a.key == "forward_channel"));
+}
+
+#[test]
+fn rewards_event_and_single_action() {
+    let mut deps = init();
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let sender = derive_intermediate_sender(
+        &config.ibc_channel_id,
+        config
+            .multisig_address_config
+            .reward_collector_address
+            .as_ref(),
+        "osmo",
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(100, NATIVE_TOKEN)),
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+
+    let actions: Vec<_> = res
+        .attributes
+        .iter()
+        .filter(|a| a.key == "action")
+        .collect();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].value, "receive_rewards");
+
+    let event = event(&res, "milkyway-rewards");
+    assert_eq!(attribute(event, "amount"), "100");
+    assert_eq!(attribute(event, "fee"), "10");
+    assert_eq!(attribute(event, "amount_after_fees"), "90");
+}
+
+#[test]
+fn ownership_event() {
+    let mut deps = init();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::TransferOwnership {
+            new_owner: "new_owner".to_string(),
+        },
+    )
+    .unwrap();
+
+    let event = event(&res, "milkyway-ownership");
+    assert_eq!(attribute(event, "action"), "transfer_ownership");
+    assert_eq!(attribute(event, "owner"), OSMO3);
+    assert_eq!(attribute(event, "pending_owner"), "new_owner");
+}
@@ -0,0 +1,560 @@
+use crate::contract::{
+    execute, migrate, query, CONTRACT_NAME, CONTRACT_VERSION, FEE_CHANGE_DELAY,
+    MAX_DAO_TREASURY_FEE,
+};
+use crate::error::ContractError;
+use crate::helpers::derive_intermediate_sender;
+use crate::migrations::v0_5_0::v0_4_20_state;
+use crate::msg::{
+    ConfigResponse, DistributedFees, ExecuteMsg, FeeDistributionResponse, MigrateMsg, QueryMsg,
+    SimulateLiquidStakeResponse, SimulateLiquidUnstakeResponse, WithdrawalResponse,
+};
+use crate::state::{
+    CappedFee, FeeRecipient, PendingProtocolFeeConfig, ProtocolFeeConfig, RewardFeeMode, BATCHES,
+    BATCH_REDEMPTION_FEES, CONFIG, OWNERSHIP, PENDING_PROTOCOL_FEE_CONFIG, STATE,
+};
+use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, Addr, BankMsg, CosmosMsg, Decimal, OwnedDeps, Response, Uint128,
+};
+use milky_way::staking::BatchStatus;
+use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+
+fn update_fees(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    deposit_fee: Option<CappedFee>,
+    redemption_fee: Option<CappedFee>,
+) -> Result<Response, ContractError> {
+    update_fee_config(deps, deposit_fee, redemption_fee, None)
+}
+
+fn update_fee_config(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    deposit_fee: Option<CappedFee>,
+    redemption_fee: Option<CappedFee>,
+    fee_recipients: Option<Vec<FeeRecipient>>,
+) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: Some(ProtocolFeeConfig {
+            dao_treasury_fee: Decimal::percent(10),
+            deposit_fee,
+            redemption_fee,
+            reward_fee_mode: RewardFeeMode::Native,
+        }),
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    // stage the fees and recipients early enough for them to be in effect at the current block
+    let mut env = mock_env();
+    env.block.time = env.block.time.minus_seconds(FEE_CHANGE_DELAY);
+    let res = execute(deps.as_mut(), env, mock_info(OSMO3, &[]), msg)?;
+    if let Some(id) = res.attributes.iter().find(|a| a.key == "config_change_id") {
+        let msg = ExecuteMsg::ExecuteConfigChange {
+            id: id.value.parse().unwrap(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg)?;
+    }
+    Ok(res)
+}
+
+fn fee_msg(amount: u128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: OSMO1.to_string(),
+        amount: coins(amount, NATIVE_TOKEN),
+    })
+}
+
+#[test]
+fn invalid_fee() {
+    let mut deps = init();
+
+    let res = update_fees(
+        &mut deps,
+        None,
+        Some(CappedFee {
+            bps: 10_001,
+            max_fee: None,
+        }),
+    );
+    assert!(matches!(res, Err(ContractError::InvalidFee {})));
+}
+
+#[test]
+fn deposit_fee() {
+    let mut deps = init();
+
+    // 1% capped at 500
+    update_fees(
+        &mut deps,
+        Some(CappedFee {
+            bps: 100,
+            max_fee: Some(Uint128::from(500u128)),
+        }),
+        None,
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateLiquidStake {
+            amount: Uint128::from(100_000u128),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateLiquidStakeResponse = from_json(res).unwrap();
+    assert_eq!(simulation.deposit_fee, Uint128::from(500u128));
+    assert_eq!(simulation.mint_amount, Uint128::from(99_500u128));
+
+    let msg = ExecuteMsg::LiquidStake {
+        mint_to: None,
+        expected_mint_amount: Some(simulation.mint_amount),
+        forward: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &coins(100_000, NATIVE_TOKEN)),
+        msg,
+    )
+    .unwrap();
+
+    // fees are sent to the treasury in the test config
+    assert!(res.messages.iter().any(|m| m.msg == fee_msg(500)));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "deposit_fee" && a.value == "500"));
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_native_token, Uint128::from(99_500u128));
+    assert_eq!(state.total_liquid_stake_token, Uint128::from(99_500u128));
+    assert_eq!(state.total_fees, Uint128::zero());
+}
+
+#[test]
+fn redemption_fee() {
+    let mut deps = init();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.send_fees_to_treasury = false;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(200_000u128);
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    // 0.5%
+    let redemption_fee = CappedFee {
+        bps: 50,
+        max_fee: None,
+    };
+    update_fees(&mut deps, None, Some(redemption_fee.clone())).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateLiquidUnstake {
+            amount: Uint128::from(10_000u128),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateLiquidUnstakeResponse = from_json(res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulateLiquidUnstakeResponse {
+            expected_native_unstaked: Uint128::from(20_000u128),
+            redemption_fee: Uint128::from(100u128),
+            expected_withdrawal: Uint128::from(19_900u128),
+        }
+    );
+
+    let info = mock_info("bob", &coins(10_000, "factory/cosmos2contract/stTIA"));
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+    assert_eq!(
+        BATCH_REDEMPTION_FEES.load(&deps.storage, 1).unwrap(),
+        redemption_fee
+    );
+
+    // the fee of the batch doesn't change with the config
+    update_fees(
+        &mut deps,
+        None,
+        Some(CappedFee {
+            bps: 1_000,
+            max_fee: None,
+        }),
+    )
+    .unwrap();
+
+    let mut batch = BATCHES.load(&deps.storage, 1).unwrap();
+    batch.received_native_unstaked = Some(Uint128::from(20_000u128));
+    batch.update_status(BatchStatus::Received, None);
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Withdrawal {
+            batch_id: 1,
+            user: "bob".to_string(),
+        },
+    )
+    .unwrap();
+    let withdrawal: WithdrawalResponse = from_json(res).unwrap();
+    assert_eq!(
+        withdrawal,
+        WithdrawalResponse {
+            amount: Uint128::from(19_900u128),
+            redemption_fee: Uint128::from(100u128),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::Withdraw { batch_id: 1 },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "amount" && a.value == "19900"));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "redemption_fee" && a.value == "100"));
+    // the fee is dropped with the archived batch
+    assert!(!BATCH_REDEMPTION_FEES.has(&deps.storage, 1));
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_fees, Uint128::from(100u128));
+}
+
+fn recipient(address: &str, percent: u64) -> FeeRecipient {
+    FeeRecipient {
+        address: Addr::unchecked(address),
+        weight: Decimal::percent(percent),
+    }
+}
+
+#[test]
+fn invalid_fee_recipients() {
+    let mut deps = init();
+
+    for recipients in [
+        vec![recipient(OSMO1, 60), recipient(OSMO2, 30)],
+        vec![recipient(OSMO1, 50), recipient(OSMO1, 50)],
+        vec![recipient(OSMO1, 100), recipient(OSMO2, 0)],
+        vec![recipient("bob", 100)],
+    ] {
+        let res = update_fee_config(&mut deps, None, None, Some(recipients));
+        assert!(matches!(res, Err(ContractError::InvalidFeeRecipients {})));
+    }
+}
+
+#[test]
+fn fee_withdraw_is_split() {
+    let mut deps = init();
+    update_fee_config(
+        &mut deps,
+        None,
+        None,
+        Some(vec![recipient(OSMO1, 60), recipient(OSMO2, 40)]),
+    )
+    .unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_fees = Uint128::from(1001u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::FeeWithdraw {
+            amount: Uint128::from(1001u128),
+        },
+    )
+    .unwrap();
+
+    // the rounding dust goes to the first recipient
+    let send = |to_address: &str, amount: &str| -> CosmosMsg {
+        MsgSend {
+            from_address: env.contract.address.to_string(),
+            to_address: to_address.to_string(),
+            amount: vec![Coin {
+                denom: NATIVE_TOKEN.to_string(),
+                amount: amount.to_string(),
+            }],
+        }
+        .into()
+    };
+    let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(messages, vec![send(OSMO1, "601"), send(OSMO2, "400")]);
+
+    // an empty list sends the fees to the treasury again
+    update_fee_config(&mut deps, None, None, Some(vec![])).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::FeeDistribution {}).unwrap();
+    let distribution: FeeDistributionResponse = from_json(res).unwrap();
+    assert_eq!(distribution.recipients, vec![recipient(OSMO1, 100)]);
+    assert_eq!(
+        distribution.distributed,
+        vec![
+            DistributedFees {
+                address: Addr::unchecked(OSMO1),
+                amount: Uint128::from(601u128),
+            },
+            DistributedFees {
+                address: Addr::unchecked(OSMO2),
+                amount: Uint128::from(400u128),
+            },
+        ]
+    );
+}
+
+#[test]
+fn reward_fees_are_split() {
+    let mut deps = init();
+    update_fee_config(
+        &mut deps,
+        None,
+        None,
+        Some(vec![recipient(OSMO1, 60), recipient(OSMO2, 40)]),
+    )
+    .unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let sender = derive_intermediate_sender(
+        &config.ibc_channel_id,
+        config
+            .multisig_address_config
+            .reward_collector_address
+            .as_ref(),
+        "osmo",
+    )
+    .unwrap();
+
+    // 10% fee of 100
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(100, NATIVE_TOKEN)),
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+
+    assert!(res.messages.iter().any(|m| m.msg == fee_msg(6)));
+    assert!(res.messages.iter().any(|m| m.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: OSMO2.to_string(),
+            amount: coins(4, NATIVE_TOKEN),
+        })));
+}
+
+#[test]
+fn dao_treasury_fee_above_max() {
+    let mut deps = init();
+    let mut protocol_fee_config = CONFIG.load(&deps.storage).unwrap().protocol_fee_config;
+    protocol_fee_config.dao_treasury_fee = Decimal::percent(21);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: Some(protocol_fee_config),
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+        fee_recipients: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::DaoTreasuryFeeTooHigh { fee, max })
+            if fee == Decimal::percent(21) && max == MAX_DAO_TREASURY_FEE
+    ));
+}
+
+#[test]
+fn fee_change_is_staged() {
+    let mut deps = init();
+    let mut protocol_fee_config = CONFIG.load(&deps.storage).unwrap().protocol_fee_config;
+    protocol_fee_config.deposit_fee = Some(CappedFee {
+        bps: 100,
+        max_fee: None,
+    });
+
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: Some(protocol_fee_config.clone()),
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    let env = mock_env();
+    let effective_at = env.block.time.seconds() + FEE_CHANGE_DELAY;
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OSMO3, &[]), msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "fee_effective_at" && a.value == effective_at.to_string()));
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_json(res).unwrap();
+    assert_eq!(config.protocol_fee_config.deposit_fee, None);
+    assert_eq!(
+        config.pending_protocol_fee_config,
+        Some(PendingProtocolFeeConfig {
+            protocol_fee_config: protocol_fee_config.clone(),
+            effective_at,
+        })
+    );
+
+    let msg = ExecuteMsg::LiquidStake {
+        mint_to: None,
+        expected_mint_amount: None,
+        forward: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &coins(100_000, NATIVE_TOKEN)),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(!res.attributes.iter().any(|a| a.key == "deposit_fee"));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(FEE_CHANGE_DELAY);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &coins(100_000, NATIVE_TOKEN)),
+        msg,
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "deposit_fee" && a.value == "1000"));
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.protocol_fee_config, protocol_fee_config);
+    assert!(PENDING_PROTOCOL_FEE_CONFIG
+        .may_load(&deps.storage)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn migrate_dao_treasury_fee() {
+    let mut deps = init();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let legacy_config = v0_4_20_state::Config {
+        native_token_denom: config.native_token_denom,
+        liquid_stake_token_denom: config.liquid_stake_token_denom,
+        treasury_address: config.treasury_address,
+        monitors: config.monitors,
+        validators: config.validators,
+        batch_period: config.batch_period,
+        unbonding_period: config.unbonding_period,
+        protocol_fee_config: v0_4_20_state::ProtocolFeeConfig {
+            dao_treasury_fee: Uint128::from(5_000u128),
+        },
+        multisig_address_config: v0_4_20_state::MultisigAddressConfig {
+            staker_address: config.multisig_address_config.staker_address,
+            reward_collector_address: config.multisig_address_config.reward_collector_address,
+        },
+        minimum_liquid_stake_amount: config.minimum_liquid_stake_amount,
+        ibc_channel_id: config.ibc_channel_id,
+        stopped: config.stopped,
+        oracle_address: config.oracle_address,
+        send_fees_to_treasury: config.send_fees_to_treasury,
+    };
+    v0_4_20_state::CONFIG
+        .save(&mut deps.storage, &legacy_config)
+        .unwrap();
+    // a pending transfer moves to the ownership component
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.pending_owner = Some(Addr::unchecked(OSMO1));
+    state.owner_transfer_min_time = Some(mock_env().block.time);
+    STATE.save(&mut deps.storage, &state).unwrap();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.4.20").unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::V0_4_20ToV0_5_0 {}).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(
+        config.protocol_fee_config.dao_treasury_fee,
+        Decimal::percent(5)
+    );
+    let pending = OWNERSHIP.pending(&deps.storage).unwrap().unwrap();
+    assert_eq!(pending.owner, Addr::unchecked(OSMO1));
+    assert_eq!(pending.min_time, mock_env().block.time);
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.pending_owner, None);
+    assert_eq!(state.owner_transfer_min_time, None);
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+}
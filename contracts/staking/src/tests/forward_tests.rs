@@ -0,0 +1,247 @@
+use crate::contract::{execute, query, reply, sudo, IBC_TIMEOUT};
+use crate::error::ContractError;
+use crate::helpers::derive_intermediate_sender;
+use crate::msg::{ExecuteMsg, IBCLifecycleComplete, QueryMsg, SudoMsg};
+use crate::state::{IbcForward, CLAIMABLE_FUNDS, INFLIGHT_FORWARDS};
+use crate::tests::test_helper::{init, CELESTIA1, CHANNEL_ID, NATIVE_TOKEN, OSMO2, OSMO3};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, BankMsg, Binary, Coin, CosmosMsg, OwnedDeps, Reply, ReplyOn, SubMsg,
+    SubMsgResponse, SubMsgResult, Uint128,
+};
+use osmosis_std::types::ibc::applications::transfer::v1::{MsgTransfer, MsgTransferResponse};
+use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgMint;
+
+static LST_DENOM: &str = "factory/cosmos2contract/stTIA";
+static FORWARD_CHANNEL: &str = "channel-5";
+
+fn forward_stake(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    mint_to: Option<String>,
+    sequence: u64,
+) {
+    let intermediate_sender = derive_intermediate_sender(CHANNEL_ID, CELESTIA1, "osmo").unwrap();
+    let info = mock_info(&intermediate_sender, &coins(1000, NATIVE_TOKEN));
+    let msg = ExecuteMsg::LiquidStake {
+        mint_to,
+        expected_mint_amount: None,
+        forward: Some(IbcForward {
+            channel_id: FORWARD_CHANNEL.to_string(),
+            receiver: CELESTIA1.to_string(),
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: Some(Binary::from(MsgTransferResponse { sequence })),
+                events: vec![],
+            }),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn liquid_stake_with_forward() {
+    let mut deps = init();
+    let env = mock_env();
+    let intermediate_sender = derive_intermediate_sender(CHANNEL_ID, CELESTIA1, "osmo").unwrap();
+
+    let info = mock_info(&intermediate_sender, &coins(1000, NATIVE_TOKEN));
+    let msg = ExecuteMsg::LiquidStake {
+        mint_to: None,
+        expected_mint_amount: None,
+        forward: Some(IbcForward {
+            channel_id: FORWARD_CHANNEL.to_string(),
+            receiver: CELESTIA1.to_string(),
+        }),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(res.messages.len(), 4); // mint, rate update, stake transfer, forward transfer
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::from(MsgMint {
+            sender: env.contract.address.to_string(),
+            amount: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
+                denom: LST_DENOM.to_string(),
+                amount: "1000".to_string(),
+            }),
+            mint_to_address: env.contract.address.to_string(),
+        })
+    );
+    assert_eq!(
+        res.messages[3],
+        SubMsg {
+            id: 1,
+            msg: CosmosMsg::from(MsgTransfer {
+                source_channel: FORWARD_CHANNEL.to_string(),
+                source_port: "transfer".to_string(),
+                sender: env.contract.address.to_string(),
+                receiver: CELESTIA1.to_string(),
+                token: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
+                    denom: LST_DENOM.to_string(),
+                    amount: "1000".to_string(),
+                }),
+                timeout_height: None,
+                timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
+                memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
+            }),
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        }
+    );
+
+    // the packet is tracked once the reply is received
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: Some(Binary::from(MsgTransferResponse { sequence: 7 })),
+                events: vec![],
+            }),
+        },
+    )
+    .unwrap();
+    let forward = INFLIGHT_FORWARDS
+        .load(&deps.storage, (FORWARD_CHANNEL.to_string(), 7))
+        .unwrap();
+    assert_eq!(forward.amount, Uint128::from(1000u128));
+    assert_eq!(forward.fallback_address.as_str(), OSMO3);
+
+    // successful ack stops tracking without crediting anything
+    sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCAck {
+            channel: FORWARD_CHANNEL.to_string(),
+            sequence: 7,
+            ack: "".to_string(),
+            success: true,
+        }),
+    )
+    .unwrap();
+    assert!(!INFLIGHT_FORWARDS.has(&deps.storage, (FORWARD_CHANNEL.to_string(), 7)));
+    assert!(!CLAIMABLE_FUNDS.has(&deps.storage, (OSMO3.to_string(), LST_DENOM.to_string())));
+}
+
+#[test]
+fn failed_forward_is_claimable() {
+    let mut deps = init();
+    forward_stake(&mut deps, None, 7);
+
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCAck {
+            channel: FORWARD_CHANNEL.to_string(),
+            sequence: 7,
+            ack: "".to_string(),
+            success: false,
+        }),
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ClaimableFunds {
+            address: OSMO3.to_string(),
+        },
+    )
+    .unwrap();
+    let claimable: Vec<Coin> = from_json(res).unwrap();
+    assert_eq!(claimable, coins(1000, LST_DENOM));
+
+    // only the fallback address can claim
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::ClaimFunds {},
+    );
+    assert!(matches!(res, Err(ContractError::NothingToClaim {})));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::ClaimFunds {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: OSMO3.to_string(),
+            amount: coins(1000, LST_DENOM),
+        })
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::ClaimFunds {},
+    );
+    assert!(matches!(res, Err(ContractError::NothingToClaim {})));
+}
+
+#[test]
+fn timed_out_forward_is_claimable_by_mint_to() {
+    let mut deps = init();
+    forward_stake(&mut deps, Some(OSMO2.to_string()), 3);
+
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+            channel: FORWARD_CHANNEL.to_string(),
+            sequence: 3,
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        CLAIMABLE_FUNDS
+            .load(&deps.storage, (OSMO2.to_string(), LST_DENOM.to_string()))
+            .unwrap(),
+        Uint128::from(1000u128)
+    );
+    assert!(!CLAIMABLE_FUNDS.has(&deps.storage, (OSMO3.to_string(), LST_DENOM.to_string())));
+}
+
+#[test]
+fn invalid_forward() {
+    let mut deps = init();
+    let intermediate_sender = derive_intermediate_sender(CHANNEL_ID, CELESTIA1, "osmo").unwrap();
+
+    for forward in [
+        IbcForward {
+            channel_id: "connection-1".to_string(),
+            receiver: CELESTIA1.to_string(),
+        },
+        IbcForward {
+            channel_id: FORWARD_CHANNEL.to_string(),
+            receiver: "celestia".to_string(),
+        },
+    ] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&intermediate_sender, &coins(1000, NATIVE_TOKEN)),
+            ExecuteMsg::LiquidStake {
+                mint_to: None,
+                expected_mint_amount: None,
+                forward: Some(forward),
+            },
+        );
+        assert!(matches!(res, Err(ContractError::InvalidForward {})));
+    }
+}
@@ -21,6 +21,7 @@ fn success_ibc_queue() {
     let msg = ExecuteMsg::LiquidStake {
         mint_to: None,
         expected_mint_amount: None,
+        forward: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
 
@@ -165,6 +166,7 @@ fn fail_ibc_queue() {
     let msg = ExecuteMsg::LiquidStake {
         mint_to: None,
         expected_mint_amount: None,
+        forward: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
@@ -245,6 +247,7 @@ fn timeout_ibc_queue() {
     let msg = ExecuteMsg::LiquidStake {
         mint_to: None,
         expected_mint_amount: None,
+        forward: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
@@ -1,13 +1,15 @@
 #[cfg(test)]
 mod tests {
     use crate::msg::InstantiateMsg;
-    use crate::state::{Config, MultisigAddressConfig, ProtocolFeeConfig, BATCHES, CONFIG};
+    use crate::state::{
+        Config, MultisigAddressConfig, ProtocolFeeConfig, RewardFeeMode, BATCHES, CONFIG,
+    };
     use crate::tests::test_helper::{
         init, CELESTIA1, CELESTIA2, CHANNEL_ID, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3,
     };
 
     use cosmwasm_std::testing::{mock_env, mock_info};
-    use cosmwasm_std::{Addr, Order, Uint128};
+    use cosmwasm_std::{Addr, Decimal, Order, Uint128};
     use milky_way::staking::BatchStatus;
 
     #[test]
@@ -38,7 +40,10 @@ mod tests {
                 batch_period: 86400,
                 unbonding_period: 1209600,
                 protocol_fee_config: ProtocolFeeConfig {
-                    dao_treasury_fee: Uint128::from(10u128),
+                    dao_treasury_fee: Decimal::percent(1),
+                    deposit_fee: None,
+                    redemption_fee: None,
+                    reward_fee_mode: RewardFeeMode::Native,
                 },
                 multisig_address_config: MultisigAddressConfig {
                     staker_address: Addr::unchecked(CELESTIA1),
@@ -144,7 +149,10 @@ mod tests {
             batch_period: Some(86400),
             unbonding_period: Some(1209600),
             protocol_fee_config: Some(ProtocolFeeConfig {
-                dao_treasury_fee: Uint128::from(10u128),
+                dao_treasury_fee: Decimal::percent(1),
+                deposit_fee: None,
+                redemption_fee: None,
+                reward_fee_mode: RewardFeeMode::Native,
             }),
             multisig_address_config: Some(MultisigAddressConfig {
                 staker_address: Addr::unchecked(CELESTIA1),
@@ -159,6 +167,15 @@ mod tests {
             treasury_address: Some(OSMO3.to_string()),
             oracle_address: None,
             send_fees_to_treasury: None,
+            keeper_reward: None,
+            batch_trigger: None,
+            unclaimed_sweep: None,
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            oracle_targets: None,
+            oracle_throttle: None,
+            monitor_quorum: None,
+            clear: None,
         };
 
         let res = crate::contract::execute(
@@ -169,24 +186,22 @@ mod tests {
         );
         assert!(res.is_ok());
         let config: Config = CONFIG.load(&deps.storage).unwrap();
-        assert!(config.clone().monitors.unwrap().len() == 1);
-        assert!(
-            config
-                .clone()
-                .monitors
-                .unwrap()
-                .first()
-                .unwrap()
-                .to_string()
-                == *OSMO3
-        );
-        assert!(config.treasury_address == OSMO3);
+        // the monitors and treasury are only changed once the proposed change is executed
+        assert!(config.monitors.unwrap().len() == 2);
+        assert!(config.treasury_address == OSMO1);
+        let pending_change = crate::state::PENDING_CONFIG_CHANGES
+            .load(&deps.storage, 1)
+            .unwrap();
+        assert!(pending_change.change.monitors == Some(vec![Addr::unchecked(OSMO3)]));
 
         let config_update_msg = crate::msg::ExecuteMsg::UpdateConfig {
             batch_period: Some(86400),
             unbonding_period: Some(1209600),
             protocol_fee_config: Some(ProtocolFeeConfig {
-                dao_treasury_fee: Uint128::from(10u128),
+                dao_treasury_fee: Decimal::percent(1),
+                deposit_fee: None,
+                redemption_fee: None,
+                reward_fee_mode: RewardFeeMode::Native,
             }),
             multisig_address_config: Some(MultisigAddressConfig {
                 staker_address: Addr::unchecked(CELESTIA1),
@@ -199,6 +214,15 @@ mod tests {
             treasury_address: None,
             oracle_address: None,
             send_fees_to_treasury: None,
+            keeper_reward: None,
+            batch_trigger: None,
+            unclaimed_sweep: None,
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            oracle_targets: None,
+            oracle_throttle: None,
+            monitor_quorum: None,
+            clear: None,
         };
         let res = crate::contract::execute(
             deps.as_mut(),
@@ -212,7 +236,10 @@ mod tests {
             batch_period: Some(86400),
             unbonding_period: Some(1209600),
             protocol_fee_config: Some(ProtocolFeeConfig {
-                dao_treasury_fee: Uint128::from(10u128),
+                dao_treasury_fee: Decimal::percent(1),
+                deposit_fee: None,
+                redemption_fee: None,
+                reward_fee_mode: RewardFeeMode::Native,
             }),
             multisig_address_config: Some(MultisigAddressConfig {
                 staker_address: Addr::unchecked(CELESTIA1),
@@ -225,6 +252,15 @@ mod tests {
             treasury_address: None,
             oracle_address: None,
             send_fees_to_treasury: None,
+            keeper_reward: None,
+            batch_trigger: None,
+            unclaimed_sweep: None,
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            oracle_targets: None,
+            oracle_throttle: None,
+            monitor_quorum: None,
+            clear: None,
         };
         let res = crate::contract::execute(
             deps.as_mut(),
@@ -238,7 +274,10 @@ mod tests {
             batch_period: Some(86400),
             unbonding_period: Some(1209600),
             protocol_fee_config: Some(ProtocolFeeConfig {
-                dao_treasury_fee: Uint128::from(10u128),
+                dao_treasury_fee: Decimal::percent(1),
+                deposit_fee: None,
+                redemption_fee: None,
+                reward_fee_mode: RewardFeeMode::Native,
             }),
             multisig_address_config: Some(MultisigAddressConfig {
                 staker_address: Addr::unchecked(CELESTIA1),
@@ -251,6 +290,15 @@ mod tests {
             treasury_address: None,
             oracle_address: None,
             send_fees_to_treasury: None,
+            keeper_reward: None,
+            batch_trigger: None,
+            unclaimed_sweep: None,
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            oracle_targets: None,
+            oracle_throttle: None,
+            monitor_quorum: None,
+            clear: None,
         };
         let res = crate::contract::execute(
             deps.as_mut(),
@@ -278,6 +326,15 @@ mod tests {
             treasury_address: None,
             oracle_address: None,
             send_fees_to_treasury: Some(!config.send_fees_to_treasury),
+            keeper_reward: None,
+            batch_trigger: None,
+            unclaimed_sweep: None,
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            oracle_targets: None,
+            oracle_throttle: None,
+            monitor_quorum: None,
+            clear: None,
         };
         crate::contract::execute(
             deps.as_mut(),
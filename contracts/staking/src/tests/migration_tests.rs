@@ -0,0 +1,364 @@
+use std::collections::BTreeMap;
+
+use crate::contract::{execute, migrate, query, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ContractError;
+use crate::migrations::dry_run::{self, MigrationDryRun};
+use crate::migrations::v0_4_20::v0_4_18_state;
+use crate::migrations::v0_5_0::v0_4_20_state;
+use crate::migrations::v0_6_0::v0_5_0_state;
+use crate::msg::{
+    BatchResponse, BatchesResponse, ExecuteMsg, MigrateMsg, QueryMsg, WithdrawalResponse,
+};
+use crate::state::{
+    new_unstake_request, unstake_requests, UnstakeRequest, BATCHES, CONFIG, LEGACY_BATCHES_BEFORE,
+    PENDING_BATCH_ID,
+};
+use crate::tests::test_helper::init;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{from_json, Addr, Decimal, OwnedDeps, Uint128};
+use milky_way::staking::BatchStatus;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+fn legacy_fee_config() -> v0_4_20_state::ProtocolFeeConfig {
+    v0_4_20_state::ProtocolFeeConfig {
+        dao_treasury_fee: Uint128::from(5_000u128),
+    }
+}
+
+fn save_v0_4_18_state(deps: &mut Deps) {
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let legacy_config = v0_4_18_state::Config {
+        native_token_denom: config.native_token_denom,
+        liquid_stake_token_denom: config.liquid_stake_token_denom,
+        treasury_address: config.treasury_address,
+        operators: None,
+        monitors: config.monitors,
+        validators: config.validators,
+        batch_period: config.batch_period,
+        unbonding_period: config.unbonding_period,
+        protocol_fee_config: legacy_fee_config(),
+        multisig_address_config: v0_4_20_state::MultisigAddressConfig {
+            staker_address: config.multisig_address_config.staker_address,
+            reward_collector_address: config.multisig_address_config.reward_collector_address,
+        },
+        minimum_liquid_stake_amount: config.minimum_liquid_stake_amount,
+        ibc_channel_id: config.ibc_channel_id,
+        stopped: config.stopped,
+        oracle_contract_address: None,
+        oracle_contract_address_v2: None,
+        oracle_address: config.oracle_address,
+    };
+    v0_4_18_state::CONFIG
+        .save(&mut deps.storage, &legacy_config)
+        .unwrap();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.4.18").unwrap();
+}
+
+fn save_v0_4_20_state(deps: &mut Deps) {
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let legacy_config = v0_4_20_state::Config {
+        native_token_denom: config.native_token_denom,
+        liquid_stake_token_denom: config.liquid_stake_token_denom,
+        treasury_address: config.treasury_address,
+        monitors: config.monitors,
+        validators: config.validators,
+        batch_period: config.batch_period,
+        unbonding_period: config.unbonding_period,
+        protocol_fee_config: legacy_fee_config(),
+        multisig_address_config: v0_4_20_state::MultisigAddressConfig {
+            staker_address: config.multisig_address_config.staker_address,
+            reward_collector_address: config.multisig_address_config.reward_collector_address,
+        },
+        minimum_liquid_stake_amount: config.minimum_liquid_stake_amount,
+        ibc_channel_id: config.ibc_channel_id,
+        stopped: config.stopped,
+        oracle_address: config.oracle_address,
+        send_fees_to_treasury: config.send_fees_to_treasury,
+    };
+    v0_4_20_state::CONFIG
+        .save(&mut deps.storage, &legacy_config)
+        .unwrap();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.4.20").unwrap();
+}
+
+fn dry_run(deps: &Deps, msg: MigrateMsg) -> MigrationDryRun {
+    dry_run::dry_run(deps.as_ref(), &mock_env(), &msg).unwrap()
+}
+
+#[test]
+fn migrate_through_every_step() {
+    let mut deps = init();
+    save_v0_4_18_state(&mut deps);
+
+    let msg = MigrateMsg::Migrate {
+        send_fees_to_treasury: Some(true),
+        batch_limit: None,
+    };
+    let res = migrate(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "steps" && a.value == "0.4.18->0.4.20,0.4.20->0.5.0,0.5.0->0.6.0"));
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(
+        config.protocol_fee_config.dao_treasury_fee,
+        Decimal::percent(5)
+    );
+    assert!(config.send_fees_to_treasury);
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+}
+
+#[test]
+fn migrate_errors() {
+    let mut deps = init();
+    save_v0_4_18_state(&mut deps);
+
+    // the fee destination didn't exist before 0.4.20
+    let msg = MigrateMsg::Migrate {
+        send_fees_to_treasury: None,
+        batch_limit: None,
+    };
+    let res = migrate(deps.as_mut(), mock_env(), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::MissingMigrationOption { option }) if option == "send_fees_to_treasury"
+    ));
+
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.4.10").unwrap();
+    let msg = MigrateMsg::Migrate {
+        send_fees_to_treasury: Some(true),
+        batch_limit: None,
+    };
+    let res = migrate(deps.as_mut(), mock_env(), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::NoMigrationPath { from, .. }) if from == "0.4.10"
+    ));
+}
+
+#[test]
+fn dry_run_migration() {
+    let mut deps = init();
+    save_v0_4_20_state(&mut deps);
+
+    let res = dry_run(&deps, MigrateMsg::V0_4_20ToV0_5_0 {});
+    assert_eq!(res.from_version, "0.4.20");
+    assert_eq!(res.to_version, CONTRACT_VERSION);
+    assert_eq!(
+        res.steps,
+        vec!["0.4.20->0.5.0".to_string(), "0.5.0->0.6.0".to_string()]
+    );
+    assert_eq!(res.error, None);
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "dao_treasury_fee" && a.value == "0.05"));
+
+    // nothing was committed
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, "0.4.20");
+    assert!(v0_4_20_state::CONFIG.load(&deps.storage).is_ok());
+
+    // the checks of the migrated state are reported
+    BATCHES.remove(&mut deps.storage, 1);
+    let res = dry_run(&deps, MigrateMsg::V0_4_20ToV0_5_0 {});
+    assert_eq!(
+        res.error,
+        Some("Migrated state is invalid: pending batch not found".to_string())
+    );
+}
+
+fn legacy_batch(id: u64, status: BatchStatus) -> v0_5_0_state::Batch {
+    v0_5_0_state::Batch {
+        id,
+        batch_total_liquid_stake: Uint128::new(100_000),
+        expected_native_unstaked: None,
+        received_native_unstaked: None,
+        liquid_unstake_requests: None,
+        unstake_requests_count: None,
+        withdrawn_requests_count: 0,
+        withdrawn_native_unstaked: Uint128::zero(),
+        swept_native_unstaked: Uint128::zero(),
+        received_at: None,
+        next_batch_action_time: None,
+        status,
+    }
+}
+
+fn legacy_request(user: &str, shares: u128, redeemed: bool) -> v0_5_0_state::LiquidUnstakeRequest {
+    v0_5_0_state::LiquidUnstakeRequest {
+        user: Addr::unchecked(user),
+        shares: Uint128::new(shares),
+        redeemed,
+    }
+}
+
+fn save_v0_5_0_batches(deps: &mut Deps) {
+    // received batch with embedded requests, one of them already redeemed
+    let mut batch = legacy_batch(1, BatchStatus::Received);
+    batch.received_native_unstaked = Some(Uint128::new(100_000));
+    batch.received_at = Some(100);
+    batch.liquid_unstake_requests = Some(BTreeMap::from([
+        ("alice".to_string(), legacy_request("alice", 60_000, true)),
+        ("bob".to_string(), legacy_request("bob", 40_000, false)),
+    ]));
+    v0_5_0_state::BATCHES
+        .save(&mut deps.storage, 1, &batch)
+        .unwrap();
+
+    // received batch with its requests in the index
+    let mut batch = legacy_batch(2, BatchStatus::Received);
+    batch.unstake_requests_count = Some(1);
+    batch.next_batch_action_time = Some(200);
+    v0_5_0_state::BATCHES
+        .save(&mut deps.storage, 2, &batch)
+        .unwrap();
+    new_unstake_request(
+        &mut deps.as_mut(),
+        "tom".to_string(),
+        2,
+        Uint128::new(100_000),
+    )
+    .unwrap();
+
+    let mut batch = legacy_batch(3, BatchStatus::Pending);
+    batch.unstake_requests_count = Some(0);
+    batch.next_batch_action_time = Some(300);
+    v0_5_0_state::BATCHES
+        .save(&mut deps.storage, 3, &batch)
+        .unwrap();
+    PENDING_BATCH_ID.save(&mut deps.storage, &3).unwrap();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.5.0").unwrap();
+}
+
+#[test]
+fn migrate_legacy_batches() {
+    let mut deps = init();
+    save_v0_5_0_batches(&mut deps);
+
+    let msg = MigrateMsg::Migrate {
+        send_fees_to_treasury: None,
+        batch_limit: Some(2),
+    };
+    let res = migrate(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "migrated_batches" && a.value == "2"));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "legacy_batches_remaining" && a.value == "1"));
+    assert_eq!(LEGACY_BATCHES_BEFORE.load(&deps.storage).unwrap(), 2);
+
+    // the pending batch is migrated first, older ones after it
+    let batch = BATCHES.load(&deps.storage, 3).unwrap();
+    assert_eq!(batch.next_batch_action_time, 300);
+    let batch = BATCHES.load(&deps.storage, 2).unwrap();
+    assert_eq!(batch.unstake_requests_count, 1);
+    assert_eq!(batch.next_batch_action_time, 200);
+    assert!(BATCHES.load(&deps.storage, 1).is_err());
+
+    let msg = ExecuteMsg::MigrateBatches { limit: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "migrated_batches" && a.value == "1"));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "legacy_batches_remaining" && a.value == "0"));
+    assert!(!LEGACY_BATCHES_BEFORE.exists(&deps.storage));
+
+    // the open request moved to the index, the redeemed one counts as withdrawn
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(batch.unstake_requests_count, 2);
+    assert_eq!(batch.withdrawn_requests_count, 1);
+    assert_eq!(batch.next_batch_action_time, 100);
+    let request = unstake_requests()
+        .load(&deps.storage, (1, "bob".to_string()))
+        .unwrap();
+    assert_eq!(request.amount, Uint128::new(40_000));
+    assert!(unstake_requests()
+        .may_load(&deps.storage, (1, "alice".to_string()))
+        .unwrap()
+        .is_none());
+
+    let msg = ExecuteMsg::MigrateBatches { limit: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg);
+    assert!(matches!(res, Err(ContractError::NoBatchMigration {})));
+}
+
+#[test]
+fn legacy_batches_during_migration() {
+    let mut deps = init();
+    save_v0_5_0_batches(&mut deps);
+    let msg = MigrateMsg::Migrate {
+        send_fees_to_treasury: None,
+        batch_limit: Some(1),
+    };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(LEGACY_BATCHES_BEFORE.load(&deps.storage).unwrap(), 3);
+
+    // batches still in the old layout are read as they will be migrated
+    let res: BatchResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Batch { id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.unstake_request_count, 2);
+    assert_eq!(res.next_batch_action_time.seconds(), 100);
+    let res: BatchResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Batch { id: 2 }).unwrap()).unwrap();
+    assert_eq!(res.unstake_request_count, 1);
+    let msg = QueryMsg::Batches {
+        start_after: None,
+        limit: None,
+        status: Some(BatchStatus::Received),
+    };
+    let res: BatchesResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    let ids: Vec<u64> = res.batches.iter().map(|batch| batch.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    let msg = QueryMsg::UnstakeRequests {
+        user: Addr::unchecked("bob"),
+    };
+    let res: Vec<UnstakeRequest> =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].batch_id, 1);
+    let msg = QueryMsg::Withdrawal {
+        batch_id: 1,
+        user: "bob".to_string(),
+    };
+    let res: WithdrawalResponse =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.amount, Uint128::new(40_000));
+
+    // users aren't held up, a withdrawal migrates its batch on its own
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::Withdraw { batch_id: 1 },
+    )
+    .unwrap();
+    assert!(BATCHES.load(&deps.storage, 1).is_err());
+    let res: BatchResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Batch { id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.status, "archived");
+    assert_eq!(res.unstake_request_count, 2);
+    assert_eq!(LEGACY_BATCHES_BEFORE.load(&deps.storage).unwrap(), 3);
+
+    let msg = ExecuteMsg::MigrateBatches { limit: None };
+    execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+    let msg = QueryMsg::Batches {
+        start_after: None,
+        limit: None,
+        status: None,
+    };
+    let res: BatchesResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    let ids: Vec<u64> = res.batches.iter().map(|batch| batch.id).collect();
+    assert_eq!(ids, vec![2, 3]);
+    assert!(!LEGACY_BATCHES_BEFORE.exists(&deps.storage));
+}
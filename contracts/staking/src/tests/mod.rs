@@ -1,10 +1,18 @@
+mod audit_tests;
 mod circuit_breaker_tests;
+mod config_change_tests;
+mod events_tests;
+mod fee_tests;
+mod forward_tests;
 mod helper_tests;
 mod ibc_transfer_tests;
 mod instantiate_tests;
+mod migration_tests;
+mod oracle_tests;
 mod ownership_tests;
 mod query_tests;
 mod reward_tests;
+mod role_tests;
 mod stake_tests;
 mod submit_batch_tests;
 mod test_helper;
@@ -0,0 +1,233 @@
+use crate::contract::{execute, query, CONFIG_CHANGE_DELAY};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::oracle::{OracleFormat, OracleTarget};
+use crate::state::{OracleThrottleConfig, PostedRates, CONFIG, IBC_WAITING_FOR_REPLY, STATE};
+use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3, OSMO4};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{coins, from_json, Addr, CosmosMsg, Decimal, Env, OwnedDeps, Response, Uint128};
+use osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContract;
+
+fn liquid_stake(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: Env) -> Response {
+    let msg = ExecuteMsg::LiquidStake {
+        mint_to: None,
+        expected_mint_amount: None,
+        forward: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        msg,
+    )
+    .unwrap();
+
+    // drop the pending transfer so the next one can be sent
+    IBC_WAITING_FOR_REPLY.remove(&mut deps.storage, res.messages.last().unwrap().id);
+    res
+}
+
+fn oracle_msg(contract: &str, msg: &str) -> CosmosMsg {
+    MsgExecuteContract {
+        sender: mock_env().contract.address.to_string(),
+        contract: contract.to_string(),
+        msg: msg.as_bytes().to_vec(),
+        funds: vec![],
+    }
+    .into()
+}
+
+fn update_oracles(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    oracle_address: Option<String>,
+    oracle_targets: Option<Vec<OracleTarget>>,
+) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg)?;
+
+    // the oracles are timelocked
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_CHANGE_DELAY);
+    let msg = ExecuteMsg::ExecuteConfigChange { id: 1 };
+    execute(deps.as_mut(), env, mock_info(OSMO3, &[]), msg)?;
+    Ok(res)
+}
+
+#[test]
+fn no_oracle() {
+    let mut deps = init();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.oracle_address = None;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    // mint and transfer only
+    let res = liquid_stake(&mut deps, mock_env());
+    assert_eq!(res.messages.len(), 2);
+}
+
+#[test]
+fn oracle_targets() {
+    let mut deps = init();
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(1000u128);
+    state.total_liquid_stake_token = Uint128::from(1000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.oracle_targets = Some(vec![
+        OracleTarget {
+            address: Addr::unchecked(OSMO1),
+            format: OracleFormat::Relay {
+                symbol: "STTIA".to_string(),
+            },
+            enabled: true,
+        },
+        OracleTarget {
+            address: Addr::unchecked(OSMO2),
+            format: OracleFormat::Template {
+                template: r#"{"set_rate":{"asset":"{denom}","rate":"{redemption_rate}"}}"#
+                    .to_string(),
+            },
+            enabled: false,
+        },
+    ]);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let res = liquid_stake(&mut deps, mock_env());
+    let time = mock_env().block.time.seconds();
+    assert_eq!(res.messages.len(), 4);
+    assert_eq!(
+        res.messages[1].msg,
+        oracle_msg(
+            OSMO4,
+            r#"{"post_rates":{"denom":"factory/cosmos2contract/stTIA","purchase_rate":"1","redemption_rate":"1"}}"#
+        )
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        oracle_msg(
+            OSMO1,
+            &format!(
+                r#"{{"relay":{{"symbols":["STTIA"],"rates":["1000000000"],"resolve_time":"{time}","request_id":"0"}}}}"#
+            )
+        )
+    );
+
+    // only the admin can enable targets
+    let msg = ExecuteMsg::SetOracleTargetEnabled {
+        address: OSMO2.to_string(),
+        enabled: true,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+
+    let res = liquid_stake(&mut deps, mock_env());
+    assert_eq!(res.messages.len(), 5);
+    assert_eq!(
+        res.messages[3].msg,
+        oracle_msg(
+            OSMO2,
+            r#"{"set_rate":{"asset":"factory/cosmos2contract/stTIA","rate":"1"}}"#
+        )
+    );
+
+    let msg = ExecuteMsg::SetOracleTargetEnabled {
+        address: OSMO4.to_string(),
+        enabled: false,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::OracleTargetNotFound { address }) if address == OSMO4
+    ));
+}
+
+#[test]
+fn update_oracle_config() {
+    let mut deps = init();
+
+    let invalid_target = OracleTarget {
+        address: Addr::unchecked(OSMO1),
+        format: OracleFormat::Template {
+            template: r#"{"set_rate":{"rate":{redemption_rate}"#.to_string(),
+        },
+        enabled: true,
+    };
+    let res = update_oracles(&mut deps, None, Some(vec![invalid_target]));
+    assert!(matches!(res, Err(ContractError::InvalidOracleTargets {})));
+
+    // an empty oracle address removes the oracle
+    update_oracles(&mut deps, Some("".to_string()), None).unwrap();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.oracle_address, None);
+}
+
+#[test]
+fn throttled_rates() {
+    let mut deps = init();
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(1000u128);
+    state.total_liquid_stake_token = Uint128::from(1000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.oracle_throttle = Some(OracleThrottleConfig {
+        threshold: Decimal::percent(1),
+        heartbeat: 3600,
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    // posted the first time
+    let env = mock_env();
+    let res = liquid_stake(&mut deps, env.clone());
+    assert_eq!(res.messages.len(), 3);
+
+    // unchanged rates aren't posted again
+    let res = liquid_stake(&mut deps, env.clone());
+    assert_eq!(res.messages.len(), 2);
+
+    // posted once the rates moved beyond the threshold
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token += Uint128::from(100u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+    let res = liquid_stake(&mut deps, env.clone());
+    assert_eq!(res.messages.len(), 3);
+
+    // or once the heartbeat elapsed
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(3600);
+    let res = liquid_stake(&mut deps, env.clone());
+    assert_eq!(res.messages.len(), 3);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::LastPostedRates {}).unwrap();
+    let posted: Option<PostedRates> = from_json(res).unwrap();
+    let posted = posted.unwrap();
+    assert_eq!(posted.posted_at, env.block.time.seconds());
+    assert!(posted.redemption_rate > Decimal::one());
+}
@@ -1,8 +1,11 @@
-use crate::contract::execute;
-use crate::msg::ExecuteMsg;
-use crate::tests::test_helper::{init, OSMO3};
-use cosmwasm_std::coins;
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::{MonitorQuorumConfig, CONFIG};
+use crate::tests::test_helper::{init, OSMO1, OSMO3};
 use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, from_json, Addr};
+use milky_way::ownership::{OwnershipError, OwnershipResponse};
 
 #[test]
 fn proper_transfer_ownership() {
@@ -110,3 +113,104 @@ fn non_admin_revoke_ownership_transfer() {
 
     assert!(res2.is_err());
 }
+
+#[test]
+fn ownership_transfer_expiry() {
+    let mut deps = init();
+    let info = mock_info(OSMO3, &[]);
+
+    let msg = ExecuteMsg::UpdateOwnershipConfig {
+        transfer_delay: Some(60),
+        transfer_expiry: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::Ownership(
+            OwnershipError::InvalidConfig { .. }
+        ))
+    ));
+
+    let msg = ExecuteMsg::UpdateOwnershipConfig {
+        transfer_delay: Some(60 * 60 * 24),
+        transfer_expiry: Some(60 * 60),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::TransferOwnership {
+        new_owner: OSMO1.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Ownership {}).unwrap();
+    let ownership: OwnershipResponse = from_json(res).unwrap();
+    let claimable_at = mock_env().block.time.plus_seconds(60 * 60 * 24);
+    assert_eq!(ownership.owner, Some(Addr::unchecked(OSMO3)));
+    assert_eq!(ownership.pending_owner, Some(Addr::unchecked(OSMO1)));
+    assert_eq!(ownership.owner_transfer_min_time, Some(claimable_at));
+    assert_eq!(
+        ownership.owner_transfer_expiry,
+        Some(claimable_at.plus_seconds(60 * 60))
+    );
+
+    // the transfer lapsed
+    let mut env = mock_env();
+    env.block.time = claimable_at.plus_seconds(60 * 60);
+    let msg = ExecuteMsg::AcceptOwnership {};
+    let res = execute(deps.as_mut(), env, mock_info(OSMO1, &[]), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::Ownership(
+            OwnershipError::TransferExpired { .. }
+        ))
+    ));
+}
+
+#[test]
+fn renounce_ownership() {
+    let mut deps = init();
+    let info = mock_info(OSMO3, &[]);
+    let contract = mock_env().contract.address.to_string();
+
+    let msg = ExecuteMsg::RenounceOwnership {
+        confirm: OSMO3.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::Ownership(
+            OwnershipError::RenounceNotAllowed { .. }
+        ))
+    ));
+
+    // the monitors need to be able to resume the contract
+    let msg = ExecuteMsg::RenounceOwnership {
+        confirm: contract.clone(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    assert!(matches!(
+        res,
+        Err(ContractError::Ownership(
+            OwnershipError::RenounceNotAllowed { .. }
+        ))
+    ));
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.monitor_quorum = Some(MonitorQuorumConfig {
+        threshold: 2,
+        proposal_ttl: 3600,
+        recovery: false,
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Ownership {}).unwrap();
+    let ownership: OwnershipResponse = from_json(res).unwrap();
+    assert_eq!(ownership.owner, None);
+
+    let msg = ExecuteMsg::TransferOwnership {
+        new_owner: OSMO1.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert!(res.is_err());
+}
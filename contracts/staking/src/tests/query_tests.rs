@@ -1,15 +1,16 @@
 // use serde_json;
-use crate::contract::{execute, query};
+use crate::contract::{execute, query, DEFAULT_TWAP_WINDOW, MAX_TWAP_WINDOW};
 use crate::msg::{
-    BatchResponse, BatchesResponse, ConfigResponse, ExecuteMsg, QueryMsg, StateResponse,
+    BatchResponse, BatchesResponse, ConfigResponse, ExecuteMsg, QueryMsg, RatesResponse,
+    StateResponse,
 };
 use crate::query::query_pending_batch;
-use crate::state::{CONFIG, STATE};
+use crate::state::{RateSnapshot, CONFIG, RATE_SNAPSHOTS, STATE};
 use crate::tests::test_helper::{
     init, CELESTIAVAL1, CELESTIAVAL2, CHANNEL_ID, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3,
 };
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{coins, from_json, Decimal, Uint128};
+use cosmwasm_std::{coins, from_json, Decimal, Decimal256, Uint128};
 
 #[test]
 fn get_config() {
@@ -64,6 +65,7 @@ fn get_state() {
     let stake_msg = ExecuteMsg::LiquidStake {
         mint_to: None,
         expected_mint_amount: None,
+        forward: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, stake_msg);
     assert!(res.is_ok());
@@ -113,7 +115,7 @@ fn get_batch() {
 
     // unStake 1
     let info = mock_info("bob", &coins(500, "factory/cosmos2contract/stTIA"));
-    let unstake_msg = ExecuteMsg::LiquidUnstake {};
+    let unstake_msg = ExecuteMsg::LiquidUnstake { owner: None };
     let res = execute(deps.as_mut(), mock_env(), info, unstake_msg.clone());
     assert!(res.is_ok());
 
@@ -171,7 +173,7 @@ fn get_batches() {
 
     // unStake 1
     let info = mock_info("bob", &coins(500, "factory/cosmos2contract/stTIA"));
-    let unstake_msg = ExecuteMsg::LiquidUnstake {};
+    let unstake_msg = ExecuteMsg::LiquidUnstake { owner: None };
     let res = execute(deps.as_mut(), env.clone(), info, unstake_msg.clone());
     assert!(res.is_ok());
 
@@ -246,7 +248,7 @@ fn get_pending_batch() {
     let mut deps = init();
     let mut env = mock_env();
 
-    let pending_batch_id = query_pending_batch(deps.as_ref());
+    let pending_batch_id = query_pending_batch(deps.as_ref(), mock_env());
     assert!(pending_batch_id.unwrap().id == 1);
 
     let config = CONFIG.load(&deps.storage).unwrap();
@@ -258,7 +260,7 @@ fn get_pending_batch() {
     STATE.save(&mut deps.storage, &state).unwrap();
 
     let info = mock_info("bob", &coins(1000, "factory/cosmos2contract/stTIA"));
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
     let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
 
     env.block.time = env.block.time.plus_seconds(config.batch_period + 1);
@@ -273,6 +275,64 @@ fn get_pending_batch() {
     }
     assert!(res.is_ok());
 
-    let pending_batch_id = query_pending_batch(deps.as_ref());
+    let pending_batch_id = query_pending_batch(deps.as_ref(), mock_env());
     assert!(pending_batch_id.unwrap().id == 2);
 }
+
+#[test]
+fn get_rates() {
+    let mut deps = init();
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(1000u128);
+    state.total_liquid_stake_token = Uint128::from(1000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    // the rates are recorded by liquid staking
+    let env = mock_env();
+    let start = env.block.time.seconds();
+    let msg = ExecuteMsg::LiquidStake {
+        mint_to: None,
+        expected_mint_amount: None,
+        forward: None,
+    };
+    let info = mock_info(OSMO3, &coins(1000, NATIVE_TOKEN));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = QueryMsg::Rates { twap_window: None };
+    let res: RatesResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.redemption_rate, Decimal256::one());
+    assert_eq!(res.purchase_rate, Decimal256::one());
+    assert_eq!(res.updated_at, Some(start));
+    assert_eq!(res.twap_window, DEFAULT_TWAP_WINDOW);
+    assert_eq!(res.twap_redemption_rate, Decimal256::one());
+
+    // rewards raise the redemption rate to 1.1 after 600 seconds
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(2200u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+    RATE_SNAPSHOTS
+        .save(
+            &mut deps.storage,
+            start + 600,
+            &RateSnapshot {
+                redemption_rate: Decimal256::percent(110),
+                purchase_rate: Decimal256::from_ratio(2000u128, 2200u128),
+            },
+        )
+        .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(1200);
+    let msg = QueryMsg::Rates {
+        twap_window: Some(1200),
+    };
+    let res: RatesResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.redemption_rate, Decimal256::percent(110));
+    assert_eq!(res.updated_at, Some(start + 600));
+    assert_eq!(res.twap_redemption_rate, Decimal256::percent(105));
+
+    let msg = QueryMsg::Rates {
+        twap_window: Some(MAX_TWAP_WINDOW + 1),
+    };
+    assert!(query(deps.as_ref(), env, msg).is_err());
+}
@@ -1,12 +1,17 @@
-use crate::contract::{execute, IBC_TIMEOUT};
+use crate::contract::{execute, query, IBC_TIMEOUT};
 use crate::helpers::derive_intermediate_sender;
-use crate::msg::ExecuteMsg;
-use crate::state::{CONFIG, STATE};
-use crate::tests::test_helper::{init, CELESTIA1, CHANNEL_ID, NATIVE_TOKEN};
+use crate::msg::{ExecuteMsg, ExtraRewardsResponse, QueryMsg};
+use crate::state::{
+    ExtraRewardDenom, ExtraRewardDestination, FeeRecipient, RewardFeeMode, CONFIG, STATE,
+};
+use crate::tests::test_helper::{init, CELESTIA1, CHANNEL_ID, NATIVE_TOKEN, OSMO1, OSMO2};
 
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{Addr, CosmosMsg, ReplyOn, Uint128};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, ReplyOn, Uint128, WasmMsg,
+};
 use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
+use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgMint;
 
 #[test]
 fn receive_rewards() {
@@ -35,16 +40,10 @@ fn receive_rewards() {
     )
     .unwrap();
 
-    let info = mock_info(
-        &sender,
-        &[cosmwasm_std::Coin {
-            amount: Uint128::from(100u128),
-            denom: "uosmo".to_string(),
-        }],
-    );
+    let info = mock_info(&sender, &[]);
     let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
 
-    assert!(res.is_err()); // wrong denom
+    assert!(res.is_err()); // no funds
 
     let info = mock_info(
         &contract,
@@ -168,3 +167,279 @@ fn receive_rewards_and_send_fees_to_treasury() {
     assert_eq!(state.total_native_token, Uint128::from(100_090u128));
     assert_eq!(state.total_fees, Uint128::from(0u128));
 }
+
+#[test]
+fn receive_rewards_and_mint_fees_to_treasury() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    state.total_reward_amount = Uint128::from(0u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    config.protocol_fee_config.reward_fee_mode = RewardFeeMode::Mint;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let sender = derive_intermediate_sender(
+        &config.ibc_channel_id,
+        config
+            .multisig_address_config
+            .reward_collector_address
+            .as_ref(),
+        "osmo",
+    )
+    .unwrap();
+
+    let info = mock_info(
+        &sender,
+        &[cosmwasm_std::Coin {
+            amount: Uint128::from(1_000u128),
+            denom: config.native_token_denom.clone(),
+        }],
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::from(MsgTransfer {
+            source_channel: CHANNEL_ID.to_string(),
+            source_port: "transfer".to_string(),
+            sender: env.contract.address.to_string(),
+            receiver: Addr::unchecked(CELESTIA1).to_string(),
+            token: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
+                denom: NATIVE_TOKEN.to_string(),
+                amount: "1000".to_string(),
+            }),
+            timeout_height: None,
+            timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
+            memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
+        })
+    );
+    // 100 * 100_000 / (101_000 - 100)
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::from(MsgMint {
+            sender: env.contract.address.to_string(),
+            amount: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
+                denom: config.liquid_stake_token_denom.clone(),
+                amount: "99".to_string(),
+            }),
+            mint_to_address: config.treasury_address.to_string(),
+        })
+    );
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_reward_amount, Uint128::from(1_000u128));
+    assert_eq!(state.total_native_token, Uint128::from(101_000u128));
+    assert_eq!(state.total_liquid_stake_token, Uint128::from(100_099u128));
+    assert_eq!(state.total_fees, Uint128::zero());
+}
+
+#[test]
+fn minted_fees_are_split_between_fee_recipients() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.protocol_fee_config.reward_fee_mode = RewardFeeMode::Mint;
+    config.fee_recipients = Some(vec![
+        FeeRecipient {
+            address: Addr::unchecked(OSMO1),
+            weight: Decimal::percent(60),
+        },
+        FeeRecipient {
+            address: Addr::unchecked(OSMO2),
+            weight: Decimal::percent(40),
+        },
+    ]);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let sender = derive_intermediate_sender(
+        &config.ibc_channel_id,
+        config
+            .multisig_address_config
+            .reward_collector_address
+            .as_ref(),
+        "osmo",
+    )
+    .unwrap();
+    let info = mock_info(&sender, &[Coin::new(1_000, NATIVE_TOKEN)]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+
+    // the 99 stTIA minted as fee, with the rounding dust going to the first recipient
+    let mint_msg = |address: &str, amount: &str| {
+        CosmosMsg::from(MsgMint {
+            sender: env.contract.address.to_string(),
+            amount: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
+                denom: config.liquid_stake_token_denom.clone(),
+                amount: amount.to_string(),
+            }),
+            mint_to_address: address.to_string(),
+        })
+    };
+    let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(
+        messages[2..],
+        vec![mint_msg(OSMO1, "60"), mint_msg(OSMO2, "39")]
+    );
+}
+
+#[test]
+fn receive_extra_rewards() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    config.extra_reward_denoms = Some(vec![
+        ExtraRewardDenom {
+            denom: "uosmo".to_string(),
+            destination: ExtraRewardDestination::Treasury,
+        },
+        ExtraRewardDenom {
+            denom: "uatom".to_string(),
+            destination: ExtraRewardDestination::Contract {
+                address: Addr::unchecked(OSMO2),
+                msg: to_json_binary(&"swap").unwrap(),
+            },
+        },
+    ]);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let sender = derive_intermediate_sender(
+        &config.ibc_channel_id,
+        config
+            .multisig_address_config
+            .reward_collector_address
+            .as_ref(),
+        "osmo",
+    )
+    .unwrap();
+
+    // extra rewards are forwarded along with the native rewards
+    let info = mock_info(
+        &sender,
+        &[
+            Coin::new(100, NATIVE_TOKEN),
+            Coin::new(50, "uosmo"),
+            Coin::new(20, "uatom"),
+        ],
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 5);
+    assert_eq!(
+        res.messages[3].msg,
+        CosmosMsg::from(BankMsg::Send {
+            to_address: config.treasury_address.to_string(),
+            amount: vec![Coin::new(50, "uosmo")],
+        })
+    );
+    assert_eq!(
+        res.messages[4].msg,
+        CosmosMsg::from(WasmMsg::Execute {
+            contract_addr: OSMO2.to_string(),
+            msg: to_json_binary(&"swap").unwrap(),
+            funds: vec![Coin::new(20, "uatom")],
+        })
+    );
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "extra_rewards" && a.value == "50uosmo,20uatom"));
+
+    // extra rewards can be received without native rewards
+    let info = mock_info(&sender, &[Coin::new(30, "uosmo")]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_reward_amount, Uint128::from(100u128));
+
+    let res = query(deps.as_ref(), env, QueryMsg::ExtraRewards {}).unwrap();
+    let extra_rewards: ExtraRewardsResponse = from_json(res).unwrap();
+    assert_eq!(extra_rewards.denoms, config.extra_reward_denoms.unwrap());
+    assert_eq!(
+        extra_rewards.forwarded,
+        vec![Coin::new(20, "uatom"), Coin::new(80, "uosmo")]
+    );
+}
+
+#[test]
+fn unlisted_reward_denom_goes_to_treasury() {
+    let mut deps = init();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let sender = derive_intermediate_sender(
+        &config.ibc_channel_id,
+        config
+            .multisig_address_config
+            .reward_collector_address
+            .as_ref(),
+        "osmo",
+    )
+    .unwrap();
+
+    let info = mock_info(
+        &sender,
+        &[Coin::new(100, NATIVE_TOKEN), Coin::new(50, "uosmo")],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+    assert!(res.messages.iter().any(|m| m.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: config.treasury_address.to_string(),
+            amount: vec![Coin::new(50, "uosmo")],
+        })));
+
+    // the native rewards are still staked
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_reward_amount, Uint128::from(100u128));
+}
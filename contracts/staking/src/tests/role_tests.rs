@@ -0,0 +1,187 @@
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, RolesResponse};
+use crate::state::{Role, CONFIG};
+use crate::tests::test_helper::{init, CELESTIAVAL3, OSMO1, OSMO2, OSMO3};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_json, Addr, Uint128};
+
+fn update_config_msg(
+    minimum_liquid_stake_amount: Option<Uint128>,
+    send_fees_to_treasury: Option<bool>,
+) -> ExecuteMsg {
+    ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    }
+}
+
+#[test]
+fn grant_and_revoke_roles() {
+    let mut deps = init();
+
+    // only the admin grants roles
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::ValidatorManager,
+        address: OSMO1.to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::FeeManager,
+        address: OSMO2.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Roles {}).unwrap();
+    let roles: RolesResponse = from_json(res).unwrap();
+    assert_eq!(roles.admin, Some(Addr::unchecked(OSMO3)));
+    assert_eq!(roles.roles.len(), Role::ALL.len());
+    let validator_managers = roles
+        .roles
+        .iter()
+        .find(|r| r.role == Role::ValidatorManager)
+        .unwrap();
+    assert_eq!(validator_managers.addresses, vec![Addr::unchecked(OSMO1)]);
+
+    let add_validator = ExecuteMsg::AddValidator {
+        new_validator: CELESTIAVAL3.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        add_validator.clone(),
+    )
+    .unwrap();
+
+    // roles don't grant anything else
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::FeeWithdraw {
+            amount: Uint128::zero(),
+        },
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MissingRole { role, .. }) if role == "fee_manager"
+    ));
+
+    // an update without any field is rejected for everyone
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        update_config_msg(None, None),
+    );
+    assert!(matches!(res, Err(ContractError::EmptyConfigUpdate {})));
+
+    // fee managers can only update the fee fields
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        update_config_msg(None, Some(false)),
+    )
+    .unwrap();
+    assert!(!CONFIG.load(&deps.storage).unwrap().send_fees_to_treasury);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        update_config_msg(Some(Uint128::from(500u128)), Some(true)),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MissingRole { role, .. }) if role == "config_manager"
+    ));
+
+    let msg = ExecuteMsg::RevokeRole {
+        role: Role::ValidatorManager,
+        address: OSMO1.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg);
+    assert!(matches!(res, Err(ContractError::MissingRole { .. })));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        add_validator,
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MissingRole { role, .. }) if role == "validator_manager"
+    ));
+}
+
+#[test]
+fn pauser_and_resumer() {
+    let mut deps = init();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::CircuitBreaker {},
+    );
+    assert!(matches!(res, Err(ContractError::Unauthorized { .. })));
+
+    for role in [Role::Pauser, Role::Resumer] {
+        let msg = ExecuteMsg::GrantRole {
+            role,
+            address: OSMO1.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        ExecuteMsg::CircuitBreaker {},
+    )
+    .unwrap();
+    assert!(CONFIG.load(&deps.storage).unwrap().stopped);
+
+    let msg = ExecuteMsg::ResumeContract {
+        total_native_token: Uint128::zero(),
+        total_liquid_stake_token: Uint128::zero(),
+        total_reward_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO1, &[]), msg).unwrap();
+    assert!(!CONFIG.load(&deps.storage).unwrap().stopped);
+}
@@ -8,8 +8,8 @@ mod staking_tests {
     use crate::tests::test_helper::{init, CELESTIA1, CHANNEL_ID, NATIVE_TOKEN, OSMO3};
     use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
     use cosmwasm_std::{
-        attr, coins, Addr, CosmosMsg, IbcTimeout, Order, Reply, ReplyOn, SubMsg, SubMsgResponse,
-        SubMsgResult, Timestamp, Uint128, Decimal
+        attr, coins, Addr, CosmosMsg, Decimal, IbcTimeout, Order, Reply, ReplyOn, SubMsg,
+        SubMsgResponse, SubMsgResult, Timestamp, Uint128,
     };
     use milky_way::staking::BatchStatus;
     use osmosis_std::types::cosmos::base::v1beta1::Coin;
@@ -25,6 +25,7 @@ mod staking_tests {
         let msg = ExecuteMsg::LiquidStake {
             mint_to: None,
             expected_mint_amount: None,
+            forward: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
 
@@ -62,10 +63,7 @@ mod staking_tests {
                             token: Some(ibc_coin),
                             timeout_height: None,
                             timeout_timestamp: timeout.timestamp().unwrap().nanos(),
-                            memo: format!(
-                                "{{\"ibc_callback\":\"{}\"}}",
-                                env.contract.address
-                            ),
+                            memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
                         }),
                         gas_limit: None,
                         reply_on: ReplyOn::Always,
@@ -168,8 +166,14 @@ mod staking_tests {
 
         // test redemption rate, purchase rate
         let (redemption_rate, purchase_rate) = get_rates(&deps.as_ref());
-        assert_eq!(redemption_rate, Decimal::from_ratio(1_050_000_000u128, 1_050_000u128));
-        assert_eq!(purchase_rate, Decimal::from_ratio(1_050_000u128, 1_050_000_000u128));
+        assert_eq!(
+            redemption_rate,
+            Decimal::from_ratio(1_050_000_000u128, 1_050_000u128)
+        );
+        assert_eq!(
+            purchase_rate,
+            Decimal::from_ratio(1_050_000u128, 1_050_000_000u128)
+        );
     }
 
     #[test]
@@ -179,6 +183,7 @@ mod staking_tests {
         let msg = ExecuteMsg::LiquidStake {
             mint_to: None,
             expected_mint_amount: None,
+            forward: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -209,6 +214,7 @@ mod staking_tests {
         let msg: ExecuteMsg = ExecuteMsg::LiquidStake {
             mint_to: Some(OSMO3.to_string()),
             expected_mint_amount: None,
+            forward: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -230,7 +236,8 @@ mod staking_tests {
             &config.ibc_channel_id,
             config
                 .multisig_address_config
-                .reward_collector_address.as_ref(),
+                .reward_collector_address
+                .as_ref(),
             "osmo",
         )
         .unwrap();
@@ -255,6 +262,7 @@ mod staking_tests {
         let msg = ExecuteMsg::LiquidStake {
             mint_to: None,
             expected_mint_amount: Some(Uint128::from(2_000_000u128)),
+            forward: None,
         };
         let res: Result<cosmwasm_std::Response, ContractError> =
             execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
@@ -263,6 +271,7 @@ mod staking_tests {
         let msg = ExecuteMsg::LiquidStake {
             mint_to: None,
             expected_mint_amount: Some(Uint128::from(1_000_000u128)),
+            forward: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
         if res.is_err() {
@@ -285,6 +294,7 @@ mod staking_tests {
         let msg = ExecuteMsg::LiquidStake {
             mint_to: None,
             expected_mint_amount: None,
+            forward: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
         assert!(res.is_ok());
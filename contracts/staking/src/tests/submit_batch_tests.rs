@@ -1,9 +1,15 @@
-use crate::contract::execute;
-use crate::msg::ExecuteMsg;
-use crate::state::{CONFIG, STATE};
-use crate::tests::test_helper::init;
-use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::Uint128;
+use crate::contract::{execute, query, MAX_KEEPER_REWARD, MAX_KEEPER_REWARD_RATE};
+use crate::error::ContractError;
+use crate::msg::{BatchResponse, ExecuteMsg, QueryMsg};
+use crate::state::{
+    BatchTriggerConfig, KeeperRewardConfig, KeeperRewardSource, OptionalConfig, Role, CONFIG, STATE,
+};
+use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, BankMsg, CosmosMsg, Decimal, Env, OwnedDeps, Timestamp, Uint128,
+};
+use cw_utils::PaymentError;
 
 #[test]
 fn empty_submit_batch() {
@@ -50,3 +56,456 @@ fn not_ready_submit_batch() {
 
     assert!(res.is_err());
 }
+
+fn set_batch_config(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    keeper_reward: Option<KeeperRewardConfig>,
+    batch_trigger: Option<BatchTriggerConfig>,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward,
+        batch_trigger,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg)
+}
+
+// Unstakes 10_000 stTIA, which unbonds 20_000 TIA, and returns an env where the batch is ready
+fn ready_batch(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Env {
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(200_000u128);
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let info = mock_info("bob", &coins(10_000, "factory/cosmos2contract/stTIA"));
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(config.batch_period + 1);
+    env
+}
+
+fn keeper_reward_msg(amount: u128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: OSMO2.to_string(),
+        amount: coins(amount, NATIVE_TOKEN),
+    })
+}
+
+#[test]
+fn submit_batch_without_keeper_reward() {
+    let mut deps = init();
+    let env = ready_batch(&mut deps);
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "keeper" && a.value == OSMO2));
+    assert!(!res.attributes.iter().any(|a| a.key == "keeper_reward"));
+    assert!(!res
+        .messages
+        .iter()
+        .any(|m| matches!(m.msg, CosmosMsg::Bank(_))));
+}
+
+#[test]
+fn keeper_reward_from_fees() {
+    let mut deps = init();
+    let env = ready_batch(&mut deps);
+
+    // 1% of 20_000 capped at 150, limited by the collected fees
+    set_batch_config(
+        &mut deps,
+        Some(KeeperRewardConfig {
+            rate: Decimal::percent(1),
+            max_reward: Uint128::from(150u128),
+            source: KeeperRewardSource::Fees,
+        }),
+        None,
+    )
+    .unwrap();
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_fees = Uint128::from(100u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+
+    assert!(res.messages.iter().any(|m| m.msg == keeper_reward_msg(100)));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "keeper_reward" && a.value == "100"));
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_fees, Uint128::zero());
+}
+
+#[test]
+fn keeper_reward_from_bounty() {
+    let mut deps = init();
+    let env = ready_batch(&mut deps);
+
+    set_batch_config(
+        &mut deps,
+        Some(KeeperRewardConfig {
+            rate: Decimal::percent(1),
+            max_reward: Uint128::from(150u128),
+            source: KeeperRewardSource::Bounty,
+        }),
+        None,
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &coins(1000, "uosmo")),
+        ExecuteMsg::FundKeeperBounty {},
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::Payment(PaymentError::MissingDenom(_)))
+    ));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::FundKeeperBounty {},
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+
+    assert!(res.messages.iter().any(|m| m.msg == keeper_reward_msg(150)));
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.keeper_bounty, Uint128::from(850u128));
+}
+
+#[test]
+fn invalid_keeper_reward() {
+    let mut deps = init();
+
+    let res = set_batch_config(
+        &mut deps,
+        Some(KeeperRewardConfig {
+            rate: MAX_KEEPER_REWARD_RATE + Decimal::permille(1),
+            max_reward: Uint128::from(150u128),
+            source: KeeperRewardSource::Fees,
+        }),
+        None,
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::InvalidKeeperReward { .. })
+    ));
+
+    let res = set_batch_config(
+        &mut deps,
+        Some(KeeperRewardConfig {
+            rate: Decimal::percent(1),
+            max_reward: MAX_KEEPER_REWARD + Uint128::one(),
+            source: KeeperRewardSource::Fees,
+        }),
+        None,
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::InvalidKeeperReward { .. })
+    ));
+}
+
+#[test]
+fn keeper_reward_needs_fee_manager() {
+    let mut deps = init();
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::ConfigManager,
+        address: OSMO1.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+
+    // a config manager can neither set nor clear the keeper reward
+    let update = |keeper_reward: Option<KeeperRewardConfig>, clear: Option<Vec<OptionalConfig>>| {
+        ExecuteMsg::UpdateConfig {
+            batch_period: None,
+            unbonding_period: None,
+            minimum_liquid_stake_amount: None,
+            multisig_address_config: None,
+            protocol_fee_config: None,
+            native_token_denom: None,
+            channel_id: None,
+            monitors: None,
+            treasury_address: None,
+            oracle_address: None,
+            send_fees_to_treasury: None,
+            keeper_reward,
+            batch_trigger: None,
+            unclaimed_sweep: None,
+            fee_recipients: None,
+            extra_reward_denoms: None,
+            oracle_targets: None,
+            oracle_throttle: None,
+            monitor_quorum: None,
+            clear,
+        }
+    };
+    let keeper_reward = KeeperRewardConfig {
+        rate: Decimal::percent(1),
+        max_reward: Uint128::from(150u128),
+        source: KeeperRewardSource::Fees,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        update(Some(keeper_reward.clone()), None),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::MissingRole { role, .. }) if role == Role::FeeManager.as_str()
+    ));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO1, &[]),
+        update(None, Some(vec![OptionalConfig::KeeperReward])),
+    );
+    assert!(matches!(res, Err(ContractError::MissingRole { .. })));
+
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::FeeManager,
+        address: OSMO2.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        update(Some(keeper_reward.clone()), None),
+    )
+    .unwrap();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.keeper_reward, Some(keeper_reward));
+}
+
+#[test]
+fn clear_keeper_reward_and_batch_trigger() {
+    let mut deps = init();
+    let keeper_reward = KeeperRewardConfig {
+        rate: Decimal::percent(1),
+        max_reward: Uint128::from(150u128),
+        source: KeeperRewardSource::Fees,
+    };
+    set_batch_config(
+        &mut deps,
+        Some(keeper_reward.clone()),
+        Some(BatchTriggerConfig {
+            max_batch_liquid_stake: Some(Uint128::from(1000u128)),
+            max_unstake_requests: None,
+            min_submission_interval: 60,
+        }),
+    )
+    .unwrap();
+
+    let clear_msg = |keeper_reward: Option<KeeperRewardConfig>| ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward,
+        batch_trigger: None,
+        unclaimed_sweep: None,
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: Some(vec![
+            OptionalConfig::KeeperReward,
+            OptionalConfig::BatchTrigger,
+        ]),
+    };
+
+    // a feature can't be set and cleared at once
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        clear_msg(Some(keeper_reward)),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::InvalidConfigField { field, .. }) if field == "clear"
+    ));
+
+    // only the config manager clears
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        clear_msg(None),
+    );
+    assert!(res.is_err());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        clear_msg(None),
+    )
+    .unwrap();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.keeper_reward, None);
+    assert_eq!(config.batch_trigger, None);
+}
+
+fn pending_batch(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: Env) -> BatchResponse {
+    let res = query(deps.as_ref(), env, QueryMsg::PendingBatch {}).unwrap();
+    from_json(res).unwrap()
+}
+
+#[test]
+fn size_triggered_submit_batch() {
+    let mut deps = init();
+    let env = ready_batch(&mut deps);
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let now = env.block.time.seconds();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+
+    let info = mock_info("bob", &coins(10_000, "factory/cosmos2contract/stTIA"));
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let readiness = pending_batch(&deps, env.clone()).readiness.unwrap();
+    assert!(!readiness.ready);
+    assert!(!readiness.size_triggered);
+    assert_eq!(
+        readiness.ready_at,
+        Timestamp::from_seconds(now + config.batch_period)
+    );
+
+    // the 10_000 stTIA of the batch exceed the limit, but it has to wait for the submission interval
+    set_batch_config(
+        &mut deps,
+        None,
+        Some(BatchTriggerConfig {
+            max_batch_liquid_stake: Some(Uint128::from(5_000u128)),
+            max_unstake_requests: None,
+            min_submission_interval: 3600,
+        }),
+    )
+    .unwrap();
+
+    let mut env = env;
+    env.block.time = env.block.time.plus_seconds(3599);
+    let readiness = pending_batch(&deps, env.clone()).readiness.unwrap();
+    assert!(!readiness.ready);
+    assert!(readiness.size_triggered);
+    assert!(!readiness.time_triggered);
+    assert_eq!(readiness.ready_at, Timestamp::from_seconds(now + 3600));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    );
+    assert!(matches!(res, Err(ContractError::BatchNotReady { .. })));
+
+    env.block.time = env.block.time.plus_seconds(1);
+    assert!(pending_batch(&deps, env.clone()).readiness.unwrap().ready);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+}
+
+#[test]
+fn submission_interval_applies_to_time_triggered_batches() {
+    let mut deps = init();
+    let env = ready_batch(&mut deps);
+
+    set_batch_config(
+        &mut deps,
+        None,
+        Some(BatchTriggerConfig {
+            max_batch_liquid_stake: None,
+            max_unstake_requests: Some(5),
+            min_submission_interval: 100_000,
+        }),
+    )
+    .unwrap();
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.last_batch_submission = Some(env.block.time.seconds() - 50_000);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let readiness = pending_batch(&deps, env.clone()).readiness.unwrap();
+    assert!(readiness.time_triggered);
+    assert!(!readiness.ready);
+    assert_eq!(
+        readiness.ready_at,
+        Timestamp::from_seconds(env.block.time.seconds() + 50_000)
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::SubmitBatch {},
+    );
+    assert!(matches!(res, Err(ContractError::BatchNotReady { .. })));
+}
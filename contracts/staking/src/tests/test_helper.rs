@@ -1,11 +1,11 @@
 use crate::contract::instantiate;
 use crate::msg::InstantiateMsg;
-use crate::state::{Config, MultisigAddressConfig, ProtocolFeeConfig, CONFIG};
+use crate::state::{Config, MultisigAddressConfig, ProtocolFeeConfig, RewardFeeMode, CONFIG};
 
 use cosmwasm_std::testing::{
     mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
-use cosmwasm_std::{coins, Addr, OwnedDeps, Uint128};
+use cosmwasm_std::{coins, Addr, Decimal, OwnedDeps, Uint128};
 
 pub static OSMO1: &str = "osmo12z558dm3ew6avgjdj07mfslx80rp9sh8nt7q3w";
 pub static OSMO2: &str = "osmo13ftwm6z4dq6ugjvus2hf2vx3045ahfn3dq7dms";
@@ -31,7 +31,10 @@ pub fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
         batch_period: 86400,
         unbonding_period: 1209600,
         protocol_fee_config: ProtocolFeeConfig {
-            dao_treasury_fee: Uint128::from(10000u128),
+            dao_treasury_fee: Decimal::percent(10),
+            deposit_fee: None,
+            redemption_fee: None,
+            reward_fee_mode: RewardFeeMode::Native,
         },
         multisig_address_config: MultisigAddressConfig {
             staker_address: Addr::unchecked(CELESTIA1),
@@ -7,7 +7,7 @@ use crate::state::new_unstake_request;
 use crate::state::unstake_requests;
 use crate::state::UnstakeRequest;
 use crate::state::{Config, BATCHES, CONFIG, STATE};
-use crate::tests::test_helper::init;
+use crate::tests::test_helper::{init, CELESTIA1, CHANNEL_ID, OSMO2};
 use cosmwasm_std::from_json;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{coins, Addr, CosmosMsg, ReplyOn, SubMsg, Uint128};
@@ -26,7 +26,7 @@ fn proper_liquid_unstake() {
     STATE.save(&mut deps.storage, &state).unwrap();
 
     let info = mock_info("bob", &coins(1000, "factory/cosmos2contract/stTIA"));
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
     let mut res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     let resp = res.unwrap();
     let attrs = resp.attributes;
@@ -65,7 +65,7 @@ fn double_liquid_unstake() {
     state.total_native_token = Uint128::from(10_000u128);
     state.total_liquid_stake_token = Uint128::from(100_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
 
     // Bob unstakes 500
     let info = mock_info("bob", &coins(500, "factory/cosmos2contract/stTIA"));
@@ -179,7 +179,7 @@ fn invalid_denom_liquid_unstake() {
     STATE.save(&mut deps.storage, &state).unwrap();
 
     let info = mock_info("bob", &coins(1000, "factory/bob/stTIA"));
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
 
@@ -253,7 +253,7 @@ fn invalid_amount_liquid_unstake() {
         "bob",
         &coins(1_000_000_000, "factory/cosmos2contract/stTIA"),
     );
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
 
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     let resp = res.unwrap();
@@ -311,7 +311,7 @@ fn total_liquid_stake_token_with_zero() {
         "bob",
         &coins(1_000_000_000, "factory/cosmos2contract/stTIA"),
     );
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake { owner: None };
 
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     let resp = res.unwrap();
@@ -439,3 +439,73 @@ fn claimable_batches() {
     let unstake_requests = unstake_requests_res.unwrap();
     assert_eq!(unstake_requests.first().unwrap().batch_id, 1);
 }
+
+#[test]
+fn ibc_liquid_unstake_with_owner() {
+    let mut deps = init();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(10_000u128);
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let intermediate_sender = derive_intermediate_sender(CHANNEL_ID, CELESTIA1, "osmo").unwrap();
+    let info = mock_info(
+        &intermediate_sender,
+        &coins(1000, "factory/cosmos2contract/stTIA"),
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::LiquidUnstake {
+            owner: Some("cosmos1sfhy3emrgp26wnzuu64p06kpkxd9phelq3kx5v".to_string()),
+        },
+    );
+    assert!(res.is_err()); // unsupported prefix
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::LiquidUnstake {
+            owner: Some(CELESTIA1.to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[4].value, CELESTIA1);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::LiquidUnstake {
+            owner: Some(OSMO2.to_string()),
+        },
+    );
+    assert!(res.is_ok());
+
+    // the requests are bound to the owners, not the intermediate sender
+    assert!(unstake_requests()
+        .may_load(&deps.storage, (1, intermediate_sender))
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        unstake_requests()
+            .load(&deps.storage, (1, CELESTIA1.to_string()))
+            .unwrap()
+            .amount,
+        Uint128::from(1000u128)
+    );
+    assert_eq!(
+        unstake_requests()
+            .load(&deps.storage, (1, OSMO2.to_string()))
+            .unwrap()
+            .amount,
+        Uint128::from(1000u128)
+    );
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(batch.unstake_requests_count, 2);
+}
@@ -1,12 +1,20 @@
-use crate::contract::{execute, query};
-use crate::msg::{ExecuteMsg, QueryMsg};
-use crate::state::{new_unstake_request, UnstakeRequest, BATCHES, CONFIG, STATE};
-use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO1, OSMO3};
-use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
-use cosmwasm_std::{from_json, Addr, CosmosMsg, ReplyOn, SubMsg, Uint128};
+use crate::contract::{execute, query, IBC_TIMEOUT};
+use crate::error::ContractError;
+use crate::msg::{ArchivedBatchesResponse, BatchResponse, BatchesResponse, ExecuteMsg, QueryMsg};
+use crate::state::{
+    new_unstake_request, ArchivedBatch, SweepDestination, SweptRequest, UnclaimedSweepConfig,
+    UnstakeRequest, ARCHIVED_BATCHES, BATCHES, CONFIG, FORWARD_WAITING_FOR_REPLY, STATE,
+};
+use crate::tests::test_helper::{init, CELESTIA1, CHANNEL_ID, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3};
+use cosmwasm_std::testing::{
+    mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{from_json, Addr, CosmosMsg, OwnedDeps, ReplyOn, SubMsg, Uint128};
 use milky_way::staking::Batch;
 use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
 use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
+use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgMint;
 
 #[test]
 fn withdraw() {
@@ -303,3 +311,463 @@ fn fee_withdraw() {
         }
     );
 }
+
+#[test]
+fn forward_withdrawal() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut batch: Batch = Batch::new(1, Uint128::new(1_000), env.block.time.seconds());
+    new_unstake_request(
+        &mut deps.as_mut(),
+        CELESTIA1.to_string(),
+        1,
+        Uint128::from(400u128),
+    )
+    .unwrap();
+    new_unstake_request(
+        &mut deps.as_mut(),
+        OSMO2.to_string(),
+        1,
+        Uint128::from(600u128),
+    )
+    .unwrap();
+    batch.received_native_unstaked = Some(Uint128::new(2_000));
+    batch.status = milky_way::staking::BatchStatus::Received;
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+
+    // only celestia owners are forwarded
+    let msg = ExecuteMsg::ForwardWithdrawal {
+        batch_id: 1,
+        owner: OSMO2.to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), msg);
+    assert!(res.is_err());
+
+    // callable by anyone
+    let msg = ExecuteMsg::ForwardWithdrawal {
+        batch_id: 1,
+        owner: CELESTIA1.to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg {
+            id: 1,
+            msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                source_channel: CHANNEL_ID.to_string(),
+                source_port: "transfer".to_string(),
+                sender: env.contract.address.to_string(),
+                receiver: CELESTIA1.to_string(),
+                token: Some(Coin {
+                    denom: NATIVE_TOKEN.to_string(),
+                    amount: "800".to_string(),
+                }),
+                timeout_height: None,
+                timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
+                memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
+            }),
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        }
+    );
+    let forward = FORWARD_WAITING_FOR_REPLY.load(&deps.storage, 1).unwrap();
+    assert_eq!(forward.fallback_address.as_str(), OSMO3);
+
+    // the request is gone
+    let res = execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), msg);
+    assert!(res.is_err());
+
+    // osmosis owners withdraw as usual
+    let msg = ExecuteMsg::Withdraw { batch_id: 1 };
+    let res = execute(deps.as_mut(), env, mock_info(OSMO2, &[]), msg);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn withdrawn_batch_is_archived() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut batch: Batch = Batch::new(1, Uint128::new(130_000), env.block.time.seconds());
+    batch.received_native_unstaked = Some(Uint128::new(140_001));
+    batch.unstake_requests_count = 2;
+    batch.status = milky_way::staking::BatchStatus::Received;
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+    for (user, amount) in [("bob", 40_000u128), ("tom", 90_000u128)] {
+        new_unstake_request(
+            &mut deps.as_mut(),
+            user.to_string(),
+            1,
+            Uint128::from(amount),
+        )
+        .unwrap();
+    }
+
+    let msg = ExecuteMsg::Withdraw { batch_id: 1 };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(batch.withdrawn_requests_count, 1);
+    assert_eq!(batch.withdrawn_native_unstaked, Uint128::new(43_077));
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("tom", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(!BATCHES.has(&deps.storage, 1));
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::ArchivedBatches {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let resp: ArchivedBatchesResponse = from_json(res).unwrap();
+    assert_eq!(
+        resp.batches,
+        vec![ArchivedBatch {
+            id: 1,
+            batch_total_liquid_stake: Uint128::new(130_000),
+            received_native_unstaked: Uint128::new(140_001),
+            withdrawn_native_unstaked: Uint128::new(140_000),
+            swept_native_unstaked: Uint128::zero(),
+            unstake_requests_count: 2,
+        }]
+    );
+
+    // archived batches are no longer listed and can't be withdrawn from
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Batches {
+            start_after: None,
+            limit: None,
+            status: None,
+        },
+    )
+    .unwrap();
+    let resp: BatchesResponse = from_json(res).unwrap();
+    assert!(resp.batches.iter().all(|b| b.id != 1));
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Batch { id: 1 }).unwrap();
+    let resp: BatchResponse = from_json(res).unwrap();
+    assert_eq!(resp.status, "archived");
+    assert_eq!(resp.received_native_unstaked, Uint128::new(140_001));
+    assert_eq!(resp.unstake_request_count, 2);
+    let res = execute(deps.as_mut(), env, mock_info("tom", &[]), msg);
+    assert!(matches!(res, Err(ContractError::BatchEmpty {})));
+}
+
+// Batch 1 received at `received_at` with 130_000 TIA for bob's 40_000 and tom's 90_000 stTIA
+fn received_batch(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, received_at: u64) {
+    let mut batch: Batch = Batch::new(1, Uint128::new(130_000), received_at);
+    batch.received_native_unstaked = Some(Uint128::new(130_000));
+    batch.unstake_requests_count = 2;
+    batch.update_status(milky_way::staking::BatchStatus::Received, None);
+    batch.received_at = Some(received_at);
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+    for (user, amount) in [("bob", 40_000u128), ("tom", 90_000u128)] {
+        new_unstake_request(
+            &mut deps.as_mut(),
+            user.to_string(),
+            1,
+            Uint128::from(amount),
+        )
+        .unwrap();
+    }
+}
+
+fn set_unclaimed_sweep(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    destination: SweepDestination,
+    permissionless: bool,
+) {
+    let msg = ExecuteMsg::UpdateConfig {
+        batch_period: None,
+        unbonding_period: None,
+        minimum_liquid_stake_amount: None,
+        multisig_address_config: None,
+        protocol_fee_config: None,
+        native_token_denom: None,
+        channel_id: None,
+        monitors: None,
+        treasury_address: None,
+        oracle_address: None,
+        send_fees_to_treasury: None,
+        keeper_reward: None,
+        batch_trigger: None,
+        unclaimed_sweep: Some(UnclaimedSweepConfig {
+            claim_window: 1000,
+            destination,
+            permissionless,
+        }),
+        fee_recipients: None,
+        extra_reward_denoms: None,
+        oracle_targets: None,
+        oracle_throttle: None,
+        monitor_quorum: None,
+        clear: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), msg).unwrap();
+}
+
+#[test]
+fn sweep_unclaimed_to_treasury() {
+    let mut deps = init();
+    let mut env = mock_env();
+    received_batch(&mut deps, env.block.time.seconds());
+
+    let msg = ExecuteMsg::SweepUnclaimed {
+        batch_id: 1,
+        limit: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        msg.clone(),
+    );
+    assert!(matches!(res, Err(ContractError::SweepNotEnabled {})));
+
+    set_unclaimed_sweep(&mut deps, SweepDestination::Treasury, true);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        msg.clone(),
+    );
+    assert!(matches!(res, Err(ContractError::ClaimWindowOpen { .. })));
+
+    // requests can be withdrawn until they are swept
+    env.block.time = env.block.time.plus_seconds(1000);
+    let withdraw_msg = ExecuteMsg::Withdraw { batch_id: 1 };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &[]),
+        withdraw_msg.clone(),
+    )
+    .unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(cosmwasm_std::BankMsg::Send {
+            to_address: OSMO1.to_string(),
+            amount: cosmwasm_std::coins(90_000, NATIVE_TOKEN),
+        })]
+    );
+    assert!(!BATCHES.has(&deps.storage, 1));
+    let archived = ARCHIVED_BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(archived.withdrawn_native_unstaked, Uint128::new(40_000));
+    assert_eq!(archived.swept_native_unstaked, Uint128::new(90_000));
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SweptRequests {
+            user: "tom".to_string(),
+        },
+    )
+    .unwrap();
+    let swept: Vec<SweptRequest> = from_json(res).unwrap();
+    assert_eq!(
+        swept,
+        vec![SweptRequest {
+            batch_id: 1,
+            user: "tom".to_string(),
+            amount: Uint128::new(90_000),
+            liquid_stake_amount: None,
+            settled: false,
+        }]
+    );
+
+    // tom has to claim from the treasury now
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("tom", &[]),
+        withdraw_msg,
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::WithdrawalSwept { batch_id: 1 })
+    ));
+
+    let settle_msg = ExecuteMsg::SettleSweptRequest {
+        batch_id: 1,
+        user: "tom".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("tom", &[]),
+        settle_msg.clone(),
+    );
+    assert!(matches!(res, Err(ContractError::Unauthorized { .. })));
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO1, &[]),
+        settle_msg.clone(),
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), env, mock_info(OSMO1, &[]), settle_msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::SweptRequestSettled { batch_id: 1 })
+    ));
+}
+
+#[test]
+fn swept_withdrawal_is_reported() {
+    let mut deps = init();
+    let mut env = mock_env();
+    received_batch(&mut deps, env.block.time.seconds());
+    set_unclaimed_sweep(&mut deps, SweepDestination::Treasury, true);
+
+    env.block.time = env.block.time.plus_seconds(1000);
+    let msg = ExecuteMsg::SweepUnclaimed {
+        batch_id: 1,
+        limit: Some(1),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "amount" && a.value == "40000"));
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(batch.swept_native_unstaked, Uint128::new(40_000));
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("bob", &[]),
+        ExecuteMsg::Withdraw { batch_id: 1 },
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::WithdrawalSwept { batch_id: 1 })
+    ));
+}
+
+#[test]
+fn sweep_unclaimed_restake() {
+    let mut deps = init();
+    let mut env = mock_env();
+    received_batch(&mut deps, env.block.time.seconds());
+    set_unclaimed_sweep(&mut deps, SweepDestination::Restake, false);
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::from(200_000u128);
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(1000);
+    let msg = ExecuteMsg::SweepUnclaimed {
+        batch_id: 1,
+        limit: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        msg.clone(),
+    );
+    assert!(matches!(res, Err(ContractError::Unauthorized { .. })));
+
+    let res = execute(deps.as_mut(), env, mock_info(OSMO3, &[]), msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "mint_amount" && a.value == "65000"));
+    assert!(res.messages.iter().any(|m| m.msg
+        == MsgMint {
+            sender: MOCK_CONTRACT_ADDR.to_string(),
+            amount: Some(Coin {
+                denom: "factory/cosmos2contract/stTIA".to_string(),
+                amount: "65000".to_string(),
+            }),
+            mint_to_address: OSMO1.to_string(),
+        }
+        .into()));
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_native_token, Uint128::from(330_000u128));
+    assert_eq!(state.total_liquid_stake_token, Uint128::from(165_000u128));
+
+    // the owners are owed their share of the minted stTIA
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SweptRequests {
+            user: "tom".to_string(),
+        },
+    )
+    .unwrap();
+    let swept: Vec<SweptRequest> = from_json(res).unwrap();
+    assert_eq!(swept[0].amount, Uint128::new(90_000));
+    assert_eq!(swept[0].liquid_stake_amount, Some(Uint128::new(45_000)));
+}
+
+#[test]
+fn sweep_skips_owners_without_treasury_claim() {
+    let mut deps = init();
+    let mut env = mock_env();
+    received_batch(&mut deps, env.block.time.seconds());
+    new_unstake_request(
+        &mut deps.as_mut(),
+        CELESTIA1.to_string(),
+        1,
+        Uint128::from(10_000u128),
+    )
+    .unwrap();
+    set_unclaimed_sweep(&mut deps, SweepDestination::Treasury, true);
+
+    env.block.time = env.block.time.plus_seconds(1000);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("alice", &[]),
+        ExecuteMsg::SweepUnclaimed {
+            batch_id: 1,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "requests" && a.value == "2"));
+
+    // the celestia owner can still have its withdrawal forwarded
+    assert!(BATCHES.has(&deps.storage, 1));
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SweptRequests {
+            user: CELESTIA1.to_string(),
+        },
+    )
+    .unwrap();
+    let swept: Vec<SweptRequest> = from_json(res).unwrap();
+    assert!(swept.is_empty());
+}
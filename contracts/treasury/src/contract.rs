@@ -4,17 +4,17 @@ use cosmwasm_std::{
     to_json_binary, to_json_string, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
 };
 use cw2::set_contract_version;
+use milky_way::ownership::{OwnershipMsg, PendingOwnership};
 use semver::Version;
 
 use crate::error::{ContractError, ContractResult};
 use crate::execute::{
-    execute_accept_ownership, execute_revoke_ownership_transfer, execute_spend_funds,
-    execute_swap_exact_amount_in, execute_swap_exact_amount_out, execute_transfer_ownership,
-    execute_update_config,
+    execute_claim_swept_withdrawal, execute_ownership, execute_spend_funds,
+    execute_swap_exact_amount_in, execute_swap_exact_amount_out, execute_update_config,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::query::query_config;
-use crate::state::{Config, State, ADMIN, CONFIG, STATE};
+use crate::query::{query_config, query_ownership};
+use crate::state::{Config, CONFIG, OWNERSHIP, STATE};
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -33,14 +33,7 @@ pub fn instantiate(
         .map(|admin_str| deps.api.addr_validate(&admin_str))
         .transpose()?
         .unwrap_or(info.sender.clone());
-    ADMIN.set(deps.branch(), Some(admin))?;
-
-    // Init State
-    let state = State {
-        pending_owner: None,
-        owner_transfer_min_time: None,
-    };
-    STATE.save(deps.storage, &state)?;
+    OWNERSHIP.set_owner(deps.branch(), Some(admin))?;
 
     // Init Config
     let config = Config {
@@ -50,6 +43,10 @@ pub fn instantiate(
             .transpose()?
             .unwrap_or(info.sender.clone()),
         allowed_swap_routes: msg.allowed_swap_routes,
+        staking_contract: msg
+            .staking_contract
+            .map(|staking_str| deps.api.addr_validate(&staking_str))
+            .transpose()?,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -71,12 +68,32 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::TransferOwnership { new_owner } => {
-            execute_transfer_ownership(deps, env, info, new_owner)
+        ExecuteMsg::TransferOwnership { new_owner } => execute_ownership(
+            deps,
+            env,
+            info,
+            OwnershipMsg::TransferOwnership { new_owner },
+        ),
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_ownership(deps, env, info, OwnershipMsg::AcceptOwnership {})
         }
-        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
         ExecuteMsg::RevokeOwnershipTransfer {} => {
-            execute_revoke_ownership_transfer(deps, env, info)
+            execute_ownership(deps, env, info, OwnershipMsg::RevokeOwnershipTransfer {})
+        }
+        ExecuteMsg::UpdateOwnershipConfig {
+            transfer_delay,
+            transfer_expiry,
+        } => execute_ownership(
+            deps,
+            env,
+            info,
+            OwnershipMsg::UpdateOwnershipConfig {
+                transfer_delay,
+                transfer_expiry,
+            },
+        ),
+        ExecuteMsg::RenounceOwnership { confirm } => {
+            execute_ownership(deps, env, info, OwnershipMsg::RenounceOwnership { confirm })
         }
         ExecuteMsg::SpendFunds {
             amount,
@@ -96,7 +113,11 @@ pub fn execute(
         ExecuteMsg::UpdateConfig {
             trader,
             allowed_swap_routes,
-        } => execute_update_config(deps, info, trader, allowed_swap_routes),
+            staking_contract,
+        } => execute_update_config(deps, info, trader, allowed_swap_routes, staking_contract),
+        ExecuteMsg::ClaimSweptWithdrawal { batch_id } => {
+            execute_claim_swept_withdrawal(deps, info, batch_id)
+        }
     }
 }
 
@@ -104,12 +125,13 @@ pub fn execute(
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Ownership {} => to_json_binary(&query_ownership(deps)?),
     }
     .map_err(ContractError::from)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
     let current_version = cw2::get_contract_version(deps.storage)?;
     if CONTRACT_NAME != current_version.contract.as_str() {
         return Err(StdError::generic_err("Cannot upgrade to a different contract").into());
@@ -130,6 +152,28 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Res
         return Err(StdError::generic_err("Cannot migrate to the same version.").into());
     }
 
+    // Move a pending ownership transfer to the ownership component
+    if let Some(state) = STATE.may_load(deps.storage)? {
+        if let Some(owner) = state.pending_owner {
+            let min_time = state.owner_transfer_min_time.unwrap_or(env.block.time);
+            let expiry = match state.owner_transfer_expiry {
+                Some(expiry) => expiry,
+                None => min_time
+                    .max(env.block.time)
+                    .plus_seconds(OWNERSHIP.config(deps.storage)?.transfer_expiry),
+            };
+            OWNERSHIP.set_pending(
+                deps.storage,
+                &PendingOwnership {
+                    owner,
+                    min_time,
+                    expiry,
+                },
+            )?;
+        }
+        STATE.remove(deps.storage);
+    }
+
     Ok(Response::new()
         .add_attribute("action", "migrate")
         .add_attribute("from_version", current_version.version)
@@ -1,5 +1,7 @@
-use cosmwasm_std::{StdError, Timestamp};
+use cosmwasm_std::StdError;
 use cw_controllers::AdminError;
+use milky_way::ownership::OwnershipError;
+use milky_way::validation::InvalidField;
 use thiserror::Error;
 
 pub type ContractResult<T> = core::result::Result<T, ContractError>;
@@ -16,11 +18,11 @@ pub enum ContractError {
     #[error("Admin error: {0}")]
     Admin(#[from] AdminError),
 
-    #[error("No pending owner")]
-    NoPendingOwner {},
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
 
-    #[error("Ownership transfer not ready")]
-    OwnershipTransferNotReady { time_to_claim: Timestamp },
+    #[error("Invalid {field}: {reason}")]
+    InvalidConfigField { field: String, reason: String },
 
     #[error("Swap root not allowed")]
     SwapRouteNotAllowed {},
@@ -30,4 +32,19 @@ pub enum ContractError {
 
     #[error("Invalid token out denom {denom}")]
     InvalidTokenOutDenom { denom: String },
+
+    #[error("Staking contract not set")]
+    StakingContractNotSet {},
+
+    #[error("No swept withdrawal to claim in batch {batch_id}")]
+    NoSweptWithdrawal { batch_id: u64 },
+}
+
+impl From<InvalidField> for ContractError {
+    fn from(err: InvalidField) -> Self {
+        ContractError::InvalidConfigField {
+            field: err.field,
+            reason: err.reason,
+        }
+    }
 }
@@ -1,97 +1,35 @@
 use cosmwasm_std::{
-    attr, to_json_string, CosmosMsg, DepsMut, Env, MessageInfo, Response, Timestamp,
+    attr, to_json_binary, to_json_string, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response,
+    Timestamp, WasmMsg,
 };
+use milky_way::events::{MilkyWayEvent, TreasuryClaimEvent, TreasurySpendEvent, TreasurySwapEvent};
+use milky_way::ownership::OwnershipMsg;
+use milky_way::validation::invalid_field;
 use osmosis_std::types::{
     cosmos::base::v1beta1::Coin, ibc::applications::transfer::v1::MsgTransfer,
 };
+use staking::msg::{
+    ConfigResponse as StakingConfigResponse, ExecuteMsg as StakingExecuteMsg,
+    QueryMsg as StakingQueryMsg,
+};
+use staking::state::SweptRequest;
 
 use crate::{
     error::{ContractError, ContractResult},
     helpers::validate_address,
-    state::{State, SwapRoute, ADMIN, CONFIG, STATE},
+    state::{SwapRoute, CONFIG, OWNERSHIP},
 };
 
 pub const IBC_TIMEOUT: Timestamp = Timestamp::from_nanos(1000000000000); // TODO: Placeholder value for IBC timeout
 
-// Transfer ownership to another account; callable by the owner
-// This will require the new owner to accept to take effect.
-// No need to handle case of overwriting the pending owner
-// Ownership can only be claimed after 7 days to mitigate fat finger errors
-pub fn execute_transfer_ownership(
+// Ownership messages are handled by the shared ownership component
+pub fn execute_ownership(
     deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    new_owner: String,
-) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
-
-    let mut state: State = STATE.load(deps.storage)?;
-    state.pending_owner = Some(deps.api.addr_validate(&new_owner)?);
-    state.owner_transfer_min_time = Some(Timestamp::from_seconds(
-        _env.block.time.seconds() + 60 * 60 * 24 * 7,
-    )); // 7 days
-
-    STATE.save(deps.storage, &state)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "transfer_ownership")
-        .add_attribute("new_owner", new_owner)
-        .add_attribute("previous_owner", info.sender))
-}
-
-// Revoke transfer ownership, callable by the owner
-pub fn execute_revoke_ownership_transfer(
-    deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
+    msg: OwnershipMsg,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
-
-    let mut state = STATE.load(deps.storage)?;
-    state.pending_owner = None;
-    state.owner_transfer_min_time = None;
-
-    STATE.save(deps.storage, &state)?;
-
-    Ok(Response::new().add_attribute("action", "revoke_ownership_transfer"))
-}
-
-pub fn execute_accept_ownership(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-) -> ContractResult<Response> {
-    let mut state: State = STATE.load(deps.storage)?;
-    if state.owner_transfer_min_time.is_some()
-        && state.owner_transfer_min_time.unwrap().seconds() > _env.block.time.seconds()
-    {
-        return Err(ContractError::OwnershipTransferNotReady {
-            time_to_claim: Timestamp::from_seconds(
-                state.owner_transfer_min_time.unwrap().seconds(),
-            ),
-        });
-    }
-
-    let new_owner = {
-        match state.pending_owner {
-            Some(pending_owner) if pending_owner == info.sender => {
-                state.pending_owner = None;
-                STATE.save(deps.storage, &state)?;
-                Some(pending_owner)
-            }
-            _ => None,
-        }
-    };
-
-    match new_owner {
-        Some(pending_owner) => {
-            ADMIN.set(deps, Some(pending_owner))?;
-            Ok(Response::new()
-                .add_attribute("action", "accept_ownership")
-                .add_attribute("new_owner", info.sender))
-        }
-        None => Err(ContractError::NoPendingOwner {}),
-    }
+    Ok(OWNERSHIP.execute(deps, env, info, msg)?)
 }
 
 pub fn execute_spend_funds(
@@ -102,7 +40,7 @@ pub fn execute_spend_funds(
     receiver: String,
     channel_id: Option<String>,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    OWNERSHIP.assert_owner(deps.as_ref(), &info.sender)?;
 
     let msg_send: CosmosMsg = if channel_id.is_none() {
         validate_address(&receiver, "osmo")?;
@@ -138,12 +76,18 @@ pub fn execute_spend_funds(
     ];
 
     if channel_id.clone().is_some() {
-        attributes.push(attr("channel_id", channel_id.unwrap().clone()));
+        attributes.push(attr("channel_id", channel_id.clone().unwrap()));
     }
 
+    let event = TreasurySpendEvent {
+        receiver,
+        amount,
+        channel_id,
+    };
     let res = Response::new()
         .add_message(msg_send)
-        .add_attributes(attributes);
+        .add_attributes(attributes)
+        .add_event(event.to_event());
     Ok(res)
 }
 
@@ -184,12 +128,20 @@ pub fn execute_swap_exact_amount_in(
         token_out_min_amount: token_out_min_amount.to_string(),
     };
 
+    let event = TreasurySwapEvent {
+        trader: info.sender.clone(),
+        swap_type: "exact_amount_in".to_string(),
+        pool_ids: swap_routes.iter().map(|route| route.pool_id).collect(),
+        token: token_in.clone(),
+        limit: token_out_min_amount.into(),
+    };
     Ok(Response::new()
         .add_attribute("action", "swap_exact_amount_in")
         .add_attribute("sender", info.sender)
         .add_attribute("routes", to_json_string(&swap_routes)?)
         .add_attribute("token_in", token_in.to_string())
         .add_attribute("token_out_min_amount", token_out_min_amount.to_string())
+        .add_event(event.to_event())
         .add_message(message))
 }
 
@@ -230,12 +182,20 @@ pub fn execute_swap_exact_amount_out(
         token_in_max_amount: token_in_max_amount.to_string(),
     };
 
+    let event = TreasurySwapEvent {
+        trader: info.sender.clone(),
+        swap_type: "exact_amount_out".to_string(),
+        pool_ids: swap_routes.iter().map(|route| route.pool_id).collect(),
+        token: token_out.clone(),
+        limit: token_in_max_amount.into(),
+    };
     Ok(Response::new()
-        .add_attribute("action", "swap_exact_amount_in")
+        .add_attribute("action", "swap_exact_amount_out")
         .add_attribute("sender", info.sender)
         .add_attribute("routes", to_json_string(&swap_routes)?)
         .add_attribute("token_out", token_out.to_string())
         .add_attribute("token_in_max_amount", token_in_max_amount.to_string())
+        .add_event(event.to_event())
         .add_message(message))
 }
 
@@ -244,8 +204,9 @@ pub fn execute_update_config(
     info: MessageInfo,
     trader: Option<String>,
     routes: Option<Vec<Vec<SwapRoute>>>,
+    staking_contract: Option<String>,
 ) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    OWNERSHIP.assert_owner(deps.as_ref(), &info.sender)?;
 
     let mut response = Response::new()
         .add_attribute("action", "update_config")
@@ -253,14 +214,112 @@ pub fn execute_update_config(
 
     let mut config = CONFIG.load(deps.storage)?;
     if let Some(trader) = trader {
-        config.trader = deps.api.addr_validate(&trader)?;
-        response = response.add_attribute("trader", trader);
+        let trader = deps
+            .api
+            .addr_validate(&trader)
+            .map_err(invalid_field("trader"))?;
+        if trader != config.trader {
+            response = response
+                .add_attribute("old_trader", &config.trader)
+                .add_attribute("new_trader", &trader);
+        }
+        config.trader = trader;
     }
     if let Some(routes) = routes {
-        response = response.add_attribute("allowed_routes", to_json_string(&routes)?);
+        if routes != config.allowed_swap_routes {
+            response = response
+                .add_attribute(
+                    "old_allowed_routes",
+                    to_json_string(&config.allowed_swap_routes)?,
+                )
+                .add_attribute("new_allowed_routes", to_json_string(&routes)?);
+        }
         config.allowed_swap_routes = routes;
     }
+    if let Some(staking_contract) = staking_contract {
+        let staking_contract = deps
+            .api
+            .addr_validate(&staking_contract)
+            .map_err(invalid_field("staking_contract"))?;
+        if config.staking_contract.as_ref() != Some(&staking_contract) {
+            response = response
+                .add_attribute(
+                    "old_staking_contract",
+                    config
+                        .staking_contract
+                        .as_ref()
+                        .map(Addr::to_string)
+                        .unwrap_or_default(),
+                )
+                .add_attribute("new_staking_contract", &staking_contract);
+        }
+        config.staking_contract = Some(staking_contract);
+    }
     CONFIG.save(deps.storage, &config)?;
 
     Ok(response)
 }
+
+// Pays a withdrawal that the staking contract swept to the treasury. The swept request
+// is settled in the staking contract in the same transaction so it can't be paid twice
+pub fn execute_claim_swept_withdrawal(
+    deps: DepsMut,
+    info: MessageInfo,
+    batch_id: u64,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking_contract = config
+        .staking_contract
+        .ok_or(ContractError::StakingContractNotSet {})?;
+
+    let swept_requests: Vec<SweptRequest> = deps.querier.query_wasm_smart(
+        &staking_contract,
+        &StakingQueryMsg::SweptRequests {
+            user: info.sender.to_string(),
+        },
+    )?;
+    let swept = swept_requests
+        .into_iter()
+        .find(|r| r.batch_id == batch_id && !r.settled)
+        .ok_or(ContractError::NoSweptWithdrawal { batch_id })?;
+
+    let staking_config: StakingConfigResponse = deps
+        .querier
+        .query_wasm_smart(&staking_contract, &StakingQueryMsg::Config {})?;
+
+    // restaked sweeps are paid in the stTIA the treasury received for them
+    let payment = match swept.liquid_stake_amount {
+        Some(amount) => {
+            cosmwasm_std::Coin::new(amount.u128(), staking_config.liquid_stake_token_denom)
+        }
+        None => cosmwasm_std::Coin::new(swept.amount.u128(), staking_config.native_token_denom),
+    };
+
+    let settle_msg = WasmMsg::Execute {
+        contract_addr: staking_contract.to_string(),
+        msg: to_json_binary(&StakingExecuteMsg::SettleSweptRequest {
+            batch_id,
+            user: info.sender.to_string(),
+        })?,
+        funds: vec![],
+    };
+
+    let event = TreasuryClaimEvent {
+        receiver: info.sender.clone(),
+        batch_id,
+        amount: payment.amount,
+        denom: payment.denom.clone(),
+    };
+    Ok(Response::new()
+        .add_attribute("action", "claim_swept_withdrawal")
+        .add_attribute("receiver", info.sender.to_string())
+        .add_attribute("batch_id", batch_id.to_string())
+        .add_attribute("amount", payment.amount)
+        .add_attribute("denom", payment.denom.clone())
+        .add_event(event.to_event())
+        .add_message(settle_msg)
+        .add_message(cosmwasm_std::BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![payment],
+        }))
+}
@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Coin};
+use milky_way::ownership::OwnershipResponse;
 
 use crate::state::SwapRoute;
 
@@ -8,6 +9,7 @@ pub struct InstantiateMsg {
     pub admin: Option<String>,
     pub trader: Option<String>,
     pub allowed_swap_routes: Vec<Vec<SwapRoute>>,
+    pub staking_contract: Option<String>,
 }
 
 #[cw_serde]
@@ -17,6 +19,17 @@ pub enum ExecuteMsg {
     },
     AcceptOwnership {},
     RevokeOwnershipTransfer {},
+    /// Sets how long a pending owner has to wait before accepting
+    /// the ownership, and how long they can accept it for.
+    UpdateOwnershipConfig {
+        transfer_delay: Option<u64>,
+        transfer_expiry: Option<u64>,
+    },
+    /// Removes the owner for good.
+    RenounceOwnership {
+        /// Has to be the contract address.
+        confirm: String,
+    },
     SpendFunds {
         amount: Coin,
         receiver: String,
@@ -52,6 +65,15 @@ pub enum ExecuteMsg {
         /// Optional new allowed swap routes.
         /// If `None`, the allowed swap routes will not change.
         allowed_swap_routes: Option<Vec<Vec<SwapRoute>>>,
+        /// Optional new staking contract address.
+        /// If `None`, the staking contract will not change.
+        staking_contract: Option<String>,
+    },
+    /// Pays the sender's withdrawal from a batch that the staking
+    /// contract swept to the treasury after its claim window.
+    ClaimSweptWithdrawal {
+        /// The batch the withdrawal was swept from.
+        batch_id: u64,
     },
 }
 
@@ -60,13 +82,17 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
+    #[returns(OwnershipResponse)]
+    Ownership {},
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
-    pub admin: Addr,
+    /// `None` once the ownership was renounced.
+    pub admin: Option<Addr>,
     pub trader: Addr,
     pub allowed_swap_routes: Vec<Vec<SwapRoute>>,
+    pub staking_contract: Option<Addr>,
 }
 
 #[cw_serde]
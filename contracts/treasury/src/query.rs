@@ -1,20 +1,23 @@
 use cosmwasm_std::Deps;
+use milky_way::ownership::OwnershipResponse;
 
 use crate::{
     error::ContractResult,
     msg::ConfigResponse,
-    state::{ADMIN, CONFIG},
+    state::{CONFIG, OWNERSHIP},
 };
 
 pub fn query_config(deps: Deps) -> ContractResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
-    let admin = ADMIN
-        .get(deps)?
-        .expect("admin not present in the contract state");
 
     Ok(ConfigResponse {
-        admin,
+        admin: OWNERSHIP.owner(deps)?,
         trader: config.trader,
         allowed_swap_routes: config.allowed_swap_routes,
+        staking_contract: config.staking_contract,
     })
 }
+
+pub fn query_ownership(deps: Deps) -> ContractResult<OwnershipResponse> {
+    Ok(OWNERSHIP.query(deps)?)
+}
@@ -1,19 +1,23 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
 use cosmwasm_std::Timestamp;
-use cw_controllers::Admin;
 use cw_storage_plus::Item;
+use milky_way::ownership::Ownership;
 
 use crate::error::ContractError;
 use crate::error::ContractResult;
 
+/// Deprecated, only read to move a pending transfer to `OWNERSHIP` when migrating
 #[cw_serde]
 pub struct State {
     pub pending_owner: Option<Addr>,
     pub owner_transfer_min_time: Option<Timestamp>,
+    /// A pending ownership transfer lapses if not accepted by then
+    #[serde(default)]
+    pub owner_transfer_expiry: Option<Timestamp>,
 }
 
-pub const ADMIN: Admin = Admin::new("admin");
+pub const OWNERSHIP: Ownership = Ownership::new("admin", "pending_ownership", "ownership_config");
 pub const STATE: Item<State> = Item::new("state");
 
 #[cw_serde]
@@ -32,6 +36,9 @@ pub struct Config {
     pub trader: Addr,
     /// List of allowed swap routes that can be taken when performing a SwapExactAmountIn.
     pub allowed_swap_routes: Vec<Vec<SwapRoute>>,
+    /// Staking contract whose swept withdrawals are paid by the treasury.
+    #[serde(default)]
+    pub staking_contract: Option<Addr>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
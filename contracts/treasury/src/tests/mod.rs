@@ -1,5 +1,6 @@
 mod ownership_tests;
 mod send_tests;
 mod swap_tests;
+mod swept_withdrawal_tests;
 mod test_helper;
 mod update_config_test;
@@ -1,9 +1,12 @@
 
-use crate::contract::execute;
-use crate::msg::ExecuteMsg;
+use crate::contract::{execute, migrate, query};
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, ExecuteMsg, MigrateMsg, QueryMsg};
+use crate::state::{State, OWNERSHIP, STATE};
 use crate::tests::test_helper::{init, ADMIN};
-use cosmwasm_std::coins;
 use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, from_json, Addr};
+use milky_way::ownership::{OwnershipError, OwnershipResponse};
 
 #[test]
 fn proper_transfer_ownership() {
@@ -111,3 +114,98 @@ fn non_admin_revoke_ownership_transfer() {
 
     assert!(res2.is_err());
 }
+
+#[test]
+fn ownership_transfer_expiry() {
+    let mut deps = init();
+    let info = mock_info(ADMIN, &[]);
+
+    let msg = ExecuteMsg::UpdateOwnershipConfig {
+        transfer_delay: None,
+        transfer_expiry: Some(0),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        res,
+        Err(ContractError::Ownership(OwnershipError::InvalidConfig {
+            min: 60 * 60 * 24
+        }))
+    );
+
+    let msg = ExecuteMsg::UpdateOwnershipConfig {
+        transfer_delay: Some(60 * 60 * 24),
+        transfer_expiry: Some(60 * 60),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::TransferOwnership {
+        new_owner: "new_owner".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Ownership {}).unwrap();
+    let ownership: OwnershipResponse = from_json(res).unwrap();
+    let claimable_at = mock_env().block.time.plus_seconds(60 * 60 * 24);
+    let expiry = claimable_at.plus_seconds(60 * 60);
+    assert_eq!(ownership.pending_owner, Some(Addr::unchecked("new_owner")));
+    assert_eq!(ownership.owner_transfer_min_time, Some(claimable_at));
+    assert_eq!(ownership.owner_transfer_expiry, Some(expiry));
+
+    let mut env = mock_env();
+    env.block.time = expiry;
+    let msg = ExecuteMsg::AcceptOwnership {};
+    let res = execute(deps.as_mut(), env, mock_info("new_owner", &[]), msg);
+    assert_eq!(
+        res,
+        Err(ContractError::Ownership(OwnershipError::TransferExpired {
+            expired_at: expiry
+        }))
+    );
+}
+
+#[test]
+fn renounce_ownership() {
+    let mut deps = init();
+    let info = mock_info(ADMIN, &[]);
+
+    let msg = ExecuteMsg::RenounceOwnership {
+        confirm: ADMIN.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert!(matches!(
+        res,
+        Err(ContractError::Ownership(
+            OwnershipError::RenounceNotAllowed { .. }
+        ))
+    ));
+
+    let msg = ExecuteMsg::RenounceOwnership {
+        confirm: mock_env().contract.address.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_json(res).unwrap();
+    assert_eq!(config.admin, None);
+}
+
+#[test]
+fn migrate_pending_ownership() {
+    let mut deps = init();
+    let min_time = mock_env().block.time.plus_seconds(60);
+    let state = State {
+        pending_owner: Some(Addr::unchecked("new_owner")),
+        owner_transfer_min_time: Some(min_time),
+        owner_transfer_expiry: None,
+    };
+    STATE.save(&mut deps.storage, &state).unwrap();
+    cw2::set_contract_version(&mut deps.storage, "treasury", "0.0.1").unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let pending = OWNERSHIP.pending(&deps.storage).unwrap().unwrap();
+    assert_eq!(pending.owner, Addr::unchecked("new_owner"));
+    assert_eq!(pending.min_time, min_time);
+    assert_eq!(pending.expiry, min_time.plus_seconds(7 * 24 * 60 * 60));
+    assert_eq!(STATE.may_load(&deps.storage).unwrap(), None);
+}
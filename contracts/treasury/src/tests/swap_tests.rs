@@ -469,6 +469,18 @@ fn swap_amount_out() {
     let env = mock_env();
     let response = execute(deps.as_mut(), env.clone(), mock_info(TRADER, &[]), msg).unwrap();
 
+    assert_eq!(response.attributes[0].value, "swap_exact_amount_out");
+    let event = &response.events[0];
+    assert_eq!(event.ty, "milkyway-treasury_swap");
+    assert!(event
+        .attributes
+        .iter()
+        .any(|a| a.key == "swap_type" && a.value == "exact_amount_out"));
+    assert!(event
+        .attributes
+        .iter()
+        .any(|a| a.key == "token" && a.value == format!("1000{USDC_DENOM}")));
+
     let messages = response.messages;
     assert_eq!(1, messages.len());
     assert_eq!(
@@ -0,0 +1,158 @@
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::tests::test_helper::{init, STAKING, TIA_DENOM, TRADER};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, BankMsg, ContractResult, CosmosMsg, OwnedDeps, SystemResult,
+    Uint128, WasmMsg, WasmQuery,
+};
+use staking::msg::{
+    ConfigResponse as StakingConfigResponse, ExecuteMsg as StakingExecuteMsg,
+    QueryMsg as StakingQueryMsg,
+};
+use staking::state::SweptRequest;
+
+const ST_TIA_DENOM: &str = "factory/staking/stTIA";
+
+fn mock_swept_requests(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    swept_requests: Vec<SweptRequest>,
+) {
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart { contract_addr, msg } = query else {
+            panic!("unexpected query {:?}", query);
+        };
+        assert_eq!(contract_addr, STAKING);
+        let res = match from_json(msg).unwrap() {
+            StakingQueryMsg::Config {} => to_json_binary(&StakingConfigResponse {
+                native_token_denom: TIA_DENOM.to_string(),
+                liquid_stake_token_denom: ST_TIA_DENOM.to_string(),
+                ..Default::default()
+            }),
+            StakingQueryMsg::SweptRequests { user } => to_json_binary(
+                &swept_requests
+                    .iter()
+                    .filter(|r| r.user == user)
+                    .collect::<Vec<_>>(),
+            ),
+            _ => panic!("unexpected query {:?}", msg),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+}
+
+#[test]
+fn claim_swept_withdrawal() {
+    let mut deps = init();
+    mock_swept_requests(
+        &mut deps,
+        vec![
+            SweptRequest {
+                batch_id: 1,
+                user: TRADER.to_string(),
+                amount: Uint128::from(1_000u128),
+                liquid_stake_amount: None,
+                settled: true,
+            },
+            SweptRequest {
+                batch_id: 2,
+                user: TRADER.to_string(),
+                amount: Uint128::from(2_000u128),
+                liquid_stake_amount: None,
+                settled: false,
+            },
+        ],
+    );
+
+    // already settled
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TRADER, &[]),
+        ExecuteMsg::ClaimSweptWithdrawal { batch_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoSweptWithdrawal { batch_id: 1 });
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TRADER, &[]),
+        ExecuteMsg::ClaimSweptWithdrawal { batch_id: 2 },
+    )
+    .unwrap();
+
+    let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(
+        messages,
+        vec![
+            WasmMsg::Execute {
+                contract_addr: STAKING.to_string(),
+                msg: to_json_binary(&StakingExecuteMsg::SettleSweptRequest {
+                    batch_id: 2,
+                    user: TRADER.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            BankMsg::Send {
+                to_address: TRADER.to_string(),
+                amount: coins(2_000, TIA_DENOM),
+            }
+            .into(),
+        ]
+    );
+}
+
+#[test]
+fn claim_restaked_swept_withdrawal() {
+    let mut deps = init();
+    mock_swept_requests(
+        &mut deps,
+        vec![SweptRequest {
+            batch_id: 1,
+            user: TRADER.to_string(),
+            amount: Uint128::from(2_000u128),
+            liquid_stake_amount: Some(Uint128::from(1_000u128)),
+            settled: false,
+        }],
+    );
+
+    // paid in the stTIA minted for the restaked tokens
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TRADER, &[]),
+        ExecuteMsg::ClaimSweptWithdrawal { batch_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        BankMsg::Send {
+            to_address: TRADER.to_string(),
+            amount: coins(1_000, ST_TIA_DENOM),
+        }
+        .into()
+    );
+}
+
+#[test]
+fn claim_swept_withdrawal_without_staking_contract() {
+    let mut deps = init();
+    let mut config = crate::state::CONFIG.load(&deps.storage).unwrap();
+    config.staking_contract = None;
+    crate::state::CONFIG
+        .save(&mut deps.storage, &config)
+        .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TRADER, &[]),
+        ExecuteMsg::ClaimSweptWithdrawal { batch_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StakingContractNotSet {});
+}
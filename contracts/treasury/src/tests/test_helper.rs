@@ -15,6 +15,7 @@ pub static OSMO_USDC_POOL: u64 = 3;
 pub static TIA_DENOM: &str = "utia";
 pub static USDC_DENOM: &str = "uusdc";
 pub static OSMO_DENOM: &str = "uosmo";
+pub static STAKING: &str = "staking";
 
 pub fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
     let mut deps = mock_dependencies();
@@ -42,6 +43,7 @@ pub fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
                 },
             ],
         ],
+        staking_contract: Some(STAKING.to_string()),
     };
     let info = mock_info(ADMIN, &coins(1000, "uosmo"));
 
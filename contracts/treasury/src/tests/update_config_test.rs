@@ -15,6 +15,7 @@ fn update_config_not_admin_fail() {
     let update_config = ExecuteMsg::UpdateConfig {
         allowed_swap_routes: Some(vec![]),
         trader: Some(new_trader.to_string()),
+        staking_contract: None,
     };
     let err = execute(
         deps.as_mut(),
@@ -35,16 +36,45 @@ fn update_config() {
     let update_config = ExecuteMsg::UpdateConfig {
         allowed_swap_routes: Some(vec![]),
         trader: Some(new_trader.to_string()),
+        staking_contract: None,
     };
-    execute(
+    let res = execute(
         deps.as_mut(),
         mock_env(),
         mock_info(ADMIN, &[]),
         update_config,
     )
     .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "new_trader" && a.value == new_trader.as_str()));
+    assert!(res.attributes.iter().any(|a| a.key == "old_trader"));
 
     let config = CONFIG.load(&deps.storage).unwrap();
     assert_eq!(new_trader, config.trader);
     assert!(config.allowed_swap_routes.is_empty());
 }
+
+#[test]
+fn update_config_invalid_field() {
+    let mut deps = init();
+
+    let update_config = ExecuteMsg::UpdateConfig {
+        allowed_swap_routes: None,
+        trader: None,
+        staking_contract: Some("Staking".to_string()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        update_config,
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        ContractError::InvalidConfigField { field, .. } if field == "staking_contract"
+    ));
+}
@@ -0,0 +1,351 @@
+use cosmwasm_std::{Addr, Attribute, Coin, Event, Uint128};
+
+/// Version of the event types and attributes below, bumped on breaking changes
+pub const EVENT_SCHEMA_VERSION: &str = "1";
+
+/// Prefix of the event types, wasmd emits them as `wasm-milkyway-<name>`
+pub const EVENT_TYPE_PREFIX: &str = "milkyway";
+
+pub trait MilkyWayEvent {
+    /// Name of the event type, after the `milkyway-` prefix
+    const NAME: &'static str;
+
+    fn attributes(&self) -> Vec<Attribute>;
+
+    fn to_event(&self) -> Event {
+        Event::new(format!("{EVENT_TYPE_PREFIX}-{}", Self::NAME))
+            .add_attribute("schema_version", EVENT_SCHEMA_VERSION)
+            .add_attributes(self.attributes())
+    }
+}
+
+fn optional(attrs: &mut Vec<Attribute>, key: &str, value: Option<impl ToString>) {
+    if let Some(value) = value {
+        attrs.push(Attribute::new(key, value.to_string()));
+    }
+}
+
+fn coins(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(Coin::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Native tokens staked for liquid stake tokens
+#[derive(Clone, Debug, PartialEq)]
+pub struct StakeEvent {
+    pub sender: Addr,
+    pub recipient: String,
+    pub amount: Uint128,
+    pub mint_amount: Uint128,
+    pub deposit_fee: Uint128,
+    pub forward_channel: Option<String>,
+    pub forward_receiver: Option<String>,
+}
+
+impl MilkyWayEvent for StakeEvent {
+    const NAME: &'static str = "stake";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attrs = vec![
+            Attribute::new("sender", &self.sender),
+            Attribute::new("recipient", &self.recipient),
+            Attribute::new("amount", self.amount),
+            Attribute::new("mint_amount", self.mint_amount),
+            Attribute::new("deposit_fee", self.deposit_fee),
+        ];
+        optional(&mut attrs, "forward_channel", self.forward_channel.as_ref());
+        optional(
+            &mut attrs,
+            "forward_receiver",
+            self.forward_receiver.as_ref(),
+        );
+        attrs
+    }
+}
+
+/// Liquid stake tokens added to the pending batch
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnstakeEvent {
+    pub sender: Addr,
+    pub owner: String,
+    pub batch_id: u64,
+    pub amount: Uint128,
+}
+
+impl MilkyWayEvent for UnstakeEvent {
+    const NAME: &'static str = "unstake";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("sender", &self.sender),
+            Attribute::new("owner", &self.owner),
+            Attribute::new("batch_id", self.batch_id.to_string()),
+            Attribute::new("amount", self.amount),
+        ]
+    }
+}
+
+/// Pending batch submitted for unbonding
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmitBatchEvent {
+    pub batch_id: u64,
+    pub batch_total: Uint128,
+    pub expected_native_unstaked: Uint128,
+    pub keeper: Addr,
+    pub keeper_reward: Uint128,
+}
+
+impl MilkyWayEvent for SubmitBatchEvent {
+    const NAME: &'static str = "submit_batch";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("batch_id", self.batch_id.to_string()),
+            Attribute::new("batch_total", self.batch_total),
+            Attribute::new("expected_native_unstaked", self.expected_native_unstaked),
+            Attribute::new("keeper", &self.keeper),
+            Attribute::new("keeper_reward", self.keeper_reward),
+        ]
+    }
+}
+
+/// Unbonded native tokens of a batch received back from the staker
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceiveUnstakedEvent {
+    pub batch_id: u64,
+    pub amount: Uint128,
+}
+
+impl MilkyWayEvent for ReceiveUnstakedEvent {
+    const NAME: &'static str = "receive_unstaked";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("batch_id", self.batch_id.to_string()),
+            Attribute::new("amount", self.amount),
+        ]
+    }
+}
+
+/// Withdrawal of an unstake request, sent to the owner or forwarded over IBC
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithdrawEvent {
+    pub owner: String,
+    pub batch_id: u64,
+    pub amount: Uint128,
+    pub redemption_fee: Uint128,
+    pub forwarded: bool,
+}
+
+impl MilkyWayEvent for WithdrawEvent {
+    const NAME: &'static str = "withdraw";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("owner", &self.owner),
+            Attribute::new("batch_id", self.batch_id.to_string()),
+            Attribute::new("amount", self.amount),
+            Attribute::new("redemption_fee", self.redemption_fee),
+            Attribute::new("forwarded", self.forwarded.to_string()),
+        ]
+    }
+}
+
+/// Staking rewards received from the reward collector
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardsEvent {
+    pub amount: Uint128,
+    pub amount_after_fees: Uint128,
+    pub fee: Uint128,
+    pub fee_mint_amount: Uint128,
+    pub extra_rewards: Vec<Coin>,
+}
+
+impl MilkyWayEvent for RewardsEvent {
+    const NAME: &'static str = "rewards";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("amount", self.amount),
+            Attribute::new("amount_after_fees", self.amount_after_fees),
+            Attribute::new("fee", self.fee),
+            Attribute::new("fee_mint_amount", self.fee_mint_amount),
+            Attribute::new("extra_rewards", coins(&self.extra_rewards)),
+        ]
+    }
+}
+
+/// Failed or timed out packets fused into a new transfer
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoverEvent {
+    pub sender: Addr,
+    pub packets: u64,
+    pub amount: Uint128,
+    pub forced: bool,
+}
+
+impl MilkyWayEvent for RecoverEvent {
+    const NAME: &'static str = "recover";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("sender", &self.sender),
+            Attribute::new("packets", self.packets.to_string()),
+            Attribute::new("amount", self.amount),
+            Attribute::new("forced", self.forced.to_string()),
+        ]
+    }
+}
+
+/// Change to the owner or the pending ownership transfer
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnershipEvent {
+    pub action: String,
+    pub sender: Addr,
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+}
+
+impl MilkyWayEvent for OwnershipEvent {
+    const NAME: &'static str = "ownership";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attrs = vec![
+            Attribute::new("action", &self.action),
+            Attribute::new("sender", &self.sender),
+        ];
+        optional(&mut attrs, "owner", self.owner.as_ref());
+        optional(&mut attrs, "pending_owner", self.pending_owner.as_ref());
+        attrs
+    }
+}
+
+/// Treasury funds sent to an address, over IBC if a channel is set
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreasurySpendEvent {
+    pub receiver: String,
+    pub amount: Coin,
+    pub channel_id: Option<String>,
+}
+
+impl MilkyWayEvent for TreasurySpendEvent {
+    const NAME: &'static str = "treasury_spend";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attrs = vec![
+            Attribute::new("receiver", &self.receiver),
+            Attribute::new("amount", self.amount.to_string()),
+        ];
+        optional(&mut attrs, "channel_id", self.channel_id.as_ref());
+        attrs
+    }
+}
+
+/// Treasury swap by the trader; `limit` is the minimum out or maximum in amount
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreasurySwapEvent {
+    pub trader: Addr,
+    pub swap_type: String,
+    pub pool_ids: Vec<u64>,
+    pub token: Coin,
+    pub limit: Uint128,
+}
+
+impl MilkyWayEvent for TreasurySwapEvent {
+    const NAME: &'static str = "treasury_swap";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let pool_ids = self
+            .pool_ids
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        vec![
+            Attribute::new("trader", &self.trader),
+            Attribute::new("swap_type", &self.swap_type),
+            Attribute::new("pool_ids", pool_ids),
+            Attribute::new("token", self.token.to_string()),
+            Attribute::new("limit", self.limit),
+        ]
+    }
+}
+
+/// Swept withdrawal paid by the treasury
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreasuryClaimEvent {
+    pub receiver: Addr,
+    pub batch_id: u64,
+    pub amount: Uint128,
+    /// Native denom, or the stTIA denom for restaked sweeps
+    pub denom: String,
+}
+
+impl MilkyWayEvent for TreasuryClaimEvent {
+    const NAME: &'static str = "treasury_claim";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("receiver", &self.receiver),
+            Attribute::new("batch_id", self.batch_id.to_string()),
+            Attribute::new("amount", self.amount),
+            Attribute::new("denom", &self.denom),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::coin;
+
+    #[test]
+    fn event_type_and_schema_version() {
+        let event = ReceiveUnstakedEvent {
+            batch_id: 1,
+            amount: Uint128::new(100),
+        }
+        .to_event();
+
+        assert_eq!(event.ty, "milkyway-receive_unstaked");
+        assert_eq!(
+            event.attributes,
+            vec![
+                Attribute::new("schema_version", EVENT_SCHEMA_VERSION),
+                Attribute::new("batch_id", "1"),
+                Attribute::new("amount", "100"),
+            ]
+        );
+    }
+
+    #[test]
+    fn optional_attributes() {
+        let event = TreasurySpendEvent {
+            receiver: "osmo1receiver".to_string(),
+            amount: coin(10, "uosmo"),
+            channel_id: None,
+        }
+        .to_event();
+        assert!(!event.attributes.iter().any(|a| a.key == "channel_id"));
+        assert!(event
+            .attributes
+            .iter()
+            .any(|a| a.key == "amount" && a.value == "10uosmo"));
+
+        let event = RewardsEvent {
+            amount: Uint128::new(100),
+            amount_after_fees: Uint128::new(90),
+            fee: Uint128::new(10),
+            fee_mint_amount: Uint128::zero(),
+            extra_rewards: vec![coin(1, "uatom"), coin(2, "uosmo")],
+        }
+        .to_event();
+        assert!(event
+            .attributes
+            .iter()
+            .any(|a| a.key == "extra_rewards" && a.value == "1uatom,2uosmo"));
+    }
+}
@@ -1 +1,4 @@
+pub mod events;
+pub mod ownership;
 pub mod staking;
+pub mod validation;
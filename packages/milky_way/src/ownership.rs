@@ -0,0 +1,456 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage, Timestamp,
+};
+use cw_controllers::{Admin, AdminError};
+use cw_storage_plus::Item;
+use thiserror::Error;
+
+use crate::events::{MilkyWayEvent, OwnershipEvent};
+
+// Ownership transfers can't be accepted sooner than this
+pub const MIN_TRANSFER_DELAY: u64 = 24 * 60 * 60;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OwnershipError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Admin error: {0}")]
+    Admin(#[from] AdminError),
+
+    #[error("No pending owner")]
+    NoPendingOwner {},
+
+    #[error("Ownership transfer not ready")]
+    TransferNotReady { time_to_claim: Timestamp },
+
+    #[error("Ownership transfer expired at {expired_at}")]
+    TransferExpired { expired_at: Timestamp },
+
+    #[error(
+        "Ownership transfer delay can't be less than {min} seconds and the expiry can't be zero"
+    )]
+    InvalidConfig { min: u64 },
+
+    #[error("Ownership can't be renounced: {reason}")]
+    RenounceNotAllowed { reason: String },
+}
+
+/// Seconds before a pending owner can accept the ownership, and how long they can accept it for
+#[cw_serde]
+pub struct OwnershipConfig {
+    pub transfer_delay: u64,
+    pub transfer_expiry: u64,
+}
+
+impl Default for OwnershipConfig {
+    fn default() -> Self {
+        OwnershipConfig {
+            transfer_delay: 7 * 24 * 60 * 60,
+            transfer_expiry: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// Transfer waiting to be accepted by `owner` between `min_time` and `expiry`
+#[cw_serde]
+pub struct PendingOwnership {
+    pub owner: Addr,
+    pub min_time: Timestamp,
+    pub expiry: Timestamp,
+}
+
+#[cw_serde]
+pub enum OwnershipMsg {
+    /// Proposes a new owner, who has to accept the ownership; callable by the owner
+    TransferOwnership { new_owner: String },
+    /// Accepts the ownership; callable by the pending owner
+    AcceptOwnership {},
+    /// Cancels a pending transfer; callable by the owner
+    RevokeOwnershipTransfer {},
+    /// Sets the transfer delay and expiry; callable by the owner
+    UpdateOwnershipConfig {
+        transfer_delay: Option<u64>,
+        transfer_expiry: Option<u64>,
+    },
+    /// Removes the owner for good, `confirm` has to be the contract address; callable by the owner
+    RenounceOwnership { confirm: String },
+}
+
+#[cw_serde]
+pub struct OwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    /// When the pending owner can accept the ownership
+    pub owner_transfer_min_time: Option<Timestamp>,
+    /// When the pending transfer lapses
+    pub owner_transfer_expiry: Option<Timestamp>,
+    pub ownership_config: OwnershipConfig,
+}
+
+/// Two-step ownership of a contract, stored under the given keys
+pub struct Ownership<'a> {
+    owner: Admin<'a>,
+    pending: Item<'a, PendingOwnership>,
+    config: Item<'a, OwnershipConfig>,
+}
+
+impl<'a> Ownership<'a> {
+    pub const fn new(owner_key: &'a str, pending_key: &'a str, config_key: &'a str) -> Self {
+        Ownership {
+            owner: Admin::new(owner_key),
+            pending: Item::new(pending_key),
+            config: Item::new(config_key),
+        }
+    }
+
+    pub fn owner(&self, deps: Deps) -> StdResult<Option<Addr>> {
+        self.owner.get(deps)
+    }
+
+    pub fn is_owner(&self, deps: Deps, address: &Addr) -> StdResult<bool> {
+        self.owner.is_admin(deps, address)
+    }
+
+    pub fn assert_owner(&self, deps: Deps, address: &Addr) -> Result<(), AdminError> {
+        self.owner.assert_admin(deps, address)
+    }
+
+    pub fn set_owner(&self, deps: DepsMut, owner: Option<Addr>) -> StdResult<()> {
+        self.owner.set(deps, owner)
+    }
+
+    pub fn pending(&self, storage: &dyn Storage) -> StdResult<Option<PendingOwnership>> {
+        self.pending.may_load(storage)
+    }
+
+    pub fn set_pending(
+        &self,
+        storage: &mut dyn Storage,
+        pending: &PendingOwnership,
+    ) -> StdResult<()> {
+        self.pending.save(storage, pending)
+    }
+
+    // Defaults to `OwnershipConfig::default()` if not set
+    pub fn config(&self, storage: &dyn Storage) -> StdResult<OwnershipConfig> {
+        Ok(self.config.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn execute(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: OwnershipMsg,
+    ) -> Result<Response, OwnershipError> {
+        let sender = info.sender.clone();
+        let (action, response) = match msg {
+            OwnershipMsg::TransferOwnership { new_owner } => (
+                "transfer_ownership",
+                self.transfer(deps.branch(), env, info, new_owner)?,
+            ),
+            OwnershipMsg::AcceptOwnership {} => {
+                ("accept_ownership", self.accept(deps.branch(), env, info)?)
+            }
+            OwnershipMsg::RevokeOwnershipTransfer {} => (
+                "revoke_ownership_transfer",
+                self.revoke(deps.branch(), info)?,
+            ),
+            OwnershipMsg::UpdateOwnershipConfig {
+                transfer_delay,
+                transfer_expiry,
+            } => (
+                "update_ownership_config",
+                self.update_config(deps.branch(), info, transfer_delay, transfer_expiry)?,
+            ),
+            OwnershipMsg::RenounceOwnership { confirm } => (
+                "renounce_ownership",
+                self.renounce(deps.branch(), env, info, confirm)?,
+            ),
+        };
+
+        let event = OwnershipEvent {
+            action: action.to_string(),
+            sender,
+            owner: self.owner(deps.as_ref())?,
+            pending_owner: self.pending(deps.storage)?.map(|p| p.owner),
+        };
+        Ok(response.add_event(event.to_event()))
+    }
+
+    pub fn query(&self, deps: Deps) -> StdResult<OwnershipResponse> {
+        let pending = self.pending(deps.storage)?;
+        Ok(OwnershipResponse {
+            owner: self.owner(deps)?,
+            pending_owner: pending.as_ref().map(|p| p.owner.clone()),
+            owner_transfer_min_time: pending.as_ref().map(|p| p.min_time),
+            owner_transfer_expiry: pending.as_ref().map(|p| p.expiry),
+            ownership_config: self.config(deps.storage)?,
+        })
+    }
+
+    // No need to handle case of overwriting the pending owner
+    // Ownership can only be claimed after the transfer delay to mitigate fat finger errors,
+    // and lapses if not claimed within the transfer expiry
+    fn transfer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+    ) -> Result<Response, OwnershipError> {
+        self.assert_owner(deps.as_ref(), &info.sender)?;
+
+        let config = self.config(deps.storage)?;
+        let min_time = env.block.time.plus_seconds(config.transfer_delay);
+        let expiry = min_time.plus_seconds(config.transfer_expiry);
+        self.pending.save(
+            deps.storage,
+            &PendingOwnership {
+                owner: deps.api.addr_validate(&new_owner)?,
+                min_time,
+                expiry,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "transfer_ownership")
+            .add_attribute("new_owner", new_owner)
+            .add_attribute("previous_owner", info.sender)
+            .add_attribute("claimable_at", min_time.seconds().to_string())
+            .add_attribute("expires_at", expiry.seconds().to_string()))
+    }
+
+    fn accept(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, OwnershipError> {
+        let pending = self
+            .pending(deps.storage)?
+            .ok_or(OwnershipError::NoPendingOwner {})?;
+        if pending.min_time > env.block.time {
+            return Err(OwnershipError::TransferNotReady {
+                time_to_claim: pending.min_time,
+            });
+        }
+        if pending.expiry <= env.block.time {
+            return Err(OwnershipError::TransferExpired {
+                expired_at: pending.expiry,
+            });
+        }
+        if pending.owner != info.sender {
+            return Err(OwnershipError::NoPendingOwner {});
+        }
+
+        self.pending.remove(deps.storage);
+        self.set_owner(deps, Some(pending.owner))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("new_owner", info.sender))
+    }
+
+    fn revoke(&self, deps: DepsMut, info: MessageInfo) -> Result<Response, OwnershipError> {
+        self.assert_owner(deps.as_ref(), &info.sender)?;
+
+        self.pending.remove(deps.storage);
+
+        Ok(Response::new().add_attribute("action", "revoke_ownership_transfer"))
+    }
+
+    fn update_config(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        transfer_delay: Option<u64>,
+        transfer_expiry: Option<u64>,
+    ) -> Result<Response, OwnershipError> {
+        self.assert_owner(deps.as_ref(), &info.sender)?;
+
+        let mut config = self.config(deps.storage)?;
+        if let Some(transfer_delay) = transfer_delay {
+            config.transfer_delay = transfer_delay;
+        }
+        if let Some(transfer_expiry) = transfer_expiry {
+            config.transfer_expiry = transfer_expiry;
+        }
+        if config.transfer_delay < MIN_TRANSFER_DELAY || config.transfer_expiry == 0 {
+            return Err(OwnershipError::InvalidConfig {
+                min: MIN_TRANSFER_DELAY,
+            });
+        }
+        self.config.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_ownership_config")
+            .add_attribute("transfer_delay", config.transfer_delay.to_string())
+            .add_attribute("transfer_expiry", config.transfer_expiry.to_string()))
+    }
+
+    fn renounce(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        confirm: String,
+    ) -> Result<Response, OwnershipError> {
+        self.assert_owner(deps.as_ref(), &info.sender)?;
+
+        if confirm != env.contract.address {
+            return Err(OwnershipError::RenounceNotAllowed {
+                reason: "confirm with the contract address".to_string(),
+            });
+        }
+        if self.pending(deps.storage)?.is_some() {
+            return Err(OwnershipError::RenounceNotAllowed {
+                reason: "an ownership transfer is pending".to_string(),
+            });
+        }
+
+        self.set_owner(deps, None)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "renounce_ownership")
+            .add_attribute("previous_owner", info.sender))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    const OWNERSHIP: Ownership = Ownership::new("admin", "pending_ownership", "ownership_config");
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn transfer_ownership(deps: DepsMut) -> Result<Response, OwnershipError> {
+        let msg = OwnershipMsg::TransferOwnership {
+            new_owner: "new_owner".to_string(),
+        };
+        OWNERSHIP.execute(deps, mock_env(), mock_info("owner", &[]), msg)
+    }
+
+    fn accept_ownership(deps: DepsMut, time: Timestamp) -> Result<Response, OwnershipError> {
+        let mut env = mock_env();
+        env.block.time = time;
+        let msg = OwnershipMsg::AcceptOwnership {};
+        OWNERSHIP.execute(deps, env, mock_info("new_owner", &[]), msg)
+    }
+
+    #[test]
+    fn transfer_and_accept() {
+        let mut deps = mock_dependencies();
+        OWNERSHIP
+            .set_owner(deps.as_mut(), Some(Addr::unchecked("owner")))
+            .unwrap();
+
+        let msg = OwnershipMsg::TransferOwnership {
+            new_owner: "new_owner".to_string(),
+        };
+        let res = OWNERSHIP.execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), msg);
+        assert_eq!(res, Err(OwnershipError::Admin(AdminError::NotAdmin {})));
+        transfer_ownership(deps.as_mut()).unwrap();
+
+        let now = mock_env().block.time;
+        let ownership = OWNERSHIP.query(deps.as_ref()).unwrap();
+        assert_eq!(ownership.pending_owner, Some(Addr::unchecked("new_owner")));
+        assert_eq!(
+            ownership.owner_transfer_min_time,
+            Some(now.plus_seconds(7 * DAY))
+        );
+        assert_eq!(
+            ownership.owner_transfer_expiry,
+            Some(now.plus_seconds(14 * DAY))
+        );
+
+        let res = accept_ownership(deps.as_mut(), now);
+        assert_eq!(
+            res,
+            Err(OwnershipError::TransferNotReady {
+                time_to_claim: now.plus_seconds(7 * DAY)
+            })
+        );
+        accept_ownership(deps.as_mut(), now.plus_seconds(7 * DAY)).unwrap();
+
+        let ownership = OWNERSHIP.query(deps.as_ref()).unwrap();
+        assert_eq!(ownership.owner, Some(Addr::unchecked("new_owner")));
+        assert_eq!(ownership.pending_owner, None);
+    }
+
+    #[test]
+    fn transfer_expiry() {
+        let mut deps = mock_dependencies();
+        OWNERSHIP
+            .set_owner(deps.as_mut(), Some(Addr::unchecked("owner")))
+            .unwrap();
+
+        let msg = OwnershipMsg::UpdateOwnershipConfig {
+            transfer_delay: Some(DAY - 1),
+            transfer_expiry: None,
+        };
+        let res = OWNERSHIP.execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert_eq!(
+            res,
+            Err(OwnershipError::InvalidConfig {
+                min: MIN_TRANSFER_DELAY
+            })
+        );
+        let msg = OwnershipMsg::UpdateOwnershipConfig {
+            transfer_delay: Some(DAY),
+            transfer_expiry: Some(DAY),
+        };
+        OWNERSHIP
+            .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg)
+            .unwrap();
+
+        transfer_ownership(deps.as_mut()).unwrap();
+        let expiry = mock_env().block.time.plus_seconds(2 * DAY);
+        let res = accept_ownership(deps.as_mut(), expiry);
+        assert_eq!(
+            res,
+            Err(OwnershipError::TransferExpired { expired_at: expiry })
+        );
+
+        // a revoked transfer can't be accepted
+        transfer_ownership(deps.as_mut()).unwrap();
+        let msg = OwnershipMsg::RevokeOwnershipTransfer {};
+        OWNERSHIP
+            .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg)
+            .unwrap();
+        let res = accept_ownership(deps.as_mut(), mock_env().block.time.plus_seconds(DAY));
+        assert_eq!(res, Err(OwnershipError::NoPendingOwner {}));
+    }
+
+    #[test]
+    fn renounce() {
+        let mut deps = mock_dependencies();
+        OWNERSHIP
+            .set_owner(deps.as_mut(), Some(Addr::unchecked("owner")))
+            .unwrap();
+        let contract = mock_env().contract.address.to_string();
+
+        transfer_ownership(deps.as_mut()).unwrap();
+        let msg = OwnershipMsg::RenounceOwnership {
+            confirm: contract.clone(),
+        };
+        let res = OWNERSHIP.execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            res,
+            Err(OwnershipError::RenounceNotAllowed { .. })
+        ));
+
+        let msg = OwnershipMsg::RevokeOwnershipTransfer {};
+        OWNERSHIP
+            .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg)
+            .unwrap();
+        let msg = OwnershipMsg::RenounceOwnership { confirm: contract };
+        OWNERSHIP
+            .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg)
+            .unwrap();
+        assert_eq!(OWNERSHIP.owner(deps.as_ref()).unwrap(), None);
+    }
+}
@@ -1,5 +1,5 @@
-use cosmwasm_std::{Addr, Uint128};
-use schemars::{JsonSchema, Map};
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
@@ -29,12 +29,23 @@ pub struct Batch {
     pub expected_native_unstaked: Option<Uint128>,
     /// The amount of native tokens received after unbonding
     pub received_native_unstaked: Option<Uint128>,
+    /// Number of unstake requests in this batch
+    pub unstake_requests_count: u64,
+    /// Number of unstake requests withdrawn after the batch was received
+    #[serde(default)]
+    pub withdrawn_requests_count: u64,
+    /// Amount of native tokens withdrawn after the batch was received
+    #[serde(default)]
+    pub withdrawn_native_unstaked: Uint128,
+    /// Amount of native tokens swept after the claim window passed
+    #[serde(default)]
+    pub swept_native_unstaked: Uint128,
+    /// Time the unstaked tokens were received
+    #[serde(default)]
+    pub received_at: Option<u64>,
 
-    pub liquid_unstake_requests: Option<Map<String, LiquidUnstakeRequest>>,
-    pub unstake_requests_count: Option<u64>,
-
-    /// Estimated time when next batch action occurs
-    pub next_batch_action_time: Option<u64>,
+    /// Estimated time when next batch action occurs, kept once the batch is received
+    pub next_batch_action_time: u64,
 
     pub status: BatchStatus,
 }
@@ -46,49 +57,23 @@ impl Batch {
         Self {
             id,
             batch_total_liquid_stake: batch_total,
-            next_batch_action_time: Some(est_next_batch_action),
+            next_batch_action_time: est_next_batch_action,
             status: BatchStatus::Pending,
             expected_native_unstaked: None,
             received_native_unstaked: None,
-            liquid_unstake_requests: None,
-            unstake_requests_count: Some(0),
+            unstake_requests_count: 0,
+            withdrawn_requests_count: 0,
+            withdrawn_native_unstaked: Uint128::zero(),
+            swept_native_unstaked: Uint128::zero(),
+            received_at: None,
         }
     }
+    // Pending: env.block.time + batch period, Submitted: env.block.time + unbonding period,
+    // defined by the caller. Without a next action the previous one is kept
     pub fn update_status(&mut self, new_status: BatchStatus, next_action: Option<u64>) {
-        // Defined by caller - env.block.time + batch period
-        match new_status {
-            BatchStatus::Pending => {
-                self.status = new_status;
-                self.next_batch_action_time = next_action;
-            }
-            // Defined by caller - env.block.time + unbonding period
-            BatchStatus::Submitted => {
-                self.status = new_status;
-                self.next_batch_action_time = next_action;
-            }
-            BatchStatus::Received => {
-                self.status = new_status;
-                self.next_batch_action_time = None;
-            }
-        }
-    }
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
-pub struct LiquidUnstakeRequest {
-    /// The user's address
-    pub user: Addr,
-    /// The user's share in the batch
-    pub shares: Uint128,
-    pub redeemed: bool,
-}
-
-impl LiquidUnstakeRequest {
-    pub fn new(user: Addr, shares: Uint128) -> Self {
-        Self {
-            user,
-            shares,
-            redeemed: false,
+        self.status = new_status;
+        if let Some(next_action) = next_action {
+            self.next_batch_action_time = next_action;
         }
     }
 }
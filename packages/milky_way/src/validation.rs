@@ -0,0 +1,39 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// Config field a value failed to validate for, the contracts turn it into their own
+/// `InvalidConfigField` error
+#[derive(Error, Debug, PartialEq)]
+#[error("Invalid {field}: {reason}")]
+pub struct InvalidField {
+    pub field: String,
+    pub reason: String,
+}
+
+// Names the config field a value failed to validate for
+pub fn invalid_field(field: &str) -> impl Fn(StdError) -> InvalidField + '_ {
+    move |err| InvalidField {
+        field: field.to_string(),
+        reason: match err {
+            StdError::GenericErr { msg, .. } => msg,
+            err => err.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic_errors_keep_their_message() {
+        let err = invalid_field("trader")(StdError::generic_err("Invalid address"));
+        assert_eq!(
+            err,
+            InvalidField {
+                field: "trader".to_string(),
+                reason: "Invalid address".to_string(),
+            }
+        );
+    }
+}